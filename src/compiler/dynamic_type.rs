@@ -19,8 +19,8 @@ use crate::types::TokenType;
 use crate::compiler::number::NumberItem;
 use crate::types::NumberType;
 use super::{DataItem, OperationType, UnaryType};
-use crate::formatter::format_number;
-use crate::tools::do_divition;
+use crate::formatter::{format_number, format_significant};
+use crate::tools::{do_divition, do_modulo};
 
 #[derive(Debug)]
 
@@ -154,7 +154,7 @@ impl DataItem for DynamicTypeItem {
     
     fn calculate(&self, config: &SmartCalcConfig, on_left: bool, other: &dyn DataItem, operation_type: OperationType) -> Option<Rc<dyn DataItem>> {
         let (other_number, is_same_type)  = match other.type_name() {
-            "NUMBER" => (other.get_underlying_number(), false),
+            "NUMBER" | "FRACTION" => (other.get_underlying_number(), false),
             "DYNAMIC_TYPE" => {
                 let other_dynamic_type: &DynamicTypeItem = other.as_any().downcast_ref::<DynamicTypeItem>()?;
                 let (new_number, _) = DynamicTypeItem::convert(config, other_dynamic_type.get_number(), other_dynamic_type.get_type(), self.1.names[0].clone())?;
@@ -179,7 +179,10 @@ impl DataItem for DynamicTypeItem {
                 }
             },
             OperationType::Mul => left * right,
-            OperationType::Sub => left - right
+            OperationType::Sub => left - right,
+            OperationType::Mod => do_modulo(left, right),
+            /* Power of a unit is not the same unit, "3 km ^ 2" is not kilometer */
+            OperationType::Pow => return None
         };
         
         Some(Rc::new(DynamicTypeItem(result, self.1.clone())))
@@ -201,7 +204,10 @@ impl DataItem for DynamicTypeItem {
         let remove_fract_if_zero = self.1.remove_fract_if_zero.map_or(true, |x| x);
         let use_fract_rounding = self.1.use_fract_rounding.map_or(true, |x| x);
 
-        let formated_number = format_number(self.0, config.thousand_separator.to_string(), config.decimal_seperator.to_string(), decimal_digit, remove_fract_if_zero, use_fract_rounding);
+        let formated_number = match config.number_config.significant_figures {
+            0 => format_number(self.0, config.thousand_separator.to_string(), config.decimal_seperator.to_string(), decimal_digit, remove_fract_if_zero, use_fract_rounding),
+            figures => format_significant(self.0, config.thousand_separator.to_string(), config.decimal_seperator.to_string(), figures, remove_fract_if_zero, config.number_config.rounding_mode)
+        };
         self.1.format.replace("{value}", &formated_number)
     }
     fn unary(&self, unary: UnaryType) -> Rc<dyn DataItem> {
@@ -0,0 +1,218 @@
+/*
+ * smartcalc v1.0.8
+ * Copyright (c) Erhan BARIS (Ruslan Ognyanov Asenov)
+ * Licensed under the GNU General Public License v2.0.
+ */
+
+use core::any::{Any, TypeId};
+use core::convert::TryFrom;
+use alloc::rc::Rc;
+use alloc::string::{String, ToString};
+use crate::session::Session;
+use crate::config::SmartCalcConfig;
+use crate::types::{TokenType, NumberType};
+use super::{DataItem, OperationType, UnaryType};
+use super::number::NumberItem;
+use crate::formatter::format_fraction;
+use crate::tools::do_math;
+
+/* Biggest denominator used while converting decimal numbers to fraction */
+const MAX_DENOMINATOR: i128 = 1_000_000;
+
+fn gcd(mut left: i128, mut right: i128) -> i128 {
+    while right != 0 {
+        let remainder = left % right;
+        left = right;
+        right = remainder;
+    }
+    left.abs()
+}
+
+#[derive(Debug)]
+pub struct FractionItem(pub i64, pub i64);
+
+impl FractionItem {
+    /* Fraction is always kept reduced and the sign is stored in the numerator */
+    pub fn new(numerator: i128, denominator: i128) -> Option<FractionItem> {
+        if denominator == 0 {
+            return None;
+        }
+
+        let divisor = gcd(numerator, denominator);
+        let sign = if denominator < 0 { -1 } else { 1 };
+        let numerator = i64::try_from(sign * numerator / divisor).ok()?;
+        let denominator = i64::try_from(sign * denominator / divisor).ok()?;
+        Some(FractionItem(numerator, denominator))
+    }
+
+    /* Continued fraction expansion, the closest fraction with a reasonable denominator is used */
+    pub fn from_number(number: f64) -> Option<FractionItem> {
+        if !number.is_finite() || number.abs() >= i64::MAX as f64 {
+            return None;
+        }
+
+        let (mut previous_numerator, mut numerator) = (0_i128, 1_i128);
+        let (mut previous_denominator, mut denominator) = (1_i128, 0_i128);
+        let mut rest = number;
+
+        loop {
+            let whole = rest.floor();
+            let next_numerator = whole as i128 * numerator + previous_numerator;
+            let next_denominator = whole as i128 * denominator + previous_denominator;
+            if next_denominator > MAX_DENOMINATOR {
+                break;
+            }
+
+            previous_numerator = numerator;
+            previous_denominator = denominator;
+            numerator = next_numerator;
+            denominator = next_denominator;
+
+            let fract = rest - whole;
+            if (numerator as f64 / denominator as f64 - number).abs() <= f64::EPSILON * number.abs().max(1.0) || fract == 0.0 {
+                break;
+            }
+            rest = 1.0 / fract;
+        }
+
+        let fraction = FractionItem::new(numerator, denominator)?;
+        match (fraction.get_underlying_number() - number).abs() <= f64::EPSILON * number.abs().max(1.0) {
+            true => Some(fraction),
+            false => None
+        }
+    }
+
+    pub fn get_numerator(&self) -> i64 { self.0 }
+    pub fn get_denominator(&self) -> i64 { self.1 }
+
+    fn get_fraction(other: &dyn DataItem) -> Option<FractionItem> {
+        match other.as_any().downcast_ref::<FractionItem>() {
+            Some(fraction) => Some(FractionItem(fraction.0, fraction.1)),
+            None => FractionItem::from_number(other.get_underlying_number())
+        }
+    }
+
+    /* None is returned when the result does not fit into the fraction */
+    fn exact_math(left: &FractionItem, right: &FractionItem, operation_type: OperationType) -> Option<FractionItem> {
+        let (left_numerator, left_denominator) = (left.0 as i128, left.1 as i128);
+        let (right_numerator, right_denominator) = (right.0 as i128, right.1 as i128);
+
+        match operation_type {
+            OperationType::Add => FractionItem::new(left_numerator * right_denominator + right_numerator * left_denominator, left_denominator * right_denominator),
+            OperationType::Sub => FractionItem::new(left_numerator * right_denominator - right_numerator * left_denominator, left_denominator * right_denominator),
+            OperationType::Mul => FractionItem::new(left_numerator * right_numerator, left_denominator * right_denominator),
+            OperationType::Div => FractionItem::new(left_numerator * right_denominator, left_denominator * right_numerator),
+            OperationType::Mod => match right_numerator {
+                0 => None,
+                _ => FractionItem::new((left_numerator * right_denominator) % (right_numerator * left_denominator), left_denominator * right_denominator)
+            },
+            OperationType::Pow => {
+                if right_denominator != 1 || right_numerator.abs() > 64 {
+                    return None;
+                }
+
+                let exponent = right_numerator.unsigned_abs() as u32;
+                let numerator = left_numerator.checked_pow(exponent)?;
+                let denominator = left_denominator.checked_pow(exponent)?;
+                match right_numerator < 0 {
+                    true => FractionItem::new(denominator, numerator),
+                    false => FractionItem::new(numerator, denominator)
+                }
+            }
+        }
+    }
+}
+
+impl DataItem for FractionItem {
+    fn as_token_type(&self) -> TokenType {
+        TokenType::Fraction(self.0, self.1)
+    }
+    fn is_same(&self, other: &dyn Any) -> bool {
+        match other.downcast_ref::<(i64, i64)>() {
+            Some((numerator, denominator)) => *numerator == self.0 && *denominator == self.1,
+            None => false
+        }
+    }
+    fn as_any(&self) -> &dyn Any { self }
+    fn calculate(&self, _: &SmartCalcConfig, on_left: bool, other: &dyn DataItem, operation_type: OperationType) -> Option<Rc<dyn DataItem>> {
+        if !matches!(other.type_name(), "FRACTION" | "NUMBER" | "PERCENT") {
+            return None;
+        }
+
+        /* Percent is calculated over the fraction, 1/2 + %50 is 3/4 */
+        let other_number = match other.type_name() {
+            "PERCENT" => other.get_number(self),
+            _ => other.get_underlying_number()
+        };
+
+        let (left, right) = if on_left {
+            (self.get_underlying_number(), other_number)
+        } else {
+            (other_number, self.get_underlying_number())
+        };
+
+        let other_fraction = match other.type_name() {
+            "PERCENT" => FractionItem::from_number(other_number),
+            _ => FractionItem::get_fraction(other)
+        };
+
+        /* Decimal numbers that do not have a fraction representation fallback to floating calculation */
+        let exact = other_fraction.and_then(|other_fraction| match on_left {
+            true => FractionItem::exact_math(self, &other_fraction, operation_type),
+            false => FractionItem::exact_math(&other_fraction, self, operation_type)
+        });
+
+        match exact {
+            Some(fraction) => Some(Rc::new(fraction)),
+            None => Some(Rc::new(NumberItem(do_math(left, right, operation_type), NumberType::Decimal)))
+        }
+    }
+    fn get_number(&self, _: &dyn DataItem) -> f64 { self.get_underlying_number() }
+    fn get_underlying_number(&self) -> f64 { self.0 as f64 / self.1 as f64 }
+    fn type_name(&self) -> &'static str { "FRACTION" }
+    fn type_id(&self) -> TypeId { TypeId::of::<FractionItem>() }
+    fn print(&self, config: &SmartCalcConfig, _: &Session) -> String {
+        format_fraction(self.0, self.1, config.thousand_separator.to_string())
+    }
+    fn unary(&self, unary: UnaryType) -> Rc<dyn DataItem> {
+        match unary {
+            UnaryType::Minus => Rc::new(Self(-self.0, self.1)),
+            UnaryType::Plus => Rc::new(Self(self.0, self.1))
+        }
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn fraction_new_test() {
+    let fraction = FractionItem::new(6, -8).unwrap();
+    assert_eq!((fraction.0, fraction.1), (-3, 4));
+    assert!(FractionItem::new(1, 0).is_none());
+    assert!(FractionItem::new(i64::MAX as i128 + 1, 1).is_none());
+
+    let fraction = FractionItem::from_number(0.75).unwrap();
+    assert_eq!((fraction.0, fraction.1), (3, 4));
+    let fraction = FractionItem::from_number(10.0 / 3.0).unwrap();
+    assert_eq!((fraction.0, fraction.1), (10, 3));
+    let fraction = FractionItem::from_number(-2.5).unwrap();
+    assert_eq!((fraction.0, fraction.1), (-5, 2));
+    assert!(FractionItem::from_number(f64::NAN).is_none());
+}
+
+#[cfg(test)]
+#[test]
+fn fraction_calculate_test() {
+    let config = SmartCalcConfig::default();
+    let session = Session::default();
+    let half = FractionItem(1, 2);
+
+    assert_eq!(half.calculate(&config, true, &FractionItem(1, 3), OperationType::Add).unwrap().print(&config, &session), "5/6".to_string());
+    assert_eq!(half.calculate(&config, true, &FractionItem(1, 3), OperationType::Sub).unwrap().print(&config, &session), "1/6".to_string());
+    assert_eq!(half.calculate(&config, true, &FractionItem(2, 3), OperationType::Mul).unwrap().print(&config, &session), "1/3".to_string());
+    assert_eq!(half.calculate(&config, false, &NumberItem(3.0, NumberType::Decimal), OperationType::Div).unwrap().print(&config, &session), "6".to_string());
+    assert_eq!(half.calculate(&config, true, &NumberItem(3.0, NumberType::Decimal), OperationType::Pow).unwrap().print(&config, &session), "1/8".to_string());
+    assert_eq!(FractionItem(7, 2).calculate(&config, true, &NumberItem(1.0, NumberType::Decimal), OperationType::Mod).unwrap().print(&config, &session), "1/2".to_string());
+
+    let result = half.calculate(&config, true, &NumberItem(2.0_f64.sqrt(), NumberType::Decimal), OperationType::Add).unwrap();
+    assert_eq!(result.type_name(), "NUMBER");
+}
@@ -0,0 +1,213 @@
+/*
+ * smartcalc v1.0.8
+ * Copyright (c) Erhan BARIS (Ruslan Ognyanov Asenov)
+ * Licensed under the GNU General Public License v2.0.
+ */
+
+use core::any::{Any, TypeId};
+use core::convert::TryFrom;
+use alloc::format;
+use alloc::rc::Rc;
+use alloc::string::{ToString, String};
+use crate::session::Session;
+use crate::config::SmartCalcConfig;
+use crate::constants::{XOR_OPERATOR, SHIFT_LEFT_OPERATOR, SHIFT_RIGHT_OPERATOR};
+use crate::error::SmartCalcError;
+use crate::types::{TokenType, NumberType};
+use super::number::NumberItem;
+use super::fraction::FractionItem;
+use super::{DataItem, OperationType, UnaryType};
+use crate::tools::do_math;
+
+pub const BITWISE_OPERATORS: [char; 5] = ['&', '|', XOR_OPERATOR, SHIFT_LEFT_OPERATOR, SHIFT_RIGHT_OPERATOR];
+
+pub fn to_integer(number: f64) -> Option<i128> {
+    match number.is_finite() && number.fract() == 0.0 && number.abs() < i128::MAX as f64 {
+        true => Some(number as i128),
+        false => None
+    }
+}
+
+/* Keeps only the lowest bits of the number, signed numbers use two's complement */
+pub fn wrap_integer(number: i128, bits: u8, signed: bool) -> i128 {
+    if bits == 0 || bits >= 128 {
+        return number;
+    }
+
+    let value = number & ((1_i128 << bits) - 1);
+    match signed && value & (1_i128 << (bits - 1)) != 0 {
+        true => value - (1_i128 << bits),
+        false => value
+    }
+}
+
+fn integer_math(left: i128, right: i128, operation_type: OperationType) -> Option<i128> {
+    match operation_type {
+        OperationType::Add => left.checked_add(right),
+        OperationType::Sub => left.checked_sub(right),
+        OperationType::Mul => left.checked_mul(right),
+        OperationType::Div if right == 0 => Some(0),
+        OperationType::Div => left.checked_div(right),
+        OperationType::Mod if right == 0 => Some(0),
+        OperationType::Mod => left.checked_rem(right),
+        OperationType::Pow => left.checked_pow(u32::try_from(right).ok()?)
+    }
+}
+
+/* Exact value of integers, decimal numbers are used if they do not have fract part */
+pub fn get_integer(item: &dyn DataItem) -> Option<i128> {
+    match item.as_any().downcast_ref::<IntegerItem>() {
+        Some(integer) => Some(integer.0),
+        None => item.as_any().downcast_ref::<NumberItem>().and_then(|number| to_integer(number.0))
+    }
+}
+
+/* Result keeps the number type of the item, decimal numbers stay decimal */
+pub fn with_integer(item: &dyn DataItem, value: i128) -> Rc<dyn DataItem> {
+    match (item.as_any().downcast_ref::<IntegerItem>(), item.as_any().downcast_ref::<NumberItem>()) {
+        (Some(integer), _) => Rc::new(IntegerItem(value, integer.1)),
+        (_, Some(number)) if number.is_integer_type() => Rc::new(IntegerItem(value, number.1)),
+        (_, Some(number)) => Rc::new(NumberItem(value as f64, number.1)),
+        _ => Rc::new(NumberItem(value as f64, NumberType::Decimal))
+    }
+}
+
+pub fn bitwise(config: &SmartCalcConfig, left: &dyn DataItem, right: &dyn DataItem, operator: char) -> Result<Rc<dyn DataItem>, SmartCalcError> {
+    let left_value = get_integer(left).ok_or_else(|| SmartCalcError::InvalidArgument("Bitwise operators require integer numbers".to_string()))?;
+    let right_value = get_integer(right).ok_or_else(|| SmartCalcError::InvalidArgument("Bitwise operators require integer numbers".to_string()))?;
+
+    let result = match operator {
+        '&' => left_value & right_value,
+        '|' => left_value | right_value,
+        XOR_OPERATOR => left_value ^ right_value,
+        SHIFT_LEFT_OPERATOR | SHIFT_RIGHT_OPERATOR => {
+            let shift = match u32::try_from(right_value) {
+                Ok(shift) if shift < 128 => shift,
+                _ => return Err(SmartCalcError::InvalidArgument(format!("{} is not a valid shift amount", right_value)))
+            };
+
+            match operator {
+                SHIFT_LEFT_OPERATOR => left_value << shift,
+                _ => left_value >> shift
+            }
+        },
+        _ => return Err(SmartCalcError::UnknownOperator(operator))
+    };
+
+    Ok(with_integer(left, config.integer_config.wrap(result)))
+}
+
+pub fn bitwise_not(config: &SmartCalcConfig, item: &dyn DataItem) -> Result<Rc<dyn DataItem>, SmartCalcError> {
+    match get_integer(item) {
+        Some(value) => Ok(with_integer(item, config.integer_config.wrap(!value))),
+        None => Err(SmartCalcError::InvalidArgument("Bitwise operators require integer numbers".to_string()))
+    }
+}
+
+/// Binary, octal and hexadecimal numbers, the value is kept exact for all 64 bits.
+#[derive(Debug)]
+pub struct IntegerItem(pub i128, pub NumberType);
+
+impl IntegerItem {
+    /* Negative numbers are printed with two's complement of the integer width */
+    fn integer_pattern(&self, config: &SmartCalcConfig) -> (&'static str, u128) {
+        match (self.0 < 0, config.integer_config.bits) {
+            (true, 0) => ("-", self.0.unsigned_abs()),
+            (true, bits) if bits < 128 => ("", self.0 as u128 & ((1_u128 << bits) - 1)),
+            _ => ("", self.0 as u128)
+        }
+    }
+}
+
+impl DataItem for IntegerItem {
+    fn as_token_type(&self) -> TokenType {
+        TokenType::Integer(self.0, self.1)
+    }
+    fn is_same(&self, other: &dyn Any) -> bool {
+        match other.downcast_ref::<i128>() {
+            Some(value) => *value == self.0,
+            None => false
+        }
+    }
+    fn as_any(&self) -> &dyn Any { self }
+    fn calculate(&self, config: &SmartCalcConfig, on_left: bool, other: &dyn DataItem, operation_type: OperationType) -> Option<Rc<dyn DataItem>> {
+        /* Fraction keeps the result exact */
+        if TypeId::of::<FractionItem>() == other.type_id() {
+            return other.calculate(config, !on_left, self, operation_type);
+        }
+
+        let other_number = match other.type_name() {
+            "NUMBER" => other.get_underlying_number(),
+            "PERCENT" => other.get_number(self),
+            _ => return None
+        };
+
+        let other_integer = match other.as_any().downcast_ref::<IntegerItem>() {
+            Some(integer) => Some(integer.0),
+            None => to_integer(other_number)
+        };
+
+        if let Some(other_integer) = other_integer {
+            let (left, right) = if on_left { (self.0, other_integer) } else { (other_integer, self.0) };
+            if let Some(result) = integer_math(left, right, operation_type) {
+                return Some(Rc::new(IntegerItem(config.integer_config.wrap(result), self.1)));
+            }
+        }
+
+        /* Float is used if the result does not fit or the other number has fract part, the result is rounded to keep the number type */
+        let (left, right) = if on_left { (self.0 as f64, other_number) } else { (other_number, self.0 as f64) };
+        let result = do_math(left, right, operation_type);
+        match to_integer(result.round()) {
+            Some(result) => Some(Rc::new(IntegerItem(config.integer_config.wrap(result), self.1))),
+
+            /* Too big for an integer, calculation fails with overflow */
+            None => Some(Rc::new(NumberItem(f64::INFINITY, NumberType::Decimal)))
+        }
+    }
+    fn get_number(&self, _: &dyn DataItem) -> f64 { self.0 as f64 }
+    fn get_underlying_number(&self) -> f64 { self.0 as f64 }
+    fn type_name(&self) -> &'static str { "NUMBER" }
+    fn type_id(&self) -> TypeId { TypeId::of::<IntegerItem>() }
+    fn print(&self, config: &SmartCalcConfig, session: &Session) -> String {
+        let (sign, pattern) = self.integer_pattern(config);
+        match self.1 {
+            NumberType::Binary      => format!("{}{:#b}", sign, pattern),
+            NumberType::Octal       => format!("{}{:#o}", sign, pattern),
+            NumberType::Hexadecimal => format!("{}{:#X}", sign, pattern),
+            _ => NumberItem(self.0 as f64, self.1).print(config, session)
+        }
+    }
+    fn unary(&self, unary: UnaryType) -> Rc<dyn DataItem> {
+        match unary {
+            UnaryType::Minus => Rc::new(Self(self.0.wrapping_neg(), self.1)),
+            UnaryType::Plus => Rc::new(Self(self.0, self.1))
+        }
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn wrap_integer_test() {
+    assert_eq!(wrap_integer(255, 8, true), -1);
+    assert_eq!(wrap_integer(255, 8, false), 255);
+    assert_eq!(wrap_integer(256, 8, false), 0);
+    assert_eq!(wrap_integer(-5, 8, false), 251);
+    assert_eq!(wrap_integer(0x1_0000_0000, 32, false), 0);
+    assert_eq!(wrap_integer(-5, 0, false), -5);
+}
+
+#[cfg(test)]
+#[test]
+fn integer_print_test() {
+    let mut config = SmartCalcConfig::default();
+    let session = Session::default();
+
+    assert_eq!(IntegerItem(-1, NumberType::Hexadecimal).print(&config, &session), "0xFFFFFFFFFFFFFFFF".to_string());
+    assert_eq!(IntegerItem(0x1234567890ABCDEF, NumberType::Hexadecimal).print(&config, &session), "0x1234567890ABCDEF".to_string());
+
+    config.integer_config.bits = 8;
+    assert_eq!(IntegerItem(-5, NumberType::Binary).print(&config, &session), "0b11111011".to_string());
+
+    config.integer_config.bits = 0;
+    assert_eq!(IntegerItem(-255, NumberType::Hexadecimal).print(&config, &session), "-0xFF".to_string());
+}
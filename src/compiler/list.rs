@@ -0,0 +1,70 @@
+/*
+ * smartcalc v1.0.8
+ * Copyright (c) Erhan BARIS (Ruslan Ognyanov Asenov)
+ * Licensed under the GNU General Public License v2.0.
+ */
+
+use core::any::{Any, TypeId};
+use alloc::rc::Rc;
+use alloc::vec::Vec;
+use alloc::string::String;
+use crate::session::Session;
+use crate::config::SmartCalcConfig;
+use crate::types::TokenType;
+use super::{DataItem, OperationType, UnaryType};
+
+/* Values written with comma, "12, 15, 20 usd". Only statistics functions can use the list */
+#[derive(Debug)]
+pub struct ListItem(pub Vec<Rc<dyn DataItem>>);
+
+impl ListItem {
+    pub fn get_items(&self) -> &[Rc<dyn DataItem>] {
+        &self.0
+    }
+}
+
+impl DataItem for ListItem {
+    fn as_token_type(&self) -> TokenType {
+        TokenType::List(self.0.clone())
+    }
+    fn is_same(&self, other: &dyn Any) -> bool {
+        match other.downcast_ref::<Vec<Rc<dyn DataItem>>>() {
+            Some(items) => items.len() == self.0.len() && items.iter().zip(self.0.iter()).all(|(left, right)| left.type_name() == right.type_name() && (left.get_underlying_number() - right.get_underlying_number()).abs() < f64::EPSILON),
+            None => false
+        }
+    }
+    fn as_any(&self) -> &dyn Any { self }
+    fn calculate(&self, _: &SmartCalcConfig, _: bool, _: &dyn DataItem, _: OperationType) -> Option<Rc<dyn DataItem>> {
+        None
+    }
+    fn get_number(&self, _: &dyn DataItem) -> f64 { self.get_underlying_number() }
+    fn get_underlying_number(&self) -> f64 { self.0.len() as f64 }
+    fn type_name(&self) -> &'static str { "LIST" }
+    fn type_id(&self) -> TypeId { TypeId::of::<ListItem>() }
+    fn print(&self, config: &SmartCalcConfig, session: &Session) -> String {
+        /* Comma is not clear if it is also the decimal seperator */
+        let seperator = match config.decimal_seperator.as_str() {
+            "," => "; ",
+            _ => ", "
+        };
+
+        self.0.iter().map(|item| item.print(config, session)).collect::<Vec<_>>().join(seperator)
+    }
+    fn unary(&self, unary: UnaryType) -> Rc<dyn DataItem> {
+        Rc::new(Self(self.0.iter().map(|item| item.unary(unary)).collect()))
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn list_print_test() {
+    use alloc::string::ToString;
+    use super::number::NumberItem;
+    use crate::types::NumberType;
+    let config = SmartCalcConfig::default();
+    let session = Session::default();
+    let list = ListItem(alloc::vec![Rc::new(NumberItem(1.5, NumberType::Decimal)), Rc::new(NumberItem(-2.0, NumberType::Decimal))]);
+
+    assert_eq!(list.print(&config, &session), "1,50; -2".to_string());
+    assert_eq!(list.unary(UnaryType::Minus).print(&config, &session), "-1,50; 2".to_string());
+}
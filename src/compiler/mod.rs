@@ -9,23 +9,32 @@ use core::any::TypeId;
 use core::ops::Deref;
 
 use alloc::rc::Rc;
+use alloc::vec::Vec;
 use alloc::string::String;
 use alloc::string::ToString;
 use alloc::format;
 
 use crate::session::Session;
+use crate::error::SmartCalcError;
+use crate::compiler::money::get_money_item;
+use crate::compiler::integer::{BITWISE_OPERATORS, bitwise, bitwise_not};
 use crate::config::SmartCalcConfig;
 use crate::types::*;
 use crate::variable::VariableInfo;
 
 pub mod number;
+pub mod integer;
 pub mod percent;
+pub mod fraction;
 pub mod money;
 pub mod time;
 pub mod duration;
 pub mod date;
 pub mod date_time;
 pub mod dynamic_type;
+pub mod words;
+pub mod list;
+pub mod text;
 
 #[derive(Clone)]
 #[derive(Copy)]
@@ -33,7 +42,9 @@ pub enum OperationType {
     Add,
     Div,
     Mul,
-    Sub
+    Sub,
+    Mod,
+    Pow
 }
 
 
@@ -60,11 +71,11 @@ pub trait DataItem: alloc::fmt::Debug {
 pub struct Interpreter;
 
 impl Interpreter {
-    pub fn execute(config: &SmartCalcConfig, ast: Rc<SmartCalcAstType>, session: &Session) -> Result<Rc<SmartCalcAstType>, String> {
+    pub fn execute(config: &SmartCalcConfig, ast: Rc<SmartCalcAstType>, session: &Session) -> Result<Rc<SmartCalcAstType>, SmartCalcError> {
         Interpreter::execute_ast(config, session, ast)
     }
 
-    fn execute_ast(config: &SmartCalcConfig, session: &Session, ast: Rc<SmartCalcAstType>) -> Result<Rc<SmartCalcAstType>, String> {
+    fn execute_ast(config: &SmartCalcConfig, session: &Session, ast: Rc<SmartCalcAstType>) -> Result<Rc<SmartCalcAstType>, SmartCalcError> {
         match ast.deref() {
             SmartCalcAstType::Binary { left, operator, right } => Interpreter::executer_binary(config, session, left.clone(), *operator, right.clone()),
             SmartCalcAstType::Assignment { variable, expression } => Interpreter::executer_assignment(config, session, variable.clone(), expression.clone()),
@@ -72,6 +83,7 @@ impl Interpreter {
             SmartCalcAstType::Item(_)                          => Ok(ast),
             SmartCalcAstType::Month(_)                         => Ok(ast),
             SmartCalcAstType::PrefixUnary(ch, ast)             => Interpreter::executer_unary(config, session, *ch, ast.clone()),
+            SmartCalcAstType::Call { name, args }              => Interpreter::executer_call(config, session, name, args),
             SmartCalcAstType::None                             => Ok(Rc::new(SmartCalcAstType::None)),
             _ => {
                 log::debug!("Operation not implemented {:?}", ast);
@@ -84,57 +96,147 @@ impl Interpreter {
         variable.data.borrow().clone()
     }
 
-    fn executer_assignment(config: &SmartCalcConfig, session: &Session, variable: Rc<VariableInfo>, expression: Rc<SmartCalcAstType>) -> Result<Rc<SmartCalcAstType>, String> {
+    fn executer_assignment(config: &SmartCalcConfig, session: &Session, variable: Rc<VariableInfo>, expression: Rc<SmartCalcAstType>) -> Result<Rc<SmartCalcAstType>, SmartCalcError> {
         let computed  = Interpreter::execute_ast(config, session, expression)?;
         *variable.data.borrow_mut() = computed.clone();
         Ok(computed)
     }
     
-    fn calculate_item(config: &SmartCalcConfig, operator: char, left: Rc<SmartCalcAstType>, right: Rc<SmartCalcAstType>) -> Result<Rc<SmartCalcAstType>, String> {
-        let left = match left.deref() {
-            SmartCalcAstType::Item(left) => left.clone(),
-            _ => return Err("Unknown calculation".to_string())
+    fn check_currency_rates(config: &SmartCalcConfig, left: &dyn DataItem, right: &dyn DataItem) -> Result<(), SmartCalcError> {
+        let (left, right) = match (get_money_item(left), get_money_item(right)) {
+            (Some(left), Some(right)) if left.get_currency() != right.get_currency() => (left, right),
+            _ => return Ok(())
         };
-        
-        let right = match right.deref() {
-            SmartCalcAstType::Item(right) => right.clone(),
-            _ => return Err("Unknown calculation".to_string())
+
+        for currency in [left.get_currency(), right.get_currency()].iter() {
+            if !config.currency_rate.contains_key(currency) {
+                return Err(SmartCalcError::MissingCurrencyRate(currency.code.to_string()));
+            }
+        }
+
+        Ok(())
+    }
+    
+    /* Rates used to convert money to the currency of the result are kept for the line */
+    fn add_currency_conversions(config: &SmartCalcConfig, session: &Session, result: &dyn DataItem, items: &[Rc<dyn DataItem>]) {
+        let currency = match get_money_item(result) {
+            Some(money) => money.get_currency(),
+            None => return
+        };
+
+        for item in items.iter() {
+            match (get_money_item(item.deref()), item.as_any().downcast_ref::<list::ListItem>()) {
+                (Some(money), _) if money.get_currency() != currency => {
+                    if let Some(conversion) = config.get_currency_conversion(&money.get_currency(), &currency) {
+                        session.add_currency_conversion(conversion);
+                    }
+                },
+                (_, Some(list)) => Interpreter::add_currency_conversions(config, session, result, list.get_items()),
+                _ => ()
+            };
+        }
+    }
+
+    fn calculate_item(config: &SmartCalcConfig, operator: char, left: Rc<SmartCalcAstType>, right: Rc<SmartCalcAstType>) -> Result<Rc<SmartCalcAstType>, SmartCalcError> {
+        let incompatible_types = || SmartCalcError::IncompatibleTypes { operator, left: left.type_name(), right: right.type_name() };
+
+        let (left_item, right_item) = match (left.deref(), right.deref()) {
+            (SmartCalcAstType::Item(left_item), SmartCalcAstType::Item(right_item)) => (left_item.clone(), right_item.clone()),
+            _ => return Err(incompatible_types())
         };
         
-        let result = match operator {
-            '+' => left.calculate(config, true, right.deref(), OperationType::Add),
-            '-' => left.calculate(config, true, right.deref(), OperationType::Sub),
-            '*' => left.calculate(config, true, right.deref(), OperationType::Mul),
-            '/' => left.calculate(config, true, right.deref(), OperationType::Div),
-            _ => return Err(format!("Unknown operator. ({})", operator))
+        /* Bitwise operators only work with numbers */
+        if BITWISE_OPERATORS.contains(&operator) {
+            return match (left_item.type_name(), right_item.type_name()) {
+                ("NUMBER", "NUMBER") => Ok(Rc::new(SmartCalcAstType::Item(bitwise(config, left_item.deref(), right_item.deref(), operator)?))),
+                _ => Err(incompatible_types())
+            };
+        }
+
+        let operation_type = match operator {
+            '+' => OperationType::Add,
+            '-' => OperationType::Sub,
+            '*' => OperationType::Mul,
+            '/' => OperationType::Div,
+            '%' => OperationType::Mod,
+            '^' => OperationType::Pow,
+            _ => return Err(SmartCalcError::UnknownOperator(operator))
         };
+
+        Interpreter::check_currency_rates(config, left_item.deref(), right_item.deref())?;
         
-        match result {
-            Some(item) => Ok(Rc::new(SmartCalcAstType::Item(item.clone()))),
-            None => Err("Unknown calculation".to_string())
+        let item = match left_item.calculate(config, true, right_item.deref(), operation_type) {
+            Some(item) => item,
+            None => return Err(incompatible_types())
+        };
+
+        /* Divition helpers return zero instead of infinity, so divisor is checked after the calculation */
+        if let (OperationType::Div, 0.0) | (OperationType::Mod, 0.0) = (operation_type, right_item.get_underlying_number()) {
+            return Err(SmartCalcError::DivisionByZero);
+        }
+
+        /* Root of a negative number is not a real number, "(-8) ^ (1/3)" */
+        let result = item.get_underlying_number();
+        if result.is_nan() {
+            return Err(SmartCalcError::InvalidArgument("Result is not a real number".to_string()));
+        }
+
+        match result.is_infinite() {
+            true => Err(SmartCalcError::Overflow),
+            false => Ok(Rc::new(SmartCalcAstType::Item(item)))
         }
     }
 
-    fn executer_binary(config: &SmartCalcConfig, session: &Session, left: Rc<SmartCalcAstType>, operator: char, right: Rc<SmartCalcAstType>) -> Result<Rc<SmartCalcAstType>, String> {
+    fn executer_binary(config: &SmartCalcConfig, session: &Session, left: Rc<SmartCalcAstType>, operator: char, right: Rc<SmartCalcAstType>) -> Result<Rc<SmartCalcAstType>, SmartCalcError> {
         let computed_left  = Interpreter::execute_ast(config, session, left)?;
         let computed_right = Interpreter::execute_ast(config, session, right)?;
 
-        match (computed_left.deref(), computed_right.deref()) {
-            (SmartCalcAstType::Item(_), _)           | (_, SmartCalcAstType::Item(_))           => Interpreter::calculate_item(config, operator, computed_left.clone(), computed_right.clone()),
-            _ => Err("Uknown calculation result".to_string())
+        let result = match (computed_left.deref(), computed_right.deref()) {
+            (SmartCalcAstType::Item(_), _)           | (_, SmartCalcAstType::Item(_))           => Interpreter::calculate_item(config, operator, computed_left.clone(), computed_right.clone())?,
+            _ => return Err(SmartCalcError::IncompatibleTypes { operator, left: computed_left.type_name(), right: computed_right.type_name() })
+        };
+
+        if let (SmartCalcAstType::Item(item), SmartCalcAstType::Item(left), SmartCalcAstType::Item(right)) = (result.deref(), computed_left.deref(), computed_right.deref()) {
+            Interpreter::add_currency_conversions(config, session, item.deref(), &[left.clone(), right.clone()]);
         }
+
+        Ok(result)
+    }
+
+    fn executer_call(config: &SmartCalcConfig, session: &Session, name: &str, args: &[Rc<SmartCalcAstType>]) -> Result<Rc<SmartCalcAstType>, SmartCalcError> {
+        let function = match config.functions.get(name) {
+            Some(function) => function,
+            None => return Err(SmartCalcError::FunctionNotFound(name.to_string()))
+        };
+
+        let mut arguments = Vec::with_capacity(args.len());
+        for arg in args.iter() {
+            let computed = Interpreter::execute_ast(config, session, arg.clone())?;
+            match computed.deref() {
+                SmartCalcAstType::Item(item) => arguments.push(item.clone()),
+                _ => return Err(SmartCalcError::InvalidArgument(format!("Invalid argument for {}", name)))
+            };
+        }
+
+        let result = function.call(config, session, &arguments)?;
+        Interpreter::add_currency_conversions(config, session, result.deref(), &arguments);
+        Ok(Rc::new(SmartCalcAstType::Item(result)))
     }
 
-    fn executer_unary(config: &SmartCalcConfig, session: &Session, operator: char, ast: Rc<SmartCalcAstType>) -> Result<Rc<SmartCalcAstType>, String> {
+    fn executer_unary(config: &SmartCalcConfig, session: &Session, operator: char, ast: Rc<SmartCalcAstType>) -> Result<Rc<SmartCalcAstType>, SmartCalcError> {
         let computed = Interpreter::execute_ast(config, session, ast)?;
 
         let result = match operator {
             '+' => return Ok(computed),
             '-' => match computed.deref() {
                 SmartCalcAstType::Item(item) => SmartCalcAstType::Item(item.unary(UnaryType::Minus)),
-                _ => return Err("Syntax error".to_string())
+                _ => return Err(SmartCalcError::UnsupportedExpression(computed.type_name()))
+            },
+            '~' => match computed.deref() {
+                SmartCalcAstType::Item(item) if item.type_name() == "NUMBER" => SmartCalcAstType::Item(bitwise_not(config, item.deref())?),
+                _ => return Err(SmartCalcError::UnsupportedExpression(computed.type_name()))
             },
-            _ => return Err("Syntax error".to_string())
+            _ => return Err(SmartCalcError::UnknownOperator(operator))
         };
 
         Ok(Rc::new(result))
@@ -11,13 +11,13 @@ use alloc::string::ToString;
 use alloc::string::String;
 use core::ops::Deref;
 use crate::session::Session;
-use crate::config::SmartCalcConfig;
+use crate::config::{SmartCalcConfig, CurrencyDisplay, NegativeStyle};
 use crate::types::{CurrencyInfo, TokenType, NumberType};
 
 use super::number::NumberItem;
 use super::{DataItem, OperationType, UnaryType};
-use crate::formatter::format_number;
-use crate::tools::do_divition;
+use crate::formatter::format_number_with_rounding;
+use crate::tools::{convert_amount, do_math, round_converted_price, round_number};
 
 #[derive(Debug)]
 
@@ -33,14 +33,45 @@ impl MoneyItem {
     }
     
     fn convert_currency(&self, config: &SmartCalcConfig, left: &MoneyItem) -> f64 {
-        let as_usd = match config.currency_rate.get(&left.get_currency()) {
-            Some(l_rate) => do_divition(left.get_price(), *l_rate),
+        match (config.currency_rate.get(&left.get_currency()), config.currency_rate.get(&self.get_currency())) {
+            (Some(l_rate), Some(r_rate)) => round_converted_price(config, convert_amount(left.get_price(), *l_rate, *r_rate), &self.get_currency()),
             _ => 0.0
+        }
+    }
+
+    /* Display style of the value is used instead of the configuration for "10 usd as code" */
+    fn format(&self, config: &SmartCalcConfig, session: &Session, display: CurrencyDisplay) -> String {
+        let currency = self.get_currency();
+        let is_accounting = config.money_config.negative_style == NegativeStyle::Accounting && self.get_price() < 0.0;
+        let price = if is_accounting { -self.get_price() } else { self.get_price() };
+        let formated_price = format_number_with_rounding(price, config.thousand_separator.to_string(), config.decimal_seperator.to_string(), currency.decimal_digits, config.money_config.remove_fract_if_zero, config.money_config.use_fract_rounding, config.money_config.rounding_mode);
+
+        let (symbol, symbol_on_left, space) = match display {
+            CurrencyDisplay::Symbol => (currency.symbol.to_string(), currency.symbol_on_left, currency.space_between_amount_and_symbol),
+            CurrencyDisplay::NarrowSymbol => (currency.narrow_symbol().to_string(), currency.symbol_on_left, currency.space_between_amount_and_symbol),
+            CurrencyDisplay::Code => (currency.code.to_string(), currency.symbol_on_left, true),
+            CurrencyDisplay::Name => {
+                /* Singular is used only for exactly one, "1,50 euros" */
+                let is_one = round_number(price.abs(), currency.decimal_digits, config.money_config.rounding_mode) == 1.0;
+                let name = config.json_data.languages.get(&session.get_language())
+                    .and_then(|language| language.number_words.currencies.get(&currency.code))
+                    .map(|words| if is_one { words.singular.to_string() } else { words.plural.to_string() })
+                    .unwrap_or_else(|| currency.code.to_string());
+                (name, false, true)
+            }
         };
-    
-        match config.currency_rate.get(&self.get_currency()) {
-            Some(r_rate) => as_usd * r_rate,
-            _ => 0.0
+
+        let text = match (symbol_on_left, space) {
+            (true, true) => format!("{} {}", symbol, formated_price),
+            (true, false) => format!("{}{}", symbol, formated_price),
+            (false, true) => format!("{} {}", formated_price, symbol),
+            (false, false) => format!("{}{}", formated_price, symbol),
+        };
+
+        /* Rounded to zero is not negative anymore */
+        match is_accounting && formated_price.chars().any(|item| ('1'..='9').contains(&item)) {
+            true => format!("({})", text),
+            false => text
         }
     }
 }
@@ -60,8 +91,8 @@ impl DataItem for MoneyItem {
     fn calculate(&self, config: &SmartCalcConfig, on_left: bool, other: &dyn DataItem, operation_type: OperationType) -> Option<Rc<dyn DataItem>> {
         /* If both item is money and current money is on left side, skip calculation */
         let (other_amount, target_curreny, is_other_money)  = match other.type_name() {
-            "NUMBER" => (other.get_underlying_number(), self.1.clone(), false),
-            "MONEY" => (self.convert_currency(config, other.as_any().downcast_ref::<MoneyItem>()?), self.1.clone(), true),
+            "NUMBER" | "FRACTION" => (other.get_underlying_number(), self.1.clone(), false),
+            "MONEY" => (self.convert_currency(config, &get_money_item(other)?), self.1.clone(), true),
             "PERCENT" => (other.get_number(self), self.1.clone(), false),
             "DURATION" => (other.get_number(self), self.1.clone(), false),
             _ => return None
@@ -74,16 +105,16 @@ impl DataItem for MoneyItem {
         };
         
         let result = match operation_type {
-            OperationType::Add => left + right,
             OperationType::Div => {
-                let div_result = do_divition(left, right);
+                let div_result = do_math(left, right, operation_type);
                 match is_other_money {
                     true => return Some(Rc::new(NumberItem(div_result, NumberType::Decimal))),
                     false => div_result
                 }
             },
-            OperationType::Mul => left * right,
-            OperationType::Sub => left - right
+            /* Power of money is not money, "10 usd ^ 2" */
+            OperationType::Pow => return None,
+            _ => do_math(left, right, operation_type)
         };
         Some(Rc::new(MoneyItem(result, target_curreny)))
     }
@@ -93,21 +124,14 @@ impl DataItem for MoneyItem {
            return self.0 
        }
        
-       other.get_underlying_number() * self.0
+       do_math(other.get_underlying_number(), self.0, OperationType::Mul)
     }
     
     fn get_underlying_number(&self) -> f64 { self.0 }
     fn type_name(&self) -> &'static str { "MONEY" }
     fn type_id(&self) -> TypeId { TypeId::of::<MoneyItem>() }
-    fn print(&self, config: &SmartCalcConfig, _: &Session) -> String {
-        let currency = self.get_currency();
-        let formated_price = format_number(self.get_price(), config.thousand_separator.to_string(), config.decimal_seperator.to_string(), currency.decimal_digits, config.money_config.remove_fract_if_zero, config.money_config.use_fract_rounding);
-        match (currency.symbol_on_left, currency.space_between_amount_and_symbol) {
-            (true, true) => format!("{} {}", currency.symbol, formated_price),
-            (true, false) => format!("{}{}", currency.symbol, formated_price),
-            (false, true) => format!("{} {}", formated_price, currency.symbol),
-            (false, false) => format!("{}{}", formated_price, currency.symbol),
-        }
+    fn print(&self, config: &SmartCalcConfig, session: &Session) -> String {
+        self.format(config, session, config.money_config.display)
     }
     fn unary(&self, unary: UnaryType) -> Rc<dyn DataItem> {
         match unary {
@@ -117,6 +141,52 @@ impl DataItem for MoneyItem {
     }
 }
 
+/* Money and money with its own display style are the same for calculations */
+pub fn get_money_item(item: &dyn DataItem) -> Option<MoneyItem> {
+    match item.as_any().downcast_ref::<DisplayMoneyItem>() {
+        Some(money) => Some(money.get_item()),
+        None => item.as_any().downcast_ref::<MoneyItem>().map(|money| MoneyItem(money.0, money.1.clone()))
+    }
+}
+
+/* "10 usd as code", display style is kept with the value and calculations with it */
+#[derive(Debug)]
+pub struct DisplayMoneyItem(pub f64, pub Rc<CurrencyInfo>, pub CurrencyDisplay);
+
+impl DisplayMoneyItem {
+    pub fn get_item(&self) -> MoneyItem {
+        MoneyItem(self.0, self.1.clone())
+    }
+}
+
+impl DataItem for DisplayMoneyItem {
+    fn as_token_type(&self) -> TokenType {
+        TokenType::DisplayMoney(self.0, self.1.clone(), self.2)
+    }
+    fn is_same(&self, other: &dyn Any) -> bool { self.get_item().is_same(other) }
+    fn as_any(&self) -> &dyn Any { self }
+    fn calculate(&self, config: &SmartCalcConfig, on_left: bool, other: &dyn DataItem, operation_type: OperationType) -> Option<Rc<dyn DataItem>> {
+        let result = self.get_item().calculate(config, on_left, other, operation_type)?;
+        match result.as_any().downcast_ref::<MoneyItem>() {
+            Some(money) => Some(Rc::new(DisplayMoneyItem(money.0, money.1.clone(), self.2))),
+            None => Some(result)
+        }
+    }
+    fn get_number(&self, other: &dyn DataItem) -> f64 { self.get_item().get_number(other) }
+    fn get_underlying_number(&self) -> f64 { self.0 }
+    fn type_name(&self) -> &'static str { "MONEY" }
+    fn type_id(&self) -> TypeId { TypeId::of::<DisplayMoneyItem>() }
+    fn print(&self, config: &SmartCalcConfig, session: &Session) -> String {
+        self.get_item().format(config, session, self.2)
+    }
+    fn unary(&self, unary: UnaryType) -> Rc<dyn DataItem> {
+        match unary {
+            UnaryType::Minus => Rc::new(Self(-self.0, self.1.clone(), self.2)),
+            UnaryType::Plus => Rc::new(Self(self.0, self.1.clone(), self.2))
+        }
+    }
+}
+
 
 #[cfg(test)]
 #[test]
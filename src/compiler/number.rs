@@ -12,13 +12,21 @@ use crate::session::Session;
 use crate::config::SmartCalcConfig;
 use crate::types::{TokenType, NumberType};
 use super::percent::PercentItem;
+use super::fraction::FractionItem;
+use super::integer::{IntegerItem, to_integer};
 use super::{DataItem, OperationType, UnaryType};
-use crate::formatter::format_number;
-use crate::tools::do_divition;
+use crate::formatter::{format_number_with_rounding, format_significant, format_scientific, format_engineering};
+use crate::tools::do_math;
 
 #[derive(Debug)]
 
 pub struct NumberItem(pub f64, pub NumberType);
+
+impl NumberItem {
+    pub fn is_integer_type(&self) -> bool {
+        matches!(self.1, NumberType::Binary | NumberType::Octal | NumberType::Hexadecimal)
+    }
+}
 impl DataItem for NumberItem {
     fn as_token_type(&self) -> TokenType {
         TokenType::Number(self.0, self.1)
@@ -30,8 +38,13 @@ impl DataItem for NumberItem {
         }
     }
     fn as_any(&self) -> &dyn Any { self }
-    fn calculate(&self, _: &SmartCalcConfig, on_left: bool, other: &dyn DataItem, operation_type: OperationType) -> Option<Rc<dyn DataItem>> {
-        let other_number  = if TypeId::of::<NumberItem>() == other.type_id() { 
+    fn calculate(&self, config: &SmartCalcConfig, on_left: bool, other: &dyn DataItem, operation_type: OperationType) -> Option<Rc<dyn DataItem>> {
+        /* Fraction keeps the result exact */
+        if TypeId::of::<FractionItem>() == other.type_id() {
+            return other.calculate(config, !on_left, self, operation_type);
+        }
+
+        let other_number  = if TypeId::of::<NumberItem>() == other.type_id() || TypeId::of::<IntegerItem>() == other.type_id() { 
             other.get_underlying_number()
             
         } else if TypeId::of::<PercentItem>() == other.type_id() { 
@@ -41,31 +54,42 @@ impl DataItem for NumberItem {
             return None;
         };
         
+        /* Conversion on the right side is used for the whole calculation, "2^63 to hex" is calculated as integer */
+        if self.1 == NumberType::Decimal {
+            match (other.as_any().downcast_ref::<IntegerItem>(), other.as_any().downcast_ref::<NumberItem>()) {
+                (Some(integer), _) if integer.1 != NumberType::Decimal => return integer.calculate(config, !on_left, self, operation_type),
+                (_, Some(number)) if number.1 != NumberType::Decimal => return number.calculate(config, !on_left, self, operation_type),
+                _ => ()
+            };
+        }
+
+        /* Binary, octal and hexadecimal numbers use integer calculation */
+        if let (true, Some(number)) = (self.is_integer_type(), to_integer(self.0.round())) {
+            return IntegerItem(number, self.1).calculate(config, on_left, other, operation_type);
+        }
+
         let (left, right) = if on_left { 
             (self.0, other_number) 
         } else { 
             (other_number, self.0 ) 
         };
-        
-        let result = match operation_type {
-            OperationType::Add => left + right,
-            OperationType::Div => do_divition(left, right),
-            OperationType::Mul => left * right,
-            OperationType::Sub => left - right
-        };
-        Some(Rc::new(NumberItem(result, self.1)))
+
+        Some(Rc::new(NumberItem(do_math(left, right, operation_type), self.1)))
     }
     fn get_number(&self, _: &dyn DataItem) -> f64 { self.0 }
     fn get_underlying_number(&self) -> f64 { self.0 }
     fn type_name(&self) -> &'static str { "NUMBER" }
     fn type_id(&self) -> TypeId { TypeId::of::<NumberItem>() }
-    fn print(&self, config: &SmartCalcConfig, _: &Session) -> String {
+    fn print(&self, config: &SmartCalcConfig, session: &Session) -> String {
         match self.1 {
-            NumberType::Decimal     => format_number(self.0, config.thousand_separator.to_string(), config.decimal_seperator.to_string(), config.number_config.decimal_digits, config.number_config.remove_fract_if_zero, config.number_config.use_fract_rounding),
-            NumberType::Binary      => format!("{:#b}", self.0 as i32),
-            NumberType::Octal       => format!("{:#o}", self.0 as i32),
-            NumberType::Hexadecimal => format!("{:#X}", self.0 as i32),
-            NumberType::Raw         => format!("{}", self.0 as i32)
+            NumberType::Decimal if config.number_config.is_scientific(self.0) => format_scientific(self.0, config.decimal_seperator.to_string(), config.number_config.mantissa_digits(), config.number_config.remove_fract_if_zero),
+            NumberType::Decimal if config.number_config.significant_figures != 0 => format_significant(self.0, config.thousand_separator.to_string(), config.decimal_seperator.to_string(), config.number_config.significant_figures, config.number_config.remove_fract_if_zero, config.number_config.rounding_mode),
+            NumberType::SignificantFigures(figures) => format_significant(self.0, config.thousand_separator.to_string(), config.decimal_seperator.to_string(), figures, false, config.number_config.rounding_mode),
+            NumberType::Decimal     => format_number_with_rounding(self.0, config.thousand_separator.to_string(), config.decimal_seperator.to_string(), config.number_config.decimal_digits, config.number_config.remove_fract_if_zero, config.number_config.use_fract_rounding, config.number_config.rounding_mode),
+            NumberType::Scientific  => format_scientific(self.0, config.decimal_seperator.to_string(), config.number_config.decimal_digits, config.number_config.remove_fract_if_zero),
+            NumberType::Engineering => format_engineering(self.0, config.decimal_seperator.to_string(), config.number_config.decimal_digits, config.number_config.remove_fract_if_zero),
+            NumberType::Binary | NumberType::Octal | NumberType::Hexadecimal => IntegerItem(self.0.round() as i128, self.1).print(config, session),
+            NumberType::Raw         => format!("{}", self.0 as i128)
         }
     }
     fn unary(&self, unary: UnaryType) -> Rc<dyn DataItem> {
@@ -11,9 +11,9 @@ use crate::session::Session;
 use crate::config::SmartCalcConfig;
 use crate::types::TokenType;
 use super::{DataItem, OperationType, UnaryType};
-use crate::formatter::format_number;
+use crate::formatter::{format_number_with_rounding, format_significant};
 use alloc::format;
-use crate::tools::do_divition;
+use crate::tools::{do_divition, do_math};
 
 
 #[derive(Debug)]
@@ -42,13 +42,7 @@ impl DataItem for PercentItem {
             (number, self.0 ) 
         };
         
-        let result = match operation_type {
-            OperationType::Add => left + right,
-            OperationType::Div => left / right,
-            OperationType::Mul => left * right,
-            OperationType::Sub => left - right
-        };
-        Some(Rc::new(PercentItem(result)))
+        Some(Rc::new(PercentItem(do_math(left, right, operation_type))))
     }
     
     fn get_number(&self, other: &dyn DataItem) -> f64 {
@@ -56,13 +50,19 @@ impl DataItem for PercentItem {
            return self.0 
        }
        
-       do_divition(other.get_underlying_number(), 100.0) * self.0
+       do_math(do_divition(other.get_underlying_number(), 100.0), self.0, OperationType::Mul)
     }
     
     fn get_underlying_number(&self) -> f64 { self.0 }
     fn type_name(&self) -> &'static str { "PERCENT" }
     fn type_id(&self) -> TypeId { TypeId::of::<PercentItem>() }
-    fn print(&self, config: &SmartCalcConfig, _: &Session) -> String { format!("%{:}", format_number(self.0, config.thousand_separator.to_string(), config.decimal_seperator.to_string(), config.percentage_config.decimal_digits, config.percentage_config.remove_fract_if_zero, config.percentage_config.use_fract_rounding)) }
+    fn print(&self, config: &SmartCalcConfig, _: &Session) -> String {
+        let formated_number = match config.percentage_config.significant_figures {
+            0 => format_number_with_rounding(self.0, config.thousand_separator.to_string(), config.decimal_seperator.to_string(), config.percentage_config.decimal_digits, config.percentage_config.remove_fract_if_zero, config.percentage_config.use_fract_rounding, config.percentage_config.rounding_mode),
+            figures => format_significant(self.0, config.thousand_separator.to_string(), config.decimal_seperator.to_string(), figures, config.percentage_config.remove_fract_if_zero, config.percentage_config.rounding_mode)
+        };
+        format!("%{:}", formated_number)
+    }
     fn unary(&self, unary: UnaryType) -> Rc<dyn DataItem> {
         match unary {
             UnaryType::Minus => Rc::new(Self(-1.0 * self.0)),
@@ -0,0 +1,43 @@
+/*
+ * smartcalc v1.0.8
+ * Copyright (c) Erhan BARIS (Ruslan Ognyanov Asenov)
+ * Licensed under the GNU General Public License v2.0.
+ */
+
+use core::any::{Any, TypeId};
+use alloc::rc::Rc;
+use alloc::string::{String, ToString};
+use crate::session::Session;
+use crate::config::SmartCalcConfig;
+use crate::types::TokenType;
+use super::{DataItem, OperationType, UnaryType};
+
+/* Word that is picked from a list, "pick one of a, b, c". It can not be used in calculations */
+#[derive(Debug)]
+pub struct TextItem(pub String);
+
+impl DataItem for TextItem {
+    fn as_token_type(&self) -> TokenType {
+        TokenType::Text(self.0.clone())
+    }
+    fn is_same(&self, other: &dyn Any) -> bool {
+        match other.downcast_ref::<String>() {
+            Some(text) => text == &self.0,
+            None => false
+        }
+    }
+    fn as_any(&self) -> &dyn Any { self }
+    fn calculate(&self, _: &SmartCalcConfig, _: bool, _: &dyn DataItem, _: OperationType) -> Option<Rc<dyn DataItem>> {
+        None
+    }
+    fn get_number(&self, _: &dyn DataItem) -> f64 { 0.0 }
+    fn get_underlying_number(&self) -> f64 { 0.0 }
+    fn type_name(&self) -> &'static str { "TEXT" }
+    fn type_id(&self) -> TypeId { TypeId::of::<TextItem>() }
+    fn print(&self, _: &SmartCalcConfig, _: &Session) -> String {
+        self.0.to_string()
+    }
+    fn unary(&self, _: UnaryType) -> Rc<dyn DataItem> {
+        Rc::new(Self(self.0.clone()))
+    }
+}
@@ -0,0 +1,87 @@
+/*
+ * smartcalc v1.0.8
+ * Copyright (c) Erhan BARIS (Ruslan Ognyanov Asenov)
+ * Licensed under the GNU General Public License v2.0.
+ */
+
+use core::any::{Any, TypeId};
+use core::ops::Deref;
+use alloc::rc::Rc;
+use alloc::string::String;
+use crate::session::Session;
+use crate::config::SmartCalcConfig;
+use crate::types::{CurrencyInfo, TokenType, NumberType};
+use super::{DataItem, OperationType, UnaryType};
+use super::number::NumberItem;
+use super::money::MoneyItem;
+use crate::formatter::{format_words, format_money_words};
+
+/* Number or money that is written with words in the language of the session */
+#[derive(Debug)]
+pub struct WordsItem(pub f64, pub Option<Rc<CurrencyInfo>>);
+
+impl WordsItem {
+    pub fn get_item(&self) -> Rc<dyn DataItem> {
+        match &self.1 {
+            Some(currency) => Rc::new(MoneyItem(self.0, currency.clone())),
+            None => Rc::new(NumberItem(self.0, NumberType::Decimal))
+        }
+    }
+}
+
+impl DataItem for WordsItem {
+    fn as_token_type(&self) -> TokenType {
+        TokenType::Words(self.0, self.1.clone())
+    }
+    fn is_same(&self, other: &dyn Any) -> bool {
+        match other.downcast_ref::<(f64, Option<Rc<CurrencyInfo>>)>() {
+            Some((l_value, l_currency)) => (l_value - self.0).abs() < f64::EPSILON && l_currency.as_deref() == self.1.as_deref(),
+            None => false
+        }
+    }
+    fn as_any(&self) -> &dyn Any { self }
+    fn calculate(&self, config: &SmartCalcConfig, on_left: bool, other: &dyn DataItem, operation_type: OperationType) -> Option<Rc<dyn DataItem>> {
+        self.get_item().calculate(config, on_left, other, operation_type)
+    }
+    fn get_number(&self, other: &dyn DataItem) -> f64 { self.get_item().get_number(other) }
+    fn get_underlying_number(&self) -> f64 { self.0 }
+    fn type_name(&self) -> &'static str { "WORDS" }
+    fn type_id(&self) -> TypeId { TypeId::of::<WordsItem>() }
+    fn print(&self, config: &SmartCalcConfig, session: &Session) -> String {
+        let words = config.json_data.languages.get(&session.get_language()).and_then(|language| {
+            match &self.1 {
+                Some(currency) => format_money_words(&language.number_words, self.0, currency.deref()),
+                None => format_words(&language.number_words, self.0, config.number_config.decimal_digits)
+            }
+        });
+
+        /* Languages without number words and very big numbers are shown with digits */
+        match words {
+            Some(words) => words,
+            None => self.get_item().print(config, session)
+        }
+    }
+    fn unary(&self, unary: UnaryType) -> Rc<dyn DataItem> {
+        match unary {
+            UnaryType::Minus => Rc::new(Self(-self.0, self.1.clone())),
+            UnaryType::Plus => Rc::new(Self(self.0, self.1.clone()))
+        }
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn words_print_test() {
+    use alloc::string::ToString;
+    let config = SmartCalcConfig::default();
+    let mut session = Session::default();
+    session.set_language("en".to_string());
+    let eur = config.get_currency("eur".to_string()).unwrap();
+
+    assert_eq!(WordsItem(1250.0, Some(eur.clone())).print(&config, &session), "one thousand two hundred fifty euros".to_string());
+    assert_eq!(WordsItem(21.5, None).print(&config, &session), "twenty one point five".to_string());
+    assert_eq!(WordsItem(1e30, None).print(&config, &session), "1e30".to_string());
+
+    session.set_language("tr".to_string());
+    assert_eq!(WordsItem(1250.0, Some(eur)).print(&config, &session), "bin iki yüz elli avro".to_string());
+}
@@ -11,38 +11,171 @@ use alloc::string::String;
 use alloc::string::ToString;
 use alloc::vec::Vec;
 use alloc::collections::btree_map::BTreeMap;
+use chrono::{NaiveDate, NaiveTime};
 use regex::Regex;
+use serde_derive::{Deserialize, Serialize};
 use serde_json::from_str;
 use crate::session::Session;
+use crate::compiler::integer::wrap_integer;
+use crate::error::{ConfigError, SmartCalcError};
 use crate::tokinizer::RuleItemList;
 use crate::tokinizer::RuleType;
-use crate::types::CurrencyInfo;
+use crate::types::{CurrencyInfo, CurrencyRate, CurrencyConversion};
 use crate::types::TimeOffset;
 use crate::tokinizer::Tokinizer;
 use crate::tokinizer::TokenInfo;
 use crate::tokinizer::RULE_FUNCTIONS;
+use crate::function::{FUNCTIONS, SESSION_FUNCTIONS, FunctionType};
 use crate::constants::*;
 
 pub type LanguageData<T> = BTreeMap<String, T>;
 pub type CurrencyData<T> = BTreeMap<Rc<CurrencyInfo>, T>;
 
+/// How the last kept decimal digit is rounded, half up is the usual school rounding.
+#[derive(Default)]
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RoundingMode {
+    #[default]
+    HalfUp,
+    HalfEven,
+    HalfDown,
+    Truncate,
+    Ceiling,
+    Floor
+}
+
+/// How the currency of money is shown, name uses the currency words of the language like "12 euros".
+#[derive(Default)]
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CurrencyDisplay {
+    #[default]
+    Symbol,
+    NarrowSymbol,
+    Code,
+    Name
+}
+
+/// How negative money is shown, accounting style is written in parentheses like "($1.200,00)".
+#[derive(Default)]
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NegativeStyle {
+    #[default]
+    Minus,
+    Accounting
+}
+
 #[derive(Default)]
 #[derive(Clone)]
 #[derive(Debug)]
 #[derive(PartialEq)]
+#[derive(Serialize, Deserialize)]
 pub struct MoneyConfig {
     pub remove_fract_if_zero: bool,
-    pub use_fract_rounding: bool
+    pub use_fract_rounding: bool,
+
+    #[serde(default)]
+    pub rounding_mode: RoundingMode,
+
+    /// Lines using a currency rate older than the given seconds get a warning, zero disables the warning.
+    #[serde(default = "default_rate_max_age")]
+    pub rate_max_age: u64,
+
+    /// Codes of the currencies used for shared symbols like "$" and "kr", the first matching one is used.
+    #[serde(default)]
+    pub preferred_currencies: Vec<String>,
+
+    #[serde(default)]
+    pub display: CurrencyDisplay,
+
+    #[serde(default)]
+    pub negative_style: NegativeStyle
+}
+
+fn default_rate_max_age() -> u64 {
+    DEFAULT_RATE_MAX_AGE
+}
+
+fn default_scientific_upper_exponent() -> i32 {
+    DEFAULT_SCIENTIFIC_UPPER_EXPONENT
 }
 
 #[derive(Default)]
 #[derive(Clone)]
 #[derive(Debug)]
 #[derive(PartialEq)]
+#[derive(Serialize, Deserialize)]
 pub struct NumberConfig {
     pub decimal_digits: u8,
     pub remove_fract_if_zero: bool,
-    pub use_fract_rounding: bool
+    pub use_fract_rounding: bool,
+
+    #[serde(default)]
+    pub rounding_mode: RoundingMode,
+
+    /// Numbers are shown with the given significant figures instead of decimal digits, zero disables it.
+    #[serde(default)]
+    pub significant_figures: u8,
+
+    /// Numbers equal or bigger than 10^upper are shown with scientific notation, zero disables the limit.
+    #[serde(default = "default_scientific_upper_exponent")]
+    pub scientific_upper_exponent: i32,
+
+    /// Non zero numbers smaller than 10^lower are shown with scientific notation, zero disables the limit.
+    #[serde(default)]
+    pub scientific_lower_exponent: i32
+}
+
+impl NumberConfig {
+    pub fn is_scientific(&self, number: f64) -> bool {
+        let number = number.abs();
+        (self.scientific_upper_exponent != 0 && number >= 10_f64.powi(self.scientific_upper_exponent)) ||
+        (self.scientific_lower_exponent != 0 && number != 0.0 && number < 10_f64.powi(self.scientific_lower_exponent))
+    }
+
+    /* With significant figures the first digit is before the separator */
+    pub fn mantissa_digits(&self) -> u8 {
+        match self.significant_figures {
+            0 => self.decimal_digits,
+            figures => figures - 1
+        }
+    }
+}
+
+/// Width of the integers for non decimal number types and bitwise operators, zero bits means no limit.
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Serialize, Deserialize)]
+pub struct IntegerConfig {
+    pub bits: u8,
+    pub signed: bool
+}
+
+impl Default for IntegerConfig {
+    fn default() -> Self {
+        IntegerConfig {
+            bits: 64,
+            signed: true
+        }
+    }
+}
+
+impl IntegerConfig {
+    pub fn wrap(&self, number: i128) -> i128 {
+        wrap_integer(number, self.bits, self.signed)
+    }
 }
 
 #[derive(Default)]
@@ -84,20 +217,27 @@ pub struct SmartCalcConfig {
     pub(crate) format: LanguageData<JsonFormat>,
     pub(crate) currency: LanguageData<Rc<CurrencyInfo>>,
     pub(crate) currency_alias: LanguageData<Rc<CurrencyInfo>>,
+    pub(crate) ambiguous_currency: LanguageData<Vec<Rc<CurrencyInfo>>>,
     pub(crate) timezones: BTreeMap<String, i32>,
     pub(crate) currency_rate: CurrencyData<f64>,
+    pub(crate) currency_rate_info: CurrencyData<CurrencyRate>,
+    pub(crate) currency_rate_history: BTreeMap<NaiveDate, CurrencyData<f64>>,
     pub(crate) token_parse_regex: LanguageData<Vec<Regex>>,
     pub(crate) word_group: LanguageData<BTreeMap<String, Vec<String>>>,
     pub(crate) constant_pair: LanguageData<BTreeMap<String, ConstantType>>,
     pub(crate) language_alias_regex: LanguageData<Vec<(Regex, String)>>,
     pub(crate) alias_regex: Vec<(Regex, String)>,
     pub(crate) rule: LanguageData<RuleItemList>,
+    pub(crate) functions: BTreeMap<String, FunctionType>,
     pub(crate) types: BTreeMap<String, BTreeMap<usize, Rc<DynamicType>>>,
     pub(crate) type_conversion: Vec<JsonTypeConversion>,
     pub(crate) month_regex: LanguageData<MonthItemList>,
+    pub(crate) number_word_regex: LanguageData<Regex>,
+    pub(crate) number_notation_regex: LanguageData<Regex>,
     pub(crate) money_config: MoneyConfig,
     pub(crate) number_config: NumberConfig,
     pub(crate) percentage_config: NumberConfig,
+    pub(crate) integer_config: IntegerConfig,
     pub(crate) decimal_seperator: String,
     pub(crate) thousand_separator: String,
     pub(crate) timezone: String,
@@ -124,25 +264,370 @@ impl SmartCalcConfig {
             .cloned()
     }
 
+    /// New currency or asset, rate is the amount of it for one USD. Code should have at least two letters
+    /// and the symbol is added as an alias. Returns false if the code is already used or the rate is not valid.
+    pub fn add_currency(&mut self, currency: CurrencyInfo, rate: f64) -> bool {
+        let code = currency.code.to_lowercase();
+        let is_valid_code = code.chars().count() >= 2 && code.chars().all(|item| item.is_ascii_alphabetic());
+        if !is_valid_code || self.currency.contains_key(&code) || !rate.is_finite() || rate <= 0.0 {
+            return false;
+        }
+
+        let symbol = currency.symbol.to_string();
+        let currency = Rc::new(currency);
+        self.json_data.currencies.insert(code.to_string(), currency.clone());
+        self.json_data.currency_rates.insert(code.to_string(), rate);
+        self.currency.insert(code.to_string(), currency.clone());
+        self.currency_rate.insert(currency, rate);
+
+        if !symbol.is_empty() {
+            self.add_currency_alias(&symbol, &code);
+        }
+        true
+    }
+
+    /// Alias of a known currency, like "bitcoin" for BTC. The amount is not scaled, "10 bitcoin" is ₿10. Returns false if the alias is already used.
+    pub fn add_currency_alias(&mut self, alias: &str, code: &str) -> bool {
+        let alias = alias.trim().to_lowercase();
+        let currency = match self.get_currency(code.to_lowercase()) {
+            Some(currency) => currency,
+            None => return false
+        };
+
+        if alias.is_empty() || self.currency_alias.contains_key(&alias) {
+            return false;
+        }
+
+        self.json_data.currency_alias.insert(alias.to_string(), code.to_lowercase());
+        self.currency_alias.insert(alias.to_string(), currency);
+        self.add_currency_symbol_patterns(&alias);
+        true
+    }
+
+    /// Shared symbols like "$" and "kr" are read as the first preferred currency using them, unknown codes are not accepted.
+    pub fn set_preferred_currencies(&mut self, currencies: Vec<String>) -> bool {
+        if currencies.iter().any(|code| self.get_currency(code.to_lowercase()).is_none()) {
+            return false;
+        }
+
+        self.money_config.preferred_currencies = currencies.iter().map(|code| code.to_uppercase()).collect();
+        true
+    }
+
+    /* Money patterns only know currency symbols and latin letters, other symbols like "Ξ" get their own patterns */
+    fn add_currency_symbol_patterns(&mut self, symbol: &str) {
+        let is_currency_symbol = Regex::new(r"^\p{Currency_Symbol}$").map(|re| re.is_match(symbol)).unwrap_or_default();
+        if is_currency_symbol || symbol.chars().all(|item| item.is_ascii_alphabetic()) {
+            return;
+        }
+
+        let symbol = format!("(?i:{})", regex::escape(symbol));
+        let patterns = [
+            format!(r"(?P<CURRENCY>{})(?P<PRICE>[-+]?[0-9]+([0-9.,]{{0,}}[0-9])?)(?P<NOTATION>[kKMGTPZY]{{0,1}})", symbol),
+            format!(r"(?P<PRICE>[-+]?[0-9]+([0-9.,]{{0,}}[0-9])?)[ ]*(?P<CURRENCY>{})", symbol)
+        ];
+
+        for pattern in patterns.iter() {
+            if let Ok(re) = Regex::new(pattern) {
+                self.json_data.parse.entry("money".to_string()).or_default().push(pattern.to_string());
+                self.token_parse_regex.entry("money".to_string()).or_default().push(re);
+            }
+        }
+    }
+
+    /* Built-in rates have no timestamp, the oldest known timestamp of both rates is used */
+    pub(crate) fn get_currency_conversion(&self, from: &Rc<CurrencyInfo>, to: &Rc<CurrencyInfo>) -> Option<CurrencyConversion> {
+        let timestamp = [from, to].iter()
+            .filter_map(|currency| self.currency_rate_info.get(*currency).map(|info| info.timestamp))
+            .min();
+
+        Some(CurrencyConversion {
+            from: from.code.to_string(),
+            to: to.code.to_string(),
+            rate: self.currency_rate.get(to)? / self.currency_rate.get(from)?,
+            timestamp,
+            historical: false
+        })
+    }
+
+    /* Rates are converted with the current rate of their base currency. Nothing is changed if one of the rates is not valid */
+    pub(crate) fn update_currency_rates(&mut self, rates: &[CurrencyRate]) -> Result<usize, ConfigError> {
+        let mut updates = Vec::with_capacity(rates.len());
+        for (index, rate) in rates.iter().enumerate() {
+            let currency = self.get_currency(rate.currency.to_lowercase())
+                .ok_or_else(|| ConfigError::UnknownCurrency { path: format!("rates[{}].currency", index), currency: rate.currency.to_string() })?;
+            let base_rate = self.get_currency(rate.base.to_lowercase()).and_then(|base| self.currency_rate.get(&base).copied())
+                .ok_or_else(|| ConfigError::UnknownCurrency { path: format!("rates[{}].base", index), currency: rate.base.to_string() })?;
+
+            if !rate.rate.is_finite() || rate.rate <= 0.0 {
+                return Err(ConfigError::InvalidValue { path: format!("rates[{}].rate", index), message: "rate should be a positive number".to_string() });
+            }
+
+            updates.push((currency, rate.rate * base_rate, rate.clone()));
+        }
+
+        for (currency, rate, info) in updates.iter() {
+            self.currency_rate.insert(currency.clone(), *rate);
+            self.currency_rate_info.insert(currency.clone(), info.clone());
+        }
+
+        Ok(updates.len())
+    }
+
+    /* Rates of a date are kept relative to any currency of that date. A new base is added with the rate of a known currency */
+    fn add_historical_rate(history: &mut BTreeMap<NaiveDate, CurrencyData<f64>>, date: NaiveDate, base: Rc<CurrencyInfo>, currency: Rc<CurrencyInfo>, rate: f64, path: String) -> Result<(), ConfigError> {
+        if !rate.is_finite() || rate <= 0.0 {
+            return Err(ConfigError::InvalidValue { path, message: "rate should be a positive number".to_string() });
+        }
+
+        let rates = history.entry(date).or_default();
+        if rates.is_empty() {
+            rates.insert(base.clone(), 1.0);
+        }
+
+        match (rates.get(&base).copied(), rates.get(&currency).copied()) {
+            (Some(base_rate), _) => { rates.insert(currency, rate * base_rate); },
+            (None, Some(currency_rate)) => { rates.insert(base, currency_rate / rate); },
+            (None, None) => return Err(ConfigError::InvalidValue { path, message: format!("{} or {} should have a rate on {}", base.code, currency.code, date) })
+        };
+        Ok(())
+    }
+
+    /* History is changed only if all rates are valid */
+    fn update_currency_rate_history(&mut self, rates: Vec<(String, NaiveDate, String, String, f64)>) -> Result<usize, ConfigError> {
+        let mut history = self.currency_rate_history.clone();
+        let total = rates.len();
+        for (path, date, base, currency, rate) in rates.into_iter() {
+            let base = self.get_currency(base.to_lowercase()).ok_or_else(|| ConfigError::UnknownCurrency { path: path.to_string(), currency: base.to_string() })?;
+            let currency = self.get_currency(currency.to_lowercase()).ok_or_else(|| ConfigError::UnknownCurrency { path: path.to_string(), currency: currency.to_string() })?;
+            SmartCalcConfig::add_historical_rate(&mut history, date, base, currency, rate, path)?;
+        }
+
+        self.currency_rate_history = history;
+        Ok(total)
+    }
+
+    fn parse_history_date(path: &str, date: &str) -> Result<NaiveDate, ConfigError> {
+        NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d").map_err(|_| ConfigError::InvalidValue { path: path.to_string(), message: format!("'{}' is not a YYYY-MM-DD date", date.trim()) })
+    }
+
+    /// Dated rates are loaded from JSON, `{"base": "EUR", "rates": {"2024-03-01": {"USD": 1.08, "GBP": 0.85}}}`.
+    /// Returns the number of loaded rates.
+    pub fn load_currency_rate_history_json(&mut self, json_data: &str) -> Result<usize, ConfigError> {
+        let history: JsonCurrencyRateHistory = from_str(json_data)?;
+        let mut rates = Vec::new();
+        for (date, date_rates) in history.rates.iter() {
+            let parsed_date = SmartCalcConfig::parse_history_date(&format!("rates.{}", date), date)?;
+            for (currency, rate) in date_rates.iter() {
+                rates.push((format!("rates.{}.{}", date, currency), parsed_date, history.base.to_string(), currency.to_string(), *rate));
+            }
+        }
+
+        self.update_currency_rate_history(rates)
+    }
+
+    /// Dated rates are loaded from CSV with `date,base,currency,rate` columns, header line is optional.
+    /// Returns the number of loaded rates.
+    pub fn load_currency_rate_history_csv(&mut self, csv_data: &str) -> Result<usize, ConfigError> {
+        let mut rates = Vec::new();
+        for (index, line) in csv_data.lines().enumerate() {
+            let path = format!("line {}", index + 1);
+            let columns = line.split(',').map(|column| column.trim()).collect::<Vec<_>>();
+            if line.trim().is_empty() || (index == 0 && columns[0].eq_ignore_ascii_case("date")) {
+                continue;
+            }
+
+            if columns.len() != 4 {
+                return Err(ConfigError::InvalidValue { path, message: "date,base,currency,rate columns expected".to_string() });
+            }
+
+            let date = SmartCalcConfig::parse_history_date(&path, columns[0])?;
+            let rate = columns[3].parse::<f64>().map_err(|_| ConfigError::InvalidValue { path: path.to_string(), message: format!("'{}' is not a number", columns[3]) })?;
+            rates.push((path, date, columns[1].to_string(), columns[2].to_string(), rate));
+        }
+
+        self.update_currency_rate_history(rates)
+    }
+
+    /* Rate of the nearest earlier date that has both currencies. Dates out of the history are not guessed */
+    pub(crate) fn get_historical_conversion(&self, from: &Rc<CurrencyInfo>, to: &Rc<CurrencyInfo>, date: NaiveDate) -> Result<CurrencyConversion, SmartCalcError> {
+        /* Dates after the last loaded date use the latest rate, only dates before the history are out of range */
+        let not_found = || SmartCalcError::HistoricalRateNotFound { from: from.code.to_string(), to: to.code.to_string(), date: date.format("%Y-%m-%d").to_string() };
+        let (rate_date, rates) = self.currency_rate_history.range(..=date).rev()
+            .find(|(_, rates)| rates.contains_key(from) && rates.contains_key(to))
+            .ok_or_else(not_found)?;
+
+        Ok(CurrencyConversion {
+            from: from.code.to_string(),
+            to: to.code.to_string(),
+            rate: rates[to] / rates[from],
+            timestamp: Some(rate_date.and_time(NaiveTime::MIN).and_utc().timestamp()),
+            historical: true
+        })
+    }
+
+    /* Letters are case sensitive, "M" is million but "m" is meter. Words can be written with any case */
+    pub fn get_number_notation(&self, language: &str, notation: &str) -> Option<f64> {
+        let number_notation = &self.json_data.languages.get(language)?.number_notation;
+        number_notation.get(notation)
+            .or_else(|| if notation.chars().count() > 1 { number_notation.get(&notation.to_lowercase()) } else { None })
+            .map(|notation_type| notation_type.get_multiplier())
+    }
+
+    /// Invalid parts of the configuration are skipped and logged.
     pub fn load_from_json(json_data: &str) -> Self {
+        let json_data = match from_str(json_data) {
+            Ok(data) => data,
+            Err(error) => panic!("JSON parse error: {}", error)
+        };
+
+        let (config, errors) = SmartCalcConfig::build(json_data);
+        for error in errors.iter() {
+            log::warn!("{}", error);
+        }
+
+        config
+    }
+
+    /// Configuration is loaded only if there is no validation error.
+    pub fn try_load_from_json(json_data: &str) -> Result<Self, ConfigError> {
+        let (config, mut errors) = SmartCalcConfig::build(from_str(json_data)?);
+        match errors.is_empty() {
+            true => Ok(config),
+            false => Err(errors.remove(0))
+        }
+    }
+
+    /// Overlay is merged on top of the current configuration, values with the same key are replaced.
+    /// `parse` patterns are appended, `types` are merged by name and item index, `type_conversion` is
+    /// replaced when source and target are the same. Functions, rules and settings added through
+    /// the API are kept. The configuration is not changed if the merged data is not valid.
+    pub fn merge_json(&mut self, json_data: &str) -> Result<(), ConfigError> {
+        let overlay: JsonConstantOverlay = from_str(json_data)?;
+        let mut merged_data = self.json_data.clone();
+        merge_json_constant(&mut merged_data, &overlay)?;
+
+        let (mut config, mut errors) = SmartCalcConfig::build(merged_data);
+        if !errors.is_empty() {
+            return Err(errors.remove(0));
+        }
+
+        config.keep_runtime_data(self, &overlay);
+        *self = config;
+        Ok(())
+    }
+
+    fn keep_runtime_data(&mut self, previous: &mut SmartCalcConfig, overlay: &JsonConstantOverlay) {
+        for (name, function) in core::mem::take(&mut previous.functions) {
+            if let FunctionType::Api { .. } = function {
+                self.functions.insert(name, function);
+            }
+        }
+
+        /* Rules that are not part of the JSON data (API rules, date rules) */
+        for (language, rules) in core::mem::take(&mut previous.rule) {
+            let json_rules = self.json_data.languages.get(&language).map(|language| &language.rules);
+            if let Some(current_rules) = self.rule.get_mut(&language) {
+                current_rules.extend(rules.into_iter().filter(|rule| match rule {
+                    RuleType::Internal { function_name, .. } => !matches!(json_rules, Some(json_rules) if json_rules.contains_key(function_name)),
+                    RuleType::API { .. } => true
+                }));
+            }
+        }
+
+        for (name, items) in core::mem::take(&mut previous.types) {
+            let current_items = self.types.entry(name).or_default();
+            for (index, item) in items {
+                current_items.entry(index).or_insert(item);
+            }
+        }
+
+        self.currency_rate_history = core::mem::take(&mut previous.currency_rate_history);
+
+        /* Rates updated after loading */
+        for (currency, rate) in previous.currency_rate.iter() {
+            let code = currency.code.to_lowercase();
+            if overlay.currency_rates.keys().any(|key| key.to_lowercase() == code) {
+                continue;
+            }
+
+            if let Some(currency) = self.get_currency(&code) {
+                if let Some(info) = previous.currency_rate_info.get(&currency) {
+                    self.currency_rate_info.insert(currency.clone(), info.clone());
+                }
+                self.currency_rate.insert(currency, *rate);
+            }
+        }
+
+        if overlay.settings.is_none() {
+            self.money_config = previous.money_config.clone();
+            self.number_config = previous.number_config.clone();
+            self.percentage_config = previous.percentage_config.clone();
+            self.integer_config = previous.integer_config.clone();
+            self.decimal_seperator = previous.decimal_seperator.clone();
+            self.thousand_separator = previous.thousand_separator.clone();
+            self.timezone = previous.timezone.clone();
+            self.timezone_offset = previous.timezone_offset;
+        }
+    }
+
+    /// Current configuration as JSON, it can be loaded back with `load_from_json`.
+    /// Currency rates, dynamic types, date rules and settings changed at runtime are included.
+    pub fn to_json(&self) -> Result<String, ConfigError> {
+        let mut json_data = self.json_data.clone();
+        json_data.currency_rates = self.currency_rate.iter()
+            .map(|(currency, rate)| (currency.code.to_lowercase(), *rate))
+            .collect();
+
+        json_data.settings = Some(JsonSettings {
+            money: self.money_config.clone(),
+            number: self.number_config.clone(),
+            percentage: self.percentage_config.clone(),
+            integer: self.integer_config.clone(),
+            decimal_separator: self.decimal_seperator.to_string(),
+            thousand_separator: self.thousand_separator.to_string(),
+            timezone: self.timezone.to_string(),
+            timezone_offset: self.timezone_offset
+        });
+
+        json_data.api_rules = BTreeMap::new();
+        for (language, rules) in self.rule.iter() {
+            let names = rules.iter().filter_map(|rule| match rule {
+                RuleType::API { rule, .. } => Some(rule.name()),
+                _ => None
+            }).collect::<Vec<_>>();
+
+            if !names.is_empty() {
+                json_data.api_rules.insert(language.to_string(), names);
+            }
+        }
+
+        Ok(serde_json::to_string(&json_data)?)
+    }
+
+    fn build(json_data: JsonConstant) -> (Self, Vec<ConfigError>) {
+        let mut errors = Vec::new();
         let mut config = SmartCalcConfig {
-            json_data: match from_str(json_data) {
-                Ok(data) => data,
-                Err(error) => panic!("JSON parse error: {}", error)
-            },
+            json_data,
             format: LanguageData::new(),
             currency: LanguageData::new(),
             currency_alias: LanguageData::new(),
+            ambiguous_currency: LanguageData::new(),
             timezones: BTreeMap::new(),
             currency_rate: CurrencyData::new(),
+            currency_rate_info: CurrencyData::new(),
+            currency_rate_history: BTreeMap::new(),
             token_parse_regex: LanguageData::new(),
             word_group: LanguageData::new(),
             constant_pair: LanguageData::new(),
             language_alias_regex: LanguageData::new(),
             rule: LanguageData::new(),
+            functions: BTreeMap::new(),
             types: BTreeMap::new(),
             type_conversion: Vec::new(),
             month_regex: LanguageData::new(),
+            number_word_regex: LanguageData::new(),
+            number_notation_regex: LanguageData::new(),
             alias_regex: Vec::new(),
             decimal_seperator: ",".to_string(),
             thousand_separator: ".".to_string(),
@@ -150,24 +635,57 @@ impl SmartCalcConfig {
             timezone_offset: 0,
             money_config: MoneyConfig {
                 remove_fract_if_zero: false,
-                use_fract_rounding: true
+                use_fract_rounding: true,
+                rounding_mode: RoundingMode::HalfUp,
+                rate_max_age: DEFAULT_RATE_MAX_AGE,
+                preferred_currencies: Vec::new(),
+                display: CurrencyDisplay::Symbol,
+                negative_style: NegativeStyle::Minus
             },
             number_config: NumberConfig {
                 decimal_digits: 2,
                 remove_fract_if_zero: true,
-                use_fract_rounding: true
+                use_fract_rounding: true,
+                rounding_mode: RoundingMode::HalfUp,
+                significant_figures: 0,
+                scientific_upper_exponent: DEFAULT_SCIENTIFIC_UPPER_EXPONENT,
+                scientific_lower_exponent: 0
             },
             percentage_config: NumberConfig {
                 decimal_digits: 2,
                 remove_fract_if_zero: true,
-                use_fract_rounding: true
+                use_fract_rounding: true,
+                rounding_mode: RoundingMode::HalfUp,
+                significant_figures: 0,
+                scientific_upper_exponent: DEFAULT_SCIENTIFIC_UPPER_EXPONENT,
+                scientific_lower_exponent: 0
             },
+            integer_config: IntegerConfig::default()
         };
         
+        if let Some(settings) = &config.json_data.settings {
+            config.money_config = settings.money.clone();
+            config.number_config = settings.number.clone();
+            config.percentage_config = settings.percentage.clone();
+            config.integer_config = settings.integer.clone();
+            config.decimal_seperator = settings.decimal_separator.to_string();
+            config.thousand_separator = settings.thousand_separator.to_string();
+            config.timezone = settings.timezone.to_string();
+            config.timezone_offset = settings.timezone_offset;
+        }
+
         for (name, currency) in config.json_data.currencies.iter() {
             config.currency.insert(name.to_lowercase(), currency.clone());
         }
 
+        for (name, function) in FUNCTIONS.iter() {
+            config.functions.insert(name.to_string(), FunctionType::Internal { function: *function });
+        }
+
+        for (name, function) in SESSION_FUNCTIONS.iter() {
+            config.functions.insert(name.to_string(), FunctionType::Session { function: *function });
+        }
+
         for (timezone, offset) in config.json_data.timezones.iter() {
             config.timezones.insert(timezone.clone(), *offset);
         }
@@ -175,7 +693,7 @@ impl SmartCalcConfig {
         for (from, to) in config.json_data.alias.iter() {
             match Regex::new(&format!(r"\b{}\b", from)) {
                 Ok(re) => config.alias_regex.push((re, to.to_string())),
-                Err(error) => log::error!("Alias parser error ({}) {}", from, error)
+                Err(error) => errors.push(ConfigError::InvalidRegex { path: format!("alias.{}", from), message: error.to_string() })
             }
         }
 
@@ -188,14 +706,26 @@ impl SmartCalcConfig {
         for (key, value) in config.json_data.currency_alias.iter() {
             match config.get_currency(value) {
                 Some(currency) => { config.currency_alias.insert(key.to_string(), currency.clone()); },
-                None => log::warn!("'{}' currency not found at alias", value)
+                None => errors.push(ConfigError::UnknownCurrency { path: format!("currency_alias.{}", key), currency: value.to_string() })
             };
         }
 
+        for (key, values) in config.json_data.ambiguous_currency.iter() {
+            let mut currencies = Vec::new();
+            for (index, value) in values.iter().enumerate() {
+                match config.get_currency(value.to_lowercase()) {
+                    Some(currency) => currencies.push(currency),
+                    None => errors.push(ConfigError::UnknownCurrency { path: format!("ambiguous_currency.{}[{}]", key, index), currency: value.to_string() })
+                };
+            }
+
+            config.ambiguous_currency.insert(key.to_lowercase(), currencies);
+        }
+
         for (key, value) in config.json_data.currency_rates.iter() {
             match config.get_currency(key) {
                 Some(currency) => { config.currency_rate.insert(currency.clone(), *value); },
-                None => log::warn!("'{}' currency not found at rate", key)
+                None => errors.push(ConfigError::UnknownCurrency { path: format!("currency_rates.{}", key), currency: key.to_string() })
             };
         }
 
@@ -205,7 +735,7 @@ impl SmartCalcConfig {
                 
                 match Regex::new(&format!(r"\b{}\b", alias)) {
                     Ok(re) => language_aliases.push((re, target_name.to_string())),
-                    Err(error) => log::error!("Alias parser error ({}) {}", alias, error)
+                    Err(error) => errors.push(ConfigError::InvalidRegex { path: format!("languages.{}.alias.{}", language, alias), message: error.to_string() })
                 }
 
             }
@@ -215,10 +745,10 @@ impl SmartCalcConfig {
         
         for (parse_type, items) in &config.json_data.parse {
             let mut patterns = Vec::new();
-            for pattern in items {
+            for (index, pattern) in items.iter().enumerate() {
                 match Regex::new(pattern) {
                     Ok(re) => patterns.push(re),
-                    Err(error) => log::error!("Token parse regex error ({}) {}", pattern, error)
+                    Err(error) => errors.push(ConfigError::InvalidRegex { path: format!("parse.{}[{}]", parse_type, index), message: error.to_string() })
                 }
             }
 
@@ -237,16 +767,16 @@ impl SmartCalcConfig {
             }
 
             for (month_name, month_number) in &language_constant.long_months {
-                match month_list.get_mut((*month_number - 1) as usize) {
+                match month_list.get_mut((*month_number as usize).wrapping_sub(1)) {
                     Some(month_object) => month_object.long = month_name.to_string(),
-                    None => log::warn!("Month not fetched. {}", month_number)
+                    None => errors.push(ConfigError::InvalidValue { path: format!("languages.{}.long_months.{}", language, month_name), message: format!("{} is not a valid month", month_number) })
                 };
             }
 
             for (month_name, month_number) in &language_constant.short_months {
-                match month_list.get_mut((*month_number - 1) as usize) {
+                match month_list.get_mut((*month_number as usize).wrapping_sub(1)) {
                     Some(month_object) => month_object.short = month_name.to_string(),
-                    None => log::warn!("Month not fetched. {}", month_number)
+                    None => errors.push(ConfigError::InvalidValue { path: format!("languages.{}.short_months.{}", language, month_name), message: format!("{} is not a valid month", month_number) })
                 };
             }
 
@@ -254,13 +784,59 @@ impl SmartCalcConfig {
                 let pattern = &format!(r"\b{}\b|\b{}\b", month.long, month.short);
                 match Regex::new(pattern) {
                     Ok(re) => language_group.push((re, month.clone())),
-                    Err(error) => log::error!("Month parser error ({}) {}", month.long, error)
+                    Err(error) => errors.push(ConfigError::InvalidRegex { path: format!("languages.{}.long_months.{}", language, month.long), message: error.to_string() })
                 }
             }
 
             config.month_regex.insert(language.to_string(), language_group);
         }
 
+        for (language, language_constant) in config.json_data.languages.iter() {
+            let number_words = &language_constant.number_words;
+            let mut words = number_words.units.iter().chain(number_words.tens.iter()).chain(number_words.scales.iter()).chain(core::iter::once(&number_words.hundred))
+                .filter(|word| !word.is_empty())
+                .map(|word| regex::escape(&word.to_lowercase()))
+                .collect::<Vec<_>>();
+
+            if words.is_empty() {
+                continue;
+            }
+
+            /* Longer words first, otherwise "six" is matched inside of "sixteen" */
+            words.sort_by_key(|word| core::cmp::Reverse(word.len()));
+            let words = words.join("|");
+            let connectors = number_words.connectors.iter().map(|word| regex::escape(&word.to_lowercase())).collect::<Vec<_>>().join("|");
+            let pattern = match connectors.is_empty() {
+                true => format!(r"(?P<WORDS>\b(?:{words})(?:[ -]+(?:{words}))*\b)(?:[ ]+(?P<CURRENCY>[^\s\d()]+))?", words=words),
+                false => format!(r"(?P<WORDS>\b(?:{words})(?:[ -]+(?:(?:{connectors})[ ]+)?(?:{words}))*\b)(?:[ ]+(?P<CURRENCY>[^\s\d()]+))?", words=words, connectors=connectors)
+            };
+
+            match Regex::new(&pattern) {
+                Ok(re) => { config.number_word_regex.insert(language.to_string(), re); },
+                Err(error) => errors.push(ConfigError::InvalidRegex { path: format!("languages.{}.number_words", language), message: error.to_string() })
+            };
+        }
+
+        for (language, language_constant) in config.json_data.languages.iter() {
+            /* Single letters are parsed with the number and money patterns, "5k" */
+            let mut words = language_constant.number_notation.keys()
+                .filter(|word| word.chars().count() > 1)
+                .map(|word| regex::escape(&word.to_lowercase()))
+                .collect::<Vec<_>>();
+
+            if words.is_empty() {
+                continue;
+            }
+
+            words.sort_by_key(|word| core::cmp::Reverse(word.len()));
+            let pattern = format!(r"(?:(?P<CURRENCY_PREFIX>\p{{Currency_Symbol}})|(?P<PERCENT_PREFIX>%))?(?P<NUMBER>[-+]?[0-9]+(?:[0-9.,]{{0,}}[0-9])?)[ ]*(?P<NOTATION>(?i:{})\b)(?:[ ]*(?P<PERCENT>%)|[ ]+(?P<CURRENCY>[^\s\d()%]+))?", words.join("|"));
+
+            match Regex::new(&pattern) {
+                Ok(re) => { config.number_notation_regex.insert(language.to_string(), re); },
+                Err(error) => errors.push(ConfigError::InvalidRegex { path: format!("languages.{}.number_notation", language), message: error.to_string() })
+            };
+        }
+
         for (language, language_constant) in config.json_data.languages.iter() {
             let mut word_groups = BTreeMap::new();
             for (word_group_name, word_group_items) in language_constant.word_group.iter() {
@@ -284,7 +860,7 @@ impl SmartCalcConfig {
                     Some(const_type) => {
                         constants.insert(alias_name.to_string(), const_type);
                     },
-                    _ => log::error!("Constant type not parsed. {}", constant_type)
+                    _ => errors.push(ConfigError::InvalidValue { path: format!("languages.{}.constant_pair.{}", language, alias_name), message: format!("{} is not a valid constant type", constant_type) })
                 };
             }
 
@@ -311,7 +887,7 @@ impl SmartCalcConfig {
                     });
                 }
                 else {
-                    log::warn!("Function not found : {}", rule_name);
+                    errors.push(ConfigError::UnknownRuleFunction { path: format!("languages.{}.rules.{}", language, rule_name) });
                 }
             }
 
@@ -324,7 +900,7 @@ impl SmartCalcConfig {
             for type_item in dynamic_type.items.iter() {
                 
                 if type_item.upgrade_code.is_none() || type_item.downgrade_code.is_none() {
-                    log::warn!("Dynamic type {}:{} has missing calculation code. Please check upgrade_code and downgrade_code fields", dynamic_type.name, type_item.index);
+                    errors.push(ConfigError::InvalidValue { path: format!("types.{}[{}]", dynamic_type.name, type_item.index), message: "upgrade_code and downgrade_code are required".to_string() });
                     continue;
                 }
                 
@@ -359,7 +935,7 @@ impl SmartCalcConfig {
             config.types.insert(dynamic_type.name.to_string(), dynamic_type_holder);
         }
         
-        for type_conversion in config.json_data.type_conversion.iter() {
+        for (index, type_conversion) in config.json_data.type_conversion.iter().enumerate() {
             let source = config.types.get(&type_conversion.source.name);
             let target = config.types.get(&type_conversion.target.name);
 
@@ -377,11 +953,11 @@ impl SmartCalcConfig {
             }
 
             if !source_found {
-                log::warn!("{} type not defined", type_conversion.source.name);
+                errors.push(ConfigError::UnknownType { path: format!("type_conversion[{}].source", index), name: type_conversion.source.name.to_string(), index: type_conversion.source.index });
             }
 
             if !target_found {
-                log::warn!("{} type not defined", type_conversion.target.name);
+                errors.push(ConfigError::UnknownType { path: format!("type_conversion[{}].target", index), name: type_conversion.target.name.to_string(), index: type_conversion.target.index });
             }
             
             if source_found && target_found {
@@ -389,6 +965,93 @@ impl SmartCalcConfig {
             }
         }
 
-        config
+        (config, errors)
+    }
+}
+
+fn merge_json_constant(json_data: &mut JsonConstant, overlay: &JsonConstantOverlay) -> Result<(), ConfigError> {
+    if let Some(default_language) = &overlay.default_language {
+        json_data.default_language = default_language.to_string();
     }
-}
\ No newline at end of file
+
+    for (parse_type, patterns) in overlay.parse.iter() {
+        json_data.parse.entry(parse_type.to_string()).or_default().extend(patterns.iter().cloned());
+    }
+
+    json_data.alias.extend(overlay.alias.clone());
+    json_data.currency_alias.extend(overlay.currency_alias.clone());
+    json_data.ambiguous_currency.extend(overlay.ambiguous_currency.clone());
+    json_data.currency_rates.extend(overlay.currency_rates.clone());
+    json_data.currencies.extend(overlay.currencies.clone());
+    json_data.type_group.extend(overlay.type_group.clone());
+    json_data.timezones.extend(overlay.timezones.clone());
+
+    if let Some(settings) = &overlay.settings {
+        json_data.settings = Some(settings.clone());
+    }
+
+    for (language, language_overlay) in overlay.languages.iter() {
+        let language_constant = match json_data.languages.get_mut(language) {
+            Some(language_constant) => language_constant,
+            None => {
+                let format = match &language_overlay.format {
+                    Some(format) => format.clone(),
+                    None => return Err(ConfigError::InvalidValue { path: format!("languages.{}.format", language), message: "format is required for a new language".to_string() })
+                };
+
+                json_data.languages.entry(language.to_string()).or_insert(JsonLanguageConstant {
+                    number_notation: BTreeMap::new(),
+                    long_months: BTreeMap::new(),
+                    short_months: BTreeMap::new(),
+                    word_group: BTreeMap::new(),
+                    constant_pair: BTreeMap::new(),
+                    rules: BTreeMap::new(),
+                    alias: BTreeMap::new(),
+                    format,
+                    number_words: JsonNumberWords::default()
+                })
+            }
+        };
+
+        language_constant.number_notation.extend(language_overlay.number_notation.clone());
+        language_constant.long_months.extend(language_overlay.long_months.clone());
+        language_constant.short_months.extend(language_overlay.short_months.clone());
+        language_constant.word_group.extend(language_overlay.word_group.clone());
+        language_constant.constant_pair.extend(language_overlay.constant_pair.clone());
+        language_constant.rules.extend(language_overlay.rules.clone());
+        language_constant.alias.extend(language_overlay.alias.clone());
+
+        if let Some(format) = &language_overlay.format {
+            language_constant.format = format.clone();
+        }
+
+        if let Some(number_words) = &language_overlay.number_words {
+            language_constant.number_words = number_words.clone();
+        }
+    }
+
+    for dynamic_type in overlay.types.iter() {
+        match json_data.types.iter_mut().find(|item| item.name == dynamic_type.name) {
+            Some(current_type) => {
+                for type_item in dynamic_type.items.iter() {
+                    match current_type.items.iter_mut().find(|item| item.index == type_item.index) {
+                        Some(current_item) => *current_item = type_item.clone(),
+                        None => current_type.items.push(type_item.clone())
+                    };
+                }
+            },
+            None => json_data.types.push(dynamic_type.clone())
+        };
+    }
+
+    for type_conversion in overlay.type_conversion.iter() {
+        let position = json_data.type_conversion.iter().position(|item| item.source.name == type_conversion.source.name && item.source.index == type_conversion.source.index &&
+                                                                          item.target.name == type_conversion.target.name && item.target.index == type_conversion.target.index);
+        match position {
+            Some(position) => json_data.type_conversion[position] = type_conversion.clone(),
+            None => json_data.type_conversion.push(type_conversion.clone())
+        };
+    }
+
+    Ok(())
+}
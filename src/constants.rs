@@ -5,6 +5,7 @@
  */
 
 use crate::types::CurrencyInfo;
+use crate::config::{MoneyConfig, NumberConfig, IntegerConfig};
 use alloc::rc::Rc;
 use alloc::{collections::btree_map::BTreeMap};
 use alloc::string::String;
@@ -74,6 +75,22 @@ pub enum NumberNotationType {
     Quadrillion = 5,
     Quintillion = 6,
     Sextillion = 7,
+    Dozen = 8,
+    Gross = 9,
+    Lakh = 10,
+    Crore = 11,
+}
+
+impl NumberNotationType {
+    pub fn get_multiplier(&self) -> f64 {
+        match self {
+            NumberNotationType::Dozen => 12.0,
+            NumberNotationType::Gross => 144.0,
+            NumberNotationType::Lakh  => 100_000.0,
+            NumberNotationType::Crore => 10_000_000.0,
+            _ => 1000_f64.powi(self.clone() as i32)
+        }
+    }
 }
 
 impl ConstantType {
@@ -107,6 +124,33 @@ pub struct LanguageRule {
     pub samples: Vec<Sample>,
 }
 
+/// Names of a currency used while writing amounts with words, "euro" and "euros".
+#[derive(Serialize, Deserialize, Default, Clone)]
+#[serde(default)]
+pub struct JsonCurrencyWords {
+    pub singular: String,
+    pub plural: String,
+    pub fraction_singular: String,
+    pub fraction_plural: String
+}
+
+/// Vocabulary for numbers written with words. Units are the numbers that have their own name (0-19 in english),
+/// tens starts from zero and scales are thousand, million, billion... Implicit one scales are written without
+/// "one" in front of them, like "yüz" and "bin" in turkish.
+#[derive(Serialize, Deserialize, Default, Clone)]
+#[serde(default)]
+pub struct JsonNumberWords {
+    pub units: Vec<String>,
+    pub tens: Vec<String>,
+    pub hundred: String,
+    pub scales: Vec<String>,
+    pub implicit_one: Vec<String>,
+    pub connectors: Vec<String>,
+    pub negative: String,
+    pub point: String,
+    pub currencies: BTreeMap<String, JsonCurrencyWords>
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct JsonLanguageConstant {
     pub number_notation: BTreeMap<String, NumberNotationType>,
@@ -117,6 +161,9 @@ pub struct JsonLanguageConstant {
     pub rules: BTreeMap<String, LanguageRule>,
     pub alias: BTreeMap<String, String>,
     pub format: JsonFormat,
+
+    #[serde(default)]
+    pub number_words: JsonNumberWords,
 }
 
 #[derive(Default)]
@@ -174,21 +221,101 @@ pub struct JsonDynamicTypeItem {
     pub remove_fract_if_zero: Option<bool>
 }
 
+#[derive(Serialize, Deserialize, Clone)]
+pub struct JsonSettings {
+    pub money: MoneyConfig,
+    pub number: NumberConfig,
+    pub percentage: NumberConfig,
+    pub decimal_separator: String,
+    pub thousand_separator: String,
+    pub timezone: String,
+    pub timezone_offset: i32,
+
+    #[serde(default)]
+    pub integer: IntegerConfig
+}
+
 #[derive(Serialize, Deserialize, Default, Clone)]
 pub struct JsonConstant {
     pub default_language: String,
     pub parse: BTreeMap<String, Vec<String>>,
     pub alias: BTreeMap<String, String>,
     pub currency_alias: BTreeMap<String, String>,
+
+    /* Symbols and names used by more than one currency, the alias is used if there is no preferred currency */
+    #[serde(default)]
+    pub ambiguous_currency: BTreeMap<String, Vec<String>>,
     pub currency_rates: BTreeMap<String, f64>,
     pub currencies: BTreeMap<String, Rc<CurrencyInfo>>,
     pub languages: BTreeMap<String, JsonLanguageConstant>,
     pub type_group: BTreeMap<String, Vec<String>>,
     pub timezones: BTreeMap<String, i32>,
     pub type_conversion: Vec<JsonTypeConversion>,
-    pub types: Vec<JsonDynamicType>
+    pub types: Vec<JsonDynamicType>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub settings: Option<JsonSettings>,
+
+    /* Rules added through the API, they can not be loaded back and listed by name for information */
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub api_rules: BTreeMap<String, Vec<String>>
+}
+
+/// Dated currency rates, rates are the amount of the currency for one base currency.
+/// `{"base": "EUR", "rates": {"2024-03-01": {"USD": 1.08}}}`
+#[derive(Serialize, Deserialize, Default, Clone)]
+pub struct JsonCurrencyRateHistory {
+    pub base: String,
+    pub rates: BTreeMap<String, BTreeMap<String, f64>>
+}
+
+#[derive(Serialize, Deserialize, Default, Clone)]
+#[serde(default)]
+pub struct JsonLanguageOverlay {
+    pub number_notation: BTreeMap<String, NumberNotationType>,
+    pub long_months: BTreeMap<String, u8>,
+    pub short_months: BTreeMap<String, u8>,
+    pub word_group: BTreeMap<String, Vec<String>>,
+    pub constant_pair: BTreeMap<String, u8>,
+    pub rules: BTreeMap<String, LanguageRule>,
+    pub alias: BTreeMap<String, String>,
+    pub format: Option<JsonFormat>,
+    pub number_words: Option<JsonNumberWords>,
+}
+
+/// Partial configuration, every section is optional and merged on top of a JsonConstant.
+#[derive(Serialize, Deserialize, Default, Clone)]
+#[serde(default)]
+pub struct JsonConstantOverlay {
+    pub default_language: Option<String>,
+    pub parse: BTreeMap<String, Vec<String>>,
+    pub alias: BTreeMap<String, String>,
+    pub currency_alias: BTreeMap<String, String>,
+    pub ambiguous_currency: BTreeMap<String, Vec<String>>,
+    pub currency_rates: BTreeMap<String, f64>,
+    pub currencies: BTreeMap<String, Rc<CurrencyInfo>>,
+    pub languages: BTreeMap<String, JsonLanguageOverlay>,
+    pub type_group: BTreeMap<String, Vec<String>>,
+    pub timezones: BTreeMap<String, i32>,
+    pub type_conversion: Vec<JsonTypeConversion>,
+    pub types: Vec<JsonDynamicType>,
+    pub settings: Option<JsonSettings>
 }
 
 pub type MonthItemList = Vec<(Regex, MonthInfo)>;
 
 pub const JSON_DATA: &str = include_str!("./json/config.json");
+
+/* Operators that are typed with multiple characters or words */
+pub const XOR_OPERATOR: char = '⊕';
+pub const SHIFT_LEFT_OPERATOR: char = '«';
+pub const SHIFT_RIGHT_OPERATOR: char = '»';
+
+/* f64 keeps about 17 significant digits */
+pub const MAX_SIGNIFICANT_FIGURES: u8 = 17;
+
+/* Decimal numbers from 1e15 are shown with scientific notation, f64 is not exact after that */
+pub const DEFAULT_SCIENTIFIC_UPPER_EXPONENT: i32 = 15;
+
+/* Currency rates older than one day are stale */
+pub const DEFAULT_RATE_MAX_AGE: u64 = 24 * 60 * 60;
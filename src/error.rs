@@ -0,0 +1,163 @@
+/*
+ * smartcalc v1.0.8
+ * Copyright (c) Erhan BARIS (Ruslan Ognyanov Asenov)
+ * Licensed under the GNU General Public License v2.0.
+ */
+
+use core::fmt;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use serde_derive::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SmartCalcError {
+    /// Expression could not be parsed, start and end are character positions in the line.
+    Syntax {
+        message: String,
+        start: usize,
+        end: usize
+    },
+    UnknownOperator(char),
+    IncompatibleTypes {
+        operator: char,
+        left: String,
+        right: String
+    },
+    MissingCurrencyRate(String),
+    /// Dated currency rate is not known, date is formatted as YYYY-MM-DD.
+    HistoricalRateNotFound {
+        from: String,
+        to: String,
+        date: String
+    },
+    DivisionByZero,
+    Overflow,
+    FunctionNotFound(String),
+    InvalidArgument(String),
+    UnsupportedExpression(String),
+    NumberNotFound,
+    EmptyCalculation,
+    MultilineNotSupported
+}
+
+impl SmartCalcError {
+    pub fn syntax<T: Into<String>>(message: T, start: usize, end: usize) -> Self {
+        SmartCalcError::Syntax { message: message.into(), start, end }
+    }
+}
+
+impl fmt::Display for SmartCalcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SmartCalcError::Syntax { message, .. } => write!(f, "{}", message),
+            SmartCalcError::UnknownOperator(operator) => write!(f, "Unknown operator. ({})", operator),
+            SmartCalcError::IncompatibleTypes { operator, left, right } => write!(f, "{} {} {} could not be calculated", left, operator, right),
+            SmartCalcError::MissingCurrencyRate(currency) => write!(f, "Currency rate not found. ({})", currency),
+            SmartCalcError::HistoricalRateNotFound { from, to, date } => write!(f, "{}/{} currency rate not found for {}", from, to, date),
+            SmartCalcError::DivisionByZero => write!(f, "Division by zero"),
+            SmartCalcError::Overflow => write!(f, "Calculation overflow"),
+            SmartCalcError::FunctionNotFound(name) => write!(f, "Function not found. ({})", name),
+            SmartCalcError::InvalidArgument(message) => write!(f, "{}", message),
+            SmartCalcError::UnsupportedExpression(type_name) => write!(f, "Unsupported expression. ({})", type_name),
+            SmartCalcError::NumberNotFound => write!(f, "Number not found"),
+            SmartCalcError::EmptyCalculation => write!(f, "Calculation empty"),
+            SmartCalcError::MultilineNotSupported => write!(f, "Multiline calculation not supported")
+        }
+    }
+}
+
+/// Line is calculated but the result may not be correct.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SmartCalcWarning {
+    /// Currency rate is older than the configured age, timestamp is the unix time of the rate.
+    StaleCurrencyRate {
+        from: String,
+        to: String,
+        timestamp: i64
+    },
+
+    /// Symbol is used by more than one currency and none of them is preferred, alternatives are the other currencies.
+    AmbiguousCurrency {
+        symbol: String,
+        currency: String,
+        alternatives: Vec<String>
+    },
+
+    /// Currency is shown with its name but the language has no name for it, the code is shown instead.
+    MissingCurrencyName {
+        currency: String,
+        language: String
+    },
+
+    /// Lines of the block could not be added to the total, like numbers in a money total. Line numbers start from 1.
+    SkippedTotalLines {
+        lines: Vec<usize>
+    }
+}
+
+impl fmt::Display for SmartCalcWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SmartCalcWarning::StaleCurrencyRate { from, to, timestamp } => match chrono::DateTime::from_timestamp(*timestamp, 0) {
+                Some(date_time) => write!(f, "{}/{} currency rate is outdated, last update {}", from, to, date_time.format("%Y-%m-%d %H:%M UTC")),
+                None => write!(f, "{}/{} currency rate is outdated", from, to)
+            },
+            SmartCalcWarning::AmbiguousCurrency { symbol, currency, alternatives } => write!(f, "'{}' is read as {}, it can also be {}", symbol, currency, alternatives.join(", ")),
+            SmartCalcWarning::MissingCurrencyName { currency, language } => write!(f, "{} has no name in '{}', currency code is used", currency, language),
+            SmartCalcWarning::SkippedTotalLines { lines } => write!(f, "Lines not added to the total: {}", lines.iter().map(|line| line.to_string()).collect::<Vec<_>>().join(", "))
+        }
+    }
+}
+
+/// Validation error of a configuration file, path points to the problematic JSON value.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ConfigError {
+    Json {
+        message: String,
+        line: usize,
+        column: usize
+    },
+    InvalidRegex {
+        path: String,
+        message: String
+    },
+    UnknownRuleFunction {
+        path: String
+    },
+    UnknownCurrency {
+        path: String,
+        currency: String
+    },
+    UnknownType {
+        path: String,
+        name: String,
+        index: usize
+    },
+    InvalidValue {
+        path: String,
+        message: String
+    }
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Json { message, .. } => write!(f, "JSON parse error: {}", message),
+            ConfigError::InvalidRegex { path, message } => write!(f, "{}: invalid regex, {}", path, message),
+            ConfigError::UnknownRuleFunction { path } => write!(f, "{}: rule function not found", path),
+            ConfigError::UnknownCurrency { path, currency } => write!(f, "{}: '{}' currency not found", path, currency),
+            ConfigError::UnknownType { path, name, index } => write!(f, "{}: {}:{} type not defined", path, name, index),
+            ConfigError::InvalidValue { path, message } => write!(f, "{}: {}", path, message)
+        }
+    }
+}
+
+impl From<serde_json::Error> for ConfigError {
+    fn from(error: serde_json::Error) -> Self {
+        ConfigError::Json {
+            message: error.to_string(),
+            line: error.line(),
+            column: error.column()
+        }
+    }
+}
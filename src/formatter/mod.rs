@@ -6,14 +6,19 @@
 
 use alloc::{string::String};
 use alloc::format;
+use alloc::vec::Vec;
 use alloc::string::ToString;
 use crate::session::Session;
-use crate::tools::do_divition;
+use crate::tools::{round_number, round_to_step};
+#[cfg(feature = "decimal")]
+use rust_decimal::Decimal;
+#[cfg(feature = "decimal")]
+use rust_decimal::prelude::FromPrimitive;
 use core::ops::Deref;
 
-use crate::config::SmartCalcConfig;
-use crate::types::{SmartCalcAstType};
-use crate::constants::MonthInfo;
+use crate::config::{SmartCalcConfig, RoundingMode};
+use crate::types::{SmartCalcAstType, CurrencyInfo};
+use crate::constants::{MonthInfo, JsonNumberWords};
 
 pub const MINUTE: i64 = 60;
 pub const HOUR: i64 = MINUTE * 60;
@@ -22,6 +27,7 @@ pub const WEEK: i64 = DAY * 7;
 pub const MONTH: i64 = DAY * 30;
 pub const YEAR: i64 = DAY * 365;
 
+#[cfg(not(feature = "decimal"))]
 fn fract_information(f: f64) -> u64 {
     let eps = 1e-4;
     let mut f = f.abs().fract();
@@ -42,19 +48,42 @@ pub fn left_padding(number: i64, size: usize) -> String {
     format!("{:0width$}", &number, width = size)
 }
 
-pub fn format_number(number: f64, thousands_separator: String, decimal_separator: String, decimal_digits: u8, remove_fract_if_zero: bool, use_fract_rounding: bool) -> String {
-    let divider      = 10_u32.pow(decimal_digits.into());
-    let fract_number = do_divition((number * divider as f64).round(), divider as f64);
+/* Absolute value of the number, length of the integer part and if it has fractional part after rounding */
+#[cfg(not(feature = "decimal"))]
+fn number_parts(number: f64, decimal_digits: u8, use_fract_rounding: bool, rounding_mode: RoundingMode) -> (String, usize, bool) {
+    let fract_number = round_number(number, decimal_digits, rounding_mode);
     let trunc_part   = fract_number.trunc().abs().to_string();
 
     let formated_number = match use_fract_rounding {
-        true => format!("{:.width$}", &number.abs(), width = decimal_digits.into()),
+        true => format!("{:.width$}", &fract_number.abs(), width = decimal_digits.into()),
         false => format!("{}", &number.abs())
     };
 
-    let fract_part = fract_information(fract_number.fract());
-    let trunc_size = trunc_part.len();
-    let mut trunc_dot_index = 3 - (trunc_part.len() % 3);
+    (formated_number, trunc_part.len(), fract_information(fract_number.fract()) > 0)
+}
+
+#[cfg(feature = "decimal")]
+fn number_parts(number: f64, decimal_digits: u8, use_fract_rounding: bool, rounding_mode: RoundingMode) -> (String, usize, bool) {
+    let (number, rounded) = match (Decimal::from_f64(number), Decimal::from_f64(round_number(number, decimal_digits, rounding_mode))) {
+        (Some(number), Some(rounded)) => (number.abs(), rounded.abs()),
+        _ => return (format!("{}", number.abs()), format!("{}", number.abs().trunc()).len(), number.fract() != 0.0)
+    };
+
+    let formated_number = match use_fract_rounding {
+        true => format!("{:.width$}", rounded, width = decimal_digits.into()),
+        false => number.normalize().to_string()
+    };
+
+    (formated_number, rounded.trunc().to_string().len(), !rounded.fract().is_zero())
+}
+
+pub fn format_number(number: f64, thousands_separator: String, decimal_separator: String, decimal_digits: u8, remove_fract_if_zero: bool, use_fract_rounding: bool) -> String {
+    format_number_with_rounding(number, thousands_separator, decimal_separator, decimal_digits, remove_fract_if_zero, use_fract_rounding, RoundingMode::default())
+}
+
+pub fn format_number_with_rounding(number: f64, thousands_separator: String, decimal_separator: String, decimal_digits: u8, remove_fract_if_zero: bool, use_fract_rounding: bool, rounding_mode: RoundingMode) -> String {
+    let (formated_number, trunc_size, has_fract) = number_parts(number, decimal_digits, use_fract_rounding, rounding_mode);
+    let mut trunc_dot_index = 3 - (trunc_size % 3);
     let mut trunc_formated = String::new();
 
 
@@ -70,7 +99,7 @@ pub fn format_number(number: f64, thousands_separator: String, decimal_separator
         }
     }
     
-    if (fract_part > 0 || !remove_fract_if_zero) && trunc_size != formated_number.len() {
+    if (has_fract || !remove_fract_if_zero) && trunc_size != formated_number.len() {
         trunc_formated.push_str(&decimal_separator);
 
         for index in (trunc_size+1)..formated_number.len() {
@@ -81,6 +110,208 @@ pub fn format_number(number: f64, thousands_separator: String, decimal_separator
     trunc_formated
 }
 
+const SI_PREFIXES: [&str; 17] = ["y", "z", "a", "f", "p", "n", "µ", "m", "", "k", "M", "G", "T", "P", "E", "Z", "Y"];
+
+/* Exponent is a multiple of step and mantissa is rounded before the check, 9.999 with two digits is 1e1 */
+fn split_exponent(number: f64, decimal_digits: u8, step: i32) -> (f64, i32) {
+    if number == 0.0 || !number.is_finite() {
+        return (number, 0);
+    }
+
+    let multiplier = 10_f64.powi(decimal_digits as i32);
+    let mut exponent = number.abs().log10().floor() as i32;
+    exponent -= exponent.rem_euclid(step);
+
+    let mut mantissa = (number / 10_f64.powi(exponent) * multiplier).round() / multiplier;
+    if mantissa.abs() >= 10_f64.powi(step) {
+        exponent += step;
+        mantissa = (number / 10_f64.powi(exponent) * multiplier).round() / multiplier;
+    }
+
+    (mantissa, exponent)
+}
+
+pub fn format_scientific(number: f64, decimal_separator: String, decimal_digits: u8, remove_fract_if_zero: bool) -> String {
+    if !number.is_finite() {
+        return format_number(number, "".to_string(), decimal_separator, decimal_digits, remove_fract_if_zero, false);
+    }
+
+    let (mantissa, exponent) = split_exponent(number, decimal_digits, 1);
+    format!("{}e{}", format_number(mantissa, "".to_string(), decimal_separator, decimal_digits, remove_fract_if_zero, false), exponent)
+}
+
+/* Engineering notation uses SI prefixes, exponent is used if there is no prefix for it */
+pub fn format_engineering(number: f64, decimal_separator: String, decimal_digits: u8, remove_fract_if_zero: bool) -> String {
+    if !number.is_finite() {
+        return format_number(number, "".to_string(), decimal_separator, decimal_digits, remove_fract_if_zero, false);
+    }
+
+    let (mantissa, exponent) = split_exponent(number, decimal_digits, 3);
+    let formated_mantissa = format_number(mantissa, "".to_string(), decimal_separator, decimal_digits, remove_fract_if_zero, false);
+
+    match SI_PREFIXES.get((exponent / 3 + 8) as usize) {
+        Some(&"") => formated_mantissa,
+        Some(prefix) => format!("{} {}", formated_mantissa, prefix),
+        _ => format!("{}e{}", formated_mantissa, exponent)
+    }
+}
+
+/* Digits after the separator depend on the magnitude, 0.000123456 with 3 figures is 0.000123 and 123456 is 123.000 */
+pub fn format_significant(number: f64, thousands_separator: String, decimal_separator: String, significant_figures: u8, remove_fract_if_zero: bool, rounding_mode: RoundingMode) -> String {
+    if number == 0.0 || !number.is_finite() || significant_figures == 0 {
+        return format_number_with_rounding(number, thousands_separator, decimal_separator, 0, true, true, rounding_mode);
+    }
+
+    let magnitude = number.abs().log10().floor() as i32;
+    let mut decimal_digits = significant_figures as i32 - 1 - magnitude;
+    let rounded = match decimal_digits >= 0 {
+        true => round_number(number, decimal_digits as u8, rounding_mode),
+        false => round_to_step(number, 10_f64.powi(-decimal_digits), rounding_mode)
+    };
+
+    /* Rounding could carry to the next magnitude, 9.996 with 3 figures is 10.0 */
+    if rounded.abs() >= 10_f64.powi(magnitude + 1) {
+        decimal_digits -= 1;
+    }
+
+    format_number_with_rounding(rounded, thousands_separator, decimal_separator, decimal_digits.max(0) as u8, remove_fract_if_zero, true, rounding_mode)
+}
+
+/* Improper fractions are shown as mixed number, 7/2 is 3 1/2 */
+pub fn format_fraction(numerator: i64, denominator: i64, thousands_separator: String) -> String {
+    let whole = numerator / denominator;
+    let remainder = (numerator % denominator).abs();
+    let sign = if numerator < 0 { "-" } else { "" };
+    let formated_whole = format_number((whole as f64).abs(), thousands_separator, "".to_string(), 0, true, false);
+
+    match (whole, remainder) {
+        (_, 0) => format!("{}{}", sign, formated_whole),
+        (0, _) => format!("{}{}/{}", sign, remainder, denominator),
+        (_, _) => format!("{}{} {}/{}", sign, formated_whole, remainder, denominator)
+    }
+}
+
+fn spell_below_thousand(number_words: &JsonNumberWords, number: usize, words: &mut Vec<String>) -> Option<()> {
+    let (hundreds, rest) = (number / 100, number % 100);
+    if hundreds > 0 {
+        if hundreds > 1 || !number_words.implicit_one.contains(&number_words.hundred) {
+            words.push(number_words.units.get(hundreds)?.to_string());
+        }
+        words.push(number_words.hundred.to_string());
+    }
+
+    match rest {
+        0 => (),
+        _ if rest < number_words.units.len() => words.push(number_words.units.get(rest)?.to_string()),
+        _ => {
+            words.push(number_words.tens.get(rest / 10)?.to_string());
+            if rest % 10 > 0 {
+                words.push(number_words.units.get(rest % 10)?.to_string());
+            }
+        }
+    };
+    Some(())
+}
+
+/* None is returned if the number is bigger than the biggest scale */
+fn spell_integer(number_words: &JsonNumberWords, number: u64) -> Option<String> {
+    if number == 0 {
+        return number_words.units.first().cloned();
+    }
+
+    let mut groups = Vec::new();
+    let mut rest = number;
+    while rest > 0 {
+        groups.push((rest % 1000) as usize);
+        rest /= 1000;
+    }
+
+    if groups.len() > number_words.scales.len() + 1 {
+        return None;
+    }
+
+    let mut words = Vec::new();
+    for (index, group) in groups.iter().enumerate().rev() {
+        match (index, group) {
+            (_, 0) => (),
+            (0, _) => spell_below_thousand(number_words, *group, &mut words)?,
+            (_, _) => {
+                let scale = number_words.scales.get(index - 1)?;
+                if *group > 1 || !number_words.implicit_one.contains(scale) {
+                    spell_below_thousand(number_words, *group, &mut words)?;
+                }
+                words.push(scale.to_string());
+            }
+        };
+    }
+
+    Some(words.join(" "))
+}
+
+fn spell_sign(number_words: &JsonNumberWords, number: f64, text: String) -> String {
+    match number < 0.0 && !number_words.negative.is_empty() {
+        true => format!("{} {}", number_words.negative, text),
+        false => text
+    }
+}
+
+/* Fract part is written digit by digit, 3.14 is three point one four */
+pub fn format_words(number_words: &JsonNumberWords, number: f64, decimal_digits: u8) -> Option<String> {
+    let rounded = round_number(number.abs(), decimal_digits, RoundingMode::HalfUp);
+    if !rounded.is_finite() || rounded >= u64::MAX as f64 {
+        return None;
+    }
+
+    let mut text = spell_integer(number_words, rounded.trunc() as u64)?;
+    let fract = format!("{:.width$}", rounded.fract(), width = decimal_digits.into());
+    let fract = fract.trim_start_matches('0').trim_start_matches('.').trim_end_matches('0');
+    if !fract.is_empty() {
+        text.push(' ');
+        text.push_str(&number_words.point);
+        for digit in fract.chars().filter_map(|digit| digit.to_digit(10)) {
+            text.push(' ');
+            text.push_str(number_words.units.get(digit as usize)?);
+        }
+    }
+
+    Some(spell_sign(number_words, number, text))
+}
+
+/* Amount is written for cheques, 1250.50 usd is one thousand two hundred fifty dollars and fifty cents */
+pub fn format_money_words(number_words: &JsonNumberWords, price: f64, currency: &CurrencyInfo) -> Option<String> {
+    let rounded = round_number(price.abs(), currency.decimal_digits, RoundingMode::HalfUp);
+    if !rounded.is_finite() || rounded >= u64::MAX as f64 {
+        return None;
+    }
+
+    let whole = rounded.trunc() as u64;
+    let divider = 10_u64.pow(currency.decimal_digits.into());
+    let fract = ((rounded - rounded.trunc()) * divider as f64).round() as u64;
+    let currency_words = number_words.currencies.get(&currency.code);
+
+    let name = match (currency_words, whole) {
+        (Some(words), 1) => words.singular.to_string(),
+        (Some(words), _) => words.plural.to_string(),
+        (None, _) => currency.code.to_string()
+    };
+
+    let mut text = format!("{} {}", spell_integer(number_words, whole)?, name);
+    if fract > 0 {
+        if let Some(connector) = number_words.connectors.first() {
+            text.push(' ');
+            text.push_str(connector);
+        }
+
+        let fraction_name = currency_words.map(|words| if fract == 1 { &words.fraction_singular } else { &words.fraction_plural }).filter(|name| !name.is_empty());
+        match fraction_name {
+            Some(fraction_name) => text.push_str(&format!(" {} {}", spell_integer(number_words, fract)?, fraction_name)),
+            None => text.push_str(&format!(" {}/{}", fract, divider))
+        };
+    }
+
+    Some(spell_sign(number_words, price, text))
+}
+
 pub fn get_month_info(config: &SmartCalcConfig, language: &'_ str, month: u8) -> Option<MonthInfo> {
     match config.month_regex.get(language) {
         Some(month_list) => month_list.get((month - 1) as usize).map(|(_, month)| month.clone()),
@@ -103,7 +334,7 @@ pub fn format_result(config: &SmartCalcConfig, session: &Session, result: alloc:
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, not(feature = "decimal")))]
 #[test]
 fn get_frac_test() {
     assert_eq!(fract_information(0.1234567), 1234567);
@@ -133,6 +364,91 @@ fn format_number_test() {
     assert_eq!(format_number(-123456.1, ",".to_string(), ".".to_string(), 2, false, false), "-123,456.1".to_string());
 }
 
+#[cfg(test)]
+#[test]
+fn format_scientific_test() {
+    assert_eq!(format_scientific(6.022e23, ",".to_string(), 2, true), "6,02e23".to_string());
+    assert_eq!(format_scientific(-0.0015, ",".to_string(), 2, true), "-1,5e-3".to_string());
+    assert_eq!(format_scientific(9.999e10, ",".to_string(), 2, true), "1e11".to_string());
+    assert_eq!(format_scientific(0.0, ",".to_string(), 2, true), "0e0".to_string());
+    assert_eq!(format_scientific(f64::INFINITY, ",".to_string(), 2, true), "inf".to_string());
+    assert_eq!(format_engineering(f64::NEG_INFINITY, ",".to_string(), 2, true), "-inf".to_string());
+
+    assert_eq!(format_engineering(0.00047, ",".to_string(), 2, true), "470 µ".to_string());
+    assert_eq!(format_engineering(12346.0, ",".to_string(), 2, true), "12,35 k".to_string());
+    assert_eq!(format_engineering(999.999, ",".to_string(), 2, true), "1 k".to_string());
+    assert_eq!(format_engineering(42.0, ",".to_string(), 2, true), "42".to_string());
+    assert_eq!(format_engineering(1.5e30, ",".to_string(), 2, true), "1,5e30".to_string());
+}
+
+#[cfg(test)]
+#[test]
+fn format_fraction_test() {
+    assert_eq!(format_fraction(3, 4, ".".to_string()), "3/4".to_string());
+    assert_eq!(format_fraction(10, 3, ".".to_string()), "3 1/3".to_string());
+    assert_eq!(format_fraction(-3, 2, ".".to_string()), "-1 1/2".to_string());
+    assert_eq!(format_fraction(-1, 2, ".".to_string()), "-1/2".to_string());
+    assert_eq!(format_fraction(8, 4, ".".to_string()), "2".to_string());
+    assert_eq!(format_fraction(2469, 2, ".".to_string()), "1.234 1/2".to_string());
+}
+
+#[cfg(test)]
+#[test]
+fn format_number_rounding_test() {
+    assert_eq!(format_number_with_rounding(12.345, ",".to_string(), ".".to_string(), 2, false, true, RoundingMode::HalfEven), "12.34".to_string());
+    assert_eq!(format_number_with_rounding(12.355, ",".to_string(), ".".to_string(), 2, false, true, RoundingMode::HalfEven), "12.36".to_string());
+    assert_eq!(format_number_with_rounding(12.345, ",".to_string(), ".".to_string(), 2, false, true, RoundingMode::HalfUp), "12.35".to_string());
+    assert_eq!(format_number_with_rounding(1999.999, ",".to_string(), ".".to_string(), 2, false, true, RoundingMode::Truncate), "1,999.99".to_string());
+    assert_eq!(format_number_with_rounding(1999.999, ",".to_string(), ".".to_string(), 2, false, true, RoundingMode::HalfUp), "2,000.00".to_string());
+    assert_eq!(format_number_with_rounding(-1.001, ",".to_string(), ".".to_string(), 2, true, true, RoundingMode::Floor), "-1.01".to_string());
+    assert_eq!(format_number_with_rounding(1.001, ",".to_string(), ".".to_string(), 2, true, true, RoundingMode::Floor), "1".to_string());
+}
+
+#[cfg(test)]
+#[test]
+fn format_significant_test() {
+    assert_eq!(format_significant(0.000123456, ",".to_string(), ".".to_string(), 3, true, RoundingMode::HalfUp), "0.000123".to_string());
+    assert_eq!(format_significant(123456.0, ",".to_string(), ".".to_string(), 3, true, RoundingMode::HalfUp), "123,000".to_string());
+    assert_eq!(format_significant(3.14159, ",".to_string(), ".".to_string(), 3, true, RoundingMode::HalfUp), "3.14".to_string());
+    assert_eq!(format_significant(1.5, ",".to_string(), ".".to_string(), 3, true, RoundingMode::HalfUp), "1.50".to_string());
+    assert_eq!(format_significant(9.996, ",".to_string(), ".".to_string(), 3, false, RoundingMode::HalfUp), "10.0".to_string());
+    assert_eq!(format_significant(-0.0456789, ",".to_string(), ".".to_string(), 2, true, RoundingMode::HalfUp), "-0.046".to_string());
+    assert_eq!(format_significant(0.0, ",".to_string(), ".".to_string(), 3, true, RoundingMode::HalfUp), "0".to_string());
+}
+
+#[cfg(test)]
+#[test]
+fn format_words_test() {
+    let config = SmartCalcConfig::default();
+    let english = &config.json_data.languages.get("en").unwrap().number_words;
+    let turkish = &config.json_data.languages.get("tr").unwrap().number_words;
+    let usd = config.get_currency("usd".to_string()).unwrap();
+    let eur = config.get_currency("eur".to_string()).unwrap();
+    let chf = config.get_currency("chf".to_string()).unwrap();
+
+    assert_eq!(format_words(english, 0.0, 2), Some("zero".to_string()));
+    assert_eq!(format_words(english, 125.0, 2), Some("one hundred twenty five".to_string()));
+    assert_eq!(format_words(english, 1_000_017.0, 2), Some("one million seventeen".to_string()));
+    assert_eq!(format_words(english, -3.14, 2), Some("minus three point one four".to_string()));
+    assert_eq!(format_words(english, 1e18, 2), None);
+    assert_eq!(format_words(turkish, 1250.0, 2), Some("bin iki yüz elli".to_string()));
+    assert_eq!(format_words(turkish, 100_115.0, 2), Some("yüz bin yüz on beş".to_string()));
+    assert_eq!(format_words(turkish, 1_000_000.0, 2), Some("bir milyon".to_string()));
+
+    assert_eq!(format_money_words(english, 1250.0, &eur), Some("one thousand two hundred fifty euros".to_string()));
+    assert_eq!(format_money_words(english, 1.01, &usd), Some("one dollar and one cent".to_string()));
+    assert_eq!(format_money_words(english, 12.5, &chf), Some("twelve CHF and 50/100".to_string()));
+    assert_eq!(format_money_words(turkish, 1250.5, &usd), Some("bin iki yüz elli dolar elli sent".to_string()));
+}
+
+#[cfg(all(test, feature = "decimal"))]
+#[test]
+fn decimal_format_number_test() {
+    assert_eq!(format_number(1.005, ",".to_string(), ".".to_string(), 2, false, true), "1.01".to_string());
+    assert_eq!(format_number(0.1 + 0.2, ",".to_string(), ".".to_string(), 20, true, false), "0.3".to_string());
+    assert_eq!(format_number(-2.5, ",".to_string(), ".".to_string(), 0, true, true), "-3".to_string());
+}
+
 #[cfg(test)]
 #[test]
 fn format_result_test() {
@@ -0,0 +1,297 @@
+/*
+ * smartcalc v1.0.8
+ * Copyright (c) Erhan BARIS (Ruslan Ognyanov Asenov)
+ * Licensed under the GNU General Public License v2.0.
+ */
+
+use alloc::format;
+use alloc::rc::Rc;
+use alloc::string::ToString;
+
+use crate::compiler::DataItem;
+use crate::compiler::OperationType;
+use crate::compiler::number::NumberItem;
+use crate::compiler::integer::{IntegerItem, get_integer, with_integer, to_integer};
+use crate::compiler::money::{MoneyItem, get_money_item};
+use crate::compiler::percent::PercentItem;
+use crate::compiler::fraction::FractionItem;
+use crate::compiler::dynamic_type::DynamicTypeItem;
+use crate::config::SmartCalcConfig;
+use crate::error::SmartCalcError;
+use crate::types::NumberType;
+
+use super::statistics_functions::get_items;
+
+fn check_arguments(name: &str, arguments: &[Rc<dyn DataItem>], min: usize, max: usize) -> core::result::Result<(), SmartCalcError> {
+    match arguments.len() >= min && arguments.len() <= max {
+        true => Ok(()),
+        false if min == max => Err(SmartCalcError::InvalidArgument(format!("{} requires {} argument(s)", name, min))),
+        false => Err(SmartCalcError::InvalidArgument(format!("{} requires between {} and {} arguments", name, min, max)))
+    }
+}
+
+/* Apply calculation to underlying number and keep the unit of the argument */
+pub(super) fn map_number<F: Fn(f64) -> f64>(item: &Rc<dyn DataItem>, calculation: F) -> core::result::Result<Rc<dyn DataItem>, SmartCalcError> {
+    let result: Rc<dyn DataItem> = match item.type_name() {
+        "NUMBER" => match item.as_any().downcast_ref::<IntegerItem>() {
+            Some(integer) => match to_integer(calculation(integer.0 as f64).round()) {
+                Some(number) => Rc::new(IntegerItem(number, integer.1)),
+                None => return Err(SmartCalcError::Overflow)
+            },
+            None => {
+                let number = item.as_any().downcast_ref::<NumberItem>().unwrap();
+                Rc::new(NumberItem(calculation(number.0), number.1))
+            }
+        },
+        "MONEY" => {
+            let money = get_money_item(item.as_ref()).unwrap();
+            Rc::new(MoneyItem(calculation(money.get_price()), money.get_currency()))
+        },
+        "PERCENT" => Rc::new(PercentItem(calculation(item.get_underlying_number()))),
+        "FRACTION" => {
+            let number = calculation(item.get_underlying_number());
+            match FractionItem::from_number(number) {
+                Some(fraction) => Rc::new(fraction),
+                None => Rc::new(NumberItem(number, NumberType::Decimal))
+            }
+        },
+        "DYNAMIC_TYPE" => {
+            let dynamic_type = item.as_any().downcast_ref::<DynamicTypeItem>().unwrap();
+            Rc::new(DynamicTypeItem(calculation(dynamic_type.get_number()), dynamic_type.get_type()))
+        },
+        _ => return Err(SmartCalcError::InvalidArgument(format!("{} is not supported", item.type_name())))
+    };
+
+    let number = result.get_underlying_number();
+    match number.is_finite() {
+        true => Ok(result),
+        false if number.is_infinite() => Err(SmartCalcError::Overflow),
+        false => Err(SmartCalcError::InvalidArgument("Invalid argument".to_string()))
+    }
+}
+
+fn get_digits(name: &str, arguments: &[Rc<dyn DataItem>]) -> core::result::Result<i32, SmartCalcError> {
+    match arguments.get(1) {
+        Some(digits) if digits.type_name() == "NUMBER" => Ok(digits.get_underlying_number() as i32),
+        Some(_) => Err(SmartCalcError::InvalidArgument(format!("{} digits must be a number", name))),
+        None => Ok(0)
+    }
+}
+
+fn round_with<F: Fn(f64) -> f64>(name: &str, arguments: &[Rc<dyn DataItem>], calculation: F) -> core::result::Result<Rc<dyn DataItem>, SmartCalcError> {
+    check_arguments(name, arguments, 1, 2)?;
+    let multiplier = 10_f64.powi(get_digits(name, arguments)?);
+    map_number(&arguments[0], |number| calculation(number * multiplier) / multiplier)
+}
+
+fn find_item(config: &SmartCalcConfig, name: &str, arguments: &[Rc<dyn DataItem>], find_max: bool) -> core::result::Result<Rc<dyn DataItem>, SmartCalcError> {
+    if arguments.is_empty() {
+        return Err(SmartCalcError::InvalidArgument(format!("{} requires at least 1 argument", name)));
+    }
+
+    let arguments = get_items(name, arguments)?;
+    let mut found = arguments[0].clone();
+    for argument in arguments.iter().skip(1) {
+        /* Comparison made with the unit of the current item, 3 km - 2000 m is 1 km */
+        let difference = match argument.calculate(config, true, found.as_ref(), OperationType::Sub) {
+            Some(difference) => difference.get_underlying_number(),
+            None => return Err(SmartCalcError::InvalidArgument(format!("{} and {} could not be compared", argument.type_name(), found.type_name())))
+        };
+
+        if (find_max && difference > 0.0) || (!find_max && difference < 0.0) {
+            found = argument.clone();
+        }
+    }
+
+    Ok(found)
+}
+
+pub fn sqrt(_: &SmartCalcConfig, arguments: &[Rc<dyn DataItem>]) -> core::result::Result<Rc<dyn DataItem>, SmartCalcError> {
+    check_arguments("sqrt", arguments, 1, 1)?;
+    map_number(&arguments[0], f64::sqrt)
+}
+
+pub fn abs(_: &SmartCalcConfig, arguments: &[Rc<dyn DataItem>]) -> core::result::Result<Rc<dyn DataItem>, SmartCalcError> {
+    check_arguments("abs", arguments, 1, 1)?;
+    map_number(&arguments[0], f64::abs)
+}
+
+pub fn round(_: &SmartCalcConfig, arguments: &[Rc<dyn DataItem>]) -> core::result::Result<Rc<dyn DataItem>, SmartCalcError> {
+    round_with("round", arguments, f64::round)
+}
+
+pub fn floor(_: &SmartCalcConfig, arguments: &[Rc<dyn DataItem>]) -> core::result::Result<Rc<dyn DataItem>, SmartCalcError> {
+    round_with("floor", arguments, f64::floor)
+}
+
+pub fn ceil(_: &SmartCalcConfig, arguments: &[Rc<dyn DataItem>]) -> core::result::Result<Rc<dyn DataItem>, SmartCalcError> {
+    round_with("ceil", arguments, f64::ceil)
+}
+
+pub fn min(config: &SmartCalcConfig, arguments: &[Rc<dyn DataItem>]) -> core::result::Result<Rc<dyn DataItem>, SmartCalcError> {
+    find_item(config, "min", arguments, false)
+}
+
+pub fn max(config: &SmartCalcConfig, arguments: &[Rc<dyn DataItem>]) -> core::result::Result<Rc<dyn DataItem>, SmartCalcError> {
+    find_item(config, "max", arguments, true)
+}
+
+pub fn ln(_: &SmartCalcConfig, arguments: &[Rc<dyn DataItem>]) -> core::result::Result<Rc<dyn DataItem>, SmartCalcError> {
+    check_arguments("ln", arguments, 1, 1)?;
+    map_number(&arguments[0], f64::ln)
+}
+
+pub fn log(_: &SmartCalcConfig, arguments: &[Rc<dyn DataItem>]) -> core::result::Result<Rc<dyn DataItem>, SmartCalcError> {
+    check_arguments("log", arguments, 1, 2)?;
+    let base = match arguments.get(1) {
+        Some(base) if base.type_name() == "NUMBER" => base.get_underlying_number(),
+        Some(_) => return Err(SmartCalcError::InvalidArgument("log base must be a number".to_string())),
+        None => 10.0
+    };
+    map_number(&arguments[0], |number| number.log(base))
+}
+
+pub fn log10(_: &SmartCalcConfig, arguments: &[Rc<dyn DataItem>]) -> core::result::Result<Rc<dyn DataItem>, SmartCalcError> {
+    check_arguments("log10", arguments, 1, 1)?;
+    map_number(&arguments[0], f64::log10)
+}
+
+pub fn sin(_: &SmartCalcConfig, arguments: &[Rc<dyn DataItem>]) -> core::result::Result<Rc<dyn DataItem>, SmartCalcError> {
+    check_arguments("sin", arguments, 1, 1)?;
+    map_number(&arguments[0], f64::sin)
+}
+
+pub fn cos(_: &SmartCalcConfig, arguments: &[Rc<dyn DataItem>]) -> core::result::Result<Rc<dyn DataItem>, SmartCalcError> {
+    check_arguments("cos", arguments, 1, 1)?;
+    map_number(&arguments[0], f64::cos)
+}
+
+pub fn tan(_: &SmartCalcConfig, arguments: &[Rc<dyn DataItem>]) -> core::result::Result<Rc<dyn DataItem>, SmartCalcError> {
+    check_arguments("tan", arguments, 1, 1)?;
+    map_number(&arguments[0], f64::tan)
+}
+
+pub fn asin(_: &SmartCalcConfig, arguments: &[Rc<dyn DataItem>]) -> core::result::Result<Rc<dyn DataItem>, SmartCalcError> {
+    check_arguments("asin", arguments, 1, 1)?;
+    map_number(&arguments[0], f64::asin)
+}
+
+pub fn acos(_: &SmartCalcConfig, arguments: &[Rc<dyn DataItem>]) -> core::result::Result<Rc<dyn DataItem>, SmartCalcError> {
+    check_arguments("acos", arguments, 1, 1)?;
+    map_number(&arguments[0], f64::acos)
+}
+
+pub fn atan(_: &SmartCalcConfig, arguments: &[Rc<dyn DataItem>]) -> core::result::Result<Rc<dyn DataItem>, SmartCalcError> {
+    check_arguments("atan", arguments, 1, 1)?;
+    map_number(&arguments[0], f64::atan)
+}
+
+pub fn factorial(_: &SmartCalcConfig, arguments: &[Rc<dyn DataItem>]) -> core::result::Result<Rc<dyn DataItem>, SmartCalcError> {
+    check_arguments("factorial", arguments, 1, 1)?;
+    if arguments[0].type_name() != "NUMBER" {
+        return Err(SmartCalcError::InvalidArgument("factorial requires a number".to_string()));
+    }
+
+    let number = arguments[0].get_underlying_number();
+
+    /* 170! is the biggest factorial that fits into f64 */
+    if !(0.0..=170.0).contains(&number) || number.fract() != 0.0 {
+        return Err(SmartCalcError::InvalidArgument("factorial requires an integer between 0 and 170".to_string()));
+    }
+
+    let result = (1..=number as u64).fold(1.0, |total, item| total * item as f64);
+    Ok(Rc::new(NumberItem(result, NumberType::Decimal)))
+}
+
+pub fn byteswap(config: &SmartCalcConfig, arguments: &[Rc<dyn DataItem>]) -> core::result::Result<Rc<dyn DataItem>, SmartCalcError> {
+    check_arguments("byteswap", arguments, 1, 2)?;
+    if arguments[0].type_name() != "NUMBER" {
+        return Err(SmartCalcError::InvalidArgument("byteswap requires a number".to_string()));
+    }
+
+    let value = match get_integer(arguments[0].as_ref()) {
+        Some(value) => value,
+        None => return Err(SmartCalcError::InvalidArgument("byteswap requires an integer".to_string()))
+    };
+
+    /* Without explicit width the configured integer width is used, arbitrary width swaps 64 bit */
+    let bits = match arguments.get(1) {
+        Some(bits) if bits.type_name() == "NUMBER" => match bits.get_underlying_number() {
+            bits if bits == 16.0 || bits == 32.0 || bits == 64.0 => bits as u8,
+            _ => return Err(SmartCalcError::InvalidArgument("byteswap width must be 16, 32 or 64".to_string()))
+        },
+        Some(_) => return Err(SmartCalcError::InvalidArgument("byteswap width must be a number".to_string())),
+        None => match config.integer_config.bits {
+            0 => 64,
+            bits => bits
+        }
+    };
+
+    let swapped = match bits {
+        8 => value as u8 as i128,
+        16 => (value as u16).swap_bytes() as i128,
+        32 => (value as u32).swap_bytes() as i128,
+        _ => (value as u64).swap_bytes() as i128
+    };
+
+    let result = match arguments.len() {
+        1 => config.integer_config.wrap(swapped),
+        _ => swapped
+    };
+    Ok(with_integer(arguments[0].as_ref(), result))
+}
+
+#[cfg(test)]
+#[test]
+fn round_test() {
+    let config = SmartCalcConfig::default();
+    let usd = config.get_currency("usd".to_string()).unwrap();
+
+    let result = round(&config, &[Rc::new(MoneyItem(12.345, usd.clone())), Rc::new(NumberItem(1.0, NumberType::Decimal))]).unwrap();
+    assert_eq!(result.type_name(), "MONEY");
+    assert_eq!(result.get_underlying_number(), 12.3);
+
+    let result = floor(&config, &[Rc::new(NumberItem(12.345, NumberType::Decimal)), Rc::new(NumberItem(2.0, NumberType::Decimal))]).unwrap();
+    assert_eq!(result.get_underlying_number(), 12.34);
+
+    let result = ceil(&config, &[Rc::new(NumberItem(12.345, NumberType::Decimal))]).unwrap();
+    assert_eq!(result.get_underlying_number(), 13.0);
+
+    assert!(round(&config, &[]).is_err());
+    assert!(round(&config, &[Rc::new(NumberItem(1.0, NumberType::Decimal)), Rc::new(MoneyItem(1.0, usd))]).is_err());
+}
+
+#[cfg(test)]
+#[test]
+fn factorial_test() {
+    let config = SmartCalcConfig::default();
+
+    assert_eq!(factorial(&config, &[Rc::new(NumberItem(0.0, NumberType::Decimal))]).unwrap().get_underlying_number(), 1.0);
+    assert_eq!(factorial(&config, &[Rc::new(NumberItem(5.0, NumberType::Decimal))]).unwrap().get_underlying_number(), 120.0);
+    assert!(factorial(&config, &[Rc::new(NumberItem(-1.0, NumberType::Decimal))]).is_err());
+    assert!(factorial(&config, &[Rc::new(NumberItem(1.5, NumberType::Decimal))]).is_err());
+    assert!(factorial(&config, &[Rc::new(NumberItem(171.0, NumberType::Decimal))]).is_err());
+}
+
+#[cfg(test)]
+#[test]
+fn byteswap_test() {
+    let config = SmartCalcConfig::default();
+
+    let result = byteswap(&config, &[Rc::new(IntegerItem(0x1234, NumberType::Hexadecimal)), Rc::new(NumberItem(16.0, NumberType::Decimal))]).unwrap();
+    assert_eq!(result.get_underlying_number(), 13330.0);
+    assert_eq!(result.as_token_type(), crate::types::TokenType::Integer(0x3412, NumberType::Hexadecimal));
+
+    let result = byteswap(&config, &[Rc::new(IntegerItem(0x1234567890ABCDEF, NumberType::Hexadecimal))]).unwrap();
+    assert_eq!(result.as_token_type(), crate::types::TokenType::Integer(0xEFCDAB9078563412_u64 as i64 as i128, NumberType::Hexadecimal));
+
+    let result = byteswap(&config, &[Rc::new(NumberItem(1.0, NumberType::Decimal)), Rc::new(NumberItem(32.0, NumberType::Decimal))]).unwrap();
+    assert_eq!(result.get_underlying_number(), 16777216.0);
+
+    /* Default 64 bit signed width */
+    let result = byteswap(&config, &[Rc::new(NumberItem(128.0, NumberType::Decimal))]).unwrap();
+    assert_eq!(result.get_underlying_number(), i64::MIN as f64);
+
+    assert!(byteswap(&config, &[Rc::new(NumberItem(1.5, NumberType::Decimal))]).is_err());
+    assert!(byteswap(&config, &[Rc::new(NumberItem(1.0, NumberType::Decimal)), Rc::new(NumberItem(12.0, NumberType::Decimal))]).is_err());
+}
@@ -0,0 +1,94 @@
+/*
+ * smartcalc v1.0.8
+ * Copyright (c) Erhan BARIS (Ruslan Ognyanov Asenov)
+ * Licensed under the GNU General Public License v2.0.
+ */
+
+pub mod math_functions;
+pub mod statistics_functions;
+pub mod random_functions;
+
+use alloc::rc::Rc;
+use alloc::string::ToString;
+use alloc::collections::btree_map::BTreeMap;
+use alloc::string::String;
+use lazy_static::*;
+
+use crate::FunctionTrait;
+use crate::compiler::DataItem;
+use crate::config::SmartCalcConfig;
+use crate::error::SmartCalcError;
+use crate::session::Session;
+use crate::types::{FunctionFunc, SessionFunctionFunc};
+
+use self::math_functions::*;
+use self::statistics_functions::*;
+use self::random_functions::*;
+
+pub enum FunctionType {
+    Internal {
+        function: FunctionFunc
+    },
+    /* Functions that use the session, like random numbers */
+    Session {
+        function: SessionFunctionFunc
+    },
+    Api {
+        function: Rc<dyn FunctionTrait>
+    }
+}
+
+impl FunctionType {
+    pub fn call(&self, config: &SmartCalcConfig, session: &Session, arguments: &[Rc<dyn DataItem>]) -> core::result::Result<Rc<dyn DataItem>, SmartCalcError> {
+        match self {
+            FunctionType::Internal { function } => function(config, arguments),
+            FunctionType::Session { function } => function(config, session, arguments),
+            FunctionType::Api { function } => function.call(config, arguments)
+        }
+    }
+}
+
+lazy_static! {
+        pub static ref FUNCTIONS: BTreeMap<String, FunctionFunc> = {
+        let mut m = BTreeMap::new();
+        m.insert("sqrt".to_string(),      sqrt as FunctionFunc);
+        m.insert("abs".to_string(),       abs as FunctionFunc);
+        m.insert("round".to_string(),     round as FunctionFunc);
+        m.insert("floor".to_string(),     floor as FunctionFunc);
+        m.insert("ceil".to_string(),      ceil as FunctionFunc);
+        m.insert("min".to_string(),       min as FunctionFunc);
+        m.insert("max".to_string(),       max as FunctionFunc);
+
+        m.insert("ln".to_string(),        ln as FunctionFunc);
+        m.insert("log".to_string(),       log as FunctionFunc);
+        m.insert("log10".to_string(),     log10 as FunctionFunc);
+
+        m.insert("sin".to_string(),       sin as FunctionFunc);
+        m.insert("cos".to_string(),       cos as FunctionFunc);
+        m.insert("tan".to_string(),       tan as FunctionFunc);
+        m.insert("asin".to_string(),      asin as FunctionFunc);
+        m.insert("acos".to_string(),      acos as FunctionFunc);
+        m.insert("atan".to_string(),      atan as FunctionFunc);
+
+        m.insert("factorial".to_string(), factorial as FunctionFunc);
+        m.insert("byteswap".to_string(),  byteswap as FunctionFunc);
+
+        m.insert("list".to_string(),       list as FunctionFunc);
+        m.insert("sum".to_string(),        sum as FunctionFunc);
+        m.insert("average".to_string(),    average as FunctionFunc);
+        m.insert("median".to_string(),     median as FunctionFunc);
+        m.insert("count".to_string(),      count as FunctionFunc);
+        m.insert("stddev".to_string(),     stddev as FunctionFunc);
+        m.insert("percentile".to_string(), percentile as FunctionFunc);
+        m
+    };
+}
+
+lazy_static! {
+        pub static ref SESSION_FUNCTIONS: BTreeMap<String, SessionFunctionFunc> = {
+        let mut m = BTreeMap::new();
+        m.insert("random".to_string(),    random as SessionFunctionFunc);
+        m.insert("pick".to_string(),      pick as SessionFunctionFunc);
+        m
+    };
+}
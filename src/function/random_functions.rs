@@ -0,0 +1,148 @@
+/*
+ * smartcalc v1.0.8
+ * Copyright (c) Erhan BARIS (Ruslan Ognyanov Asenov)
+ * Licensed under the GNU General Public License v2.0.
+ */
+
+use alloc::rc::Rc;
+use alloc::vec::Vec;
+use alloc::string::ToString;
+use rand::Rng;
+use rand::seq::index::sample;
+
+use crate::compiler::DataItem;
+use crate::compiler::number::NumberItem;
+use crate::compiler::money::get_money_item;
+use crate::compiler::list::ListItem;
+use crate::config::SmartCalcConfig;
+use crate::error::SmartCalcError;
+use crate::session::Session;
+use crate::types::NumberType;
+
+use super::statistics_functions::{flatten_arguments, get_items, get_values, with_number};
+
+/* Smallest difference between two random values. Money uses the digits of the currency, integer bounds give integers */
+fn get_step(unit: &dyn DataItem, low: f64, high: f64) -> Option<f64> {
+    match get_money_item(unit) {
+        Some(money) => Some(10_f64.powi(-(money.get_currency().decimal_digits as i32))),
+        None if low.fract() == 0.0 && high.fract() == 0.0 => Some(1.0),
+        None => None
+    }
+}
+
+/* "random" is between 0 and 1, "random(1, 100)" is between 1 and 100 and both bounds can be the result */
+pub fn random(config: &SmartCalcConfig, session: &Session, arguments: &[Rc<dyn DataItem>]) -> core::result::Result<Rc<dyn DataItem>, SmartCalcError> {
+    if arguments.is_empty() {
+        return Ok(Rc::new(NumberItem(session.with_random(|random| random.gen::<f64>()), NumberType::Decimal)));
+    }
+
+    let (unit, values) = get_values(config, "random", arguments)?;
+    if values.len() != 2 {
+        return Err(SmartCalcError::InvalidArgument("random requires 0 or 2 arguments".to_string()));
+    }
+
+    let low = values[0].min(values[1]);
+    let high = values[0].max(values[1]);
+    let number = match get_step(unit.as_ref(), low, high) {
+        Some(step) => {
+            let steps = ((high - low) / step).round() as i64;
+            low + session.with_random(|random| random.gen_range(0, steps + 1)) as f64 * step
+        },
+        None if low < high => session.with_random(|random| random.gen_range(low, high)),
+        None => low
+    };
+
+    with_number(&unit, number)
+}
+
+/* First argument is the count, "pick(2, 10, 20, 30)". Same item is not picked twice */
+pub fn pick(_: &SmartCalcConfig, session: &Session, arguments: &[Rc<dyn DataItem>]) -> core::result::Result<Rc<dyn DataItem>, SmartCalcError> {
+    let count = match arguments.first() {
+        Some(count) if count.type_name() == "NUMBER" && count.get_underlying_number() >= 1.0 && count.get_underlying_number().fract() == 0.0 => count.get_underlying_number() as usize,
+        _ => return Err(SmartCalcError::InvalidArgument("pick count must be a positive integer".to_string()))
+    };
+
+    /* Text items are picked as they are written, numbers in the list do not get a unit from them */
+    let items = flatten_arguments(&arguments[1..]);
+    let items = match items.iter().any(|item| item.type_name() == "TEXT") {
+        true => items,
+        false => get_items("pick", &arguments[1..])?
+    };
+    if count > items.len() {
+        return Err(SmartCalcError::InvalidArgument(alloc::format!("pick can not choose {} of {} values", count, items.len())));
+    }
+
+    let mut picked: Vec<Rc<dyn DataItem>> = session.with_random(|random| sample(random, items.len(), count).iter().map(|index| items[index].clone()).collect());
+    match count {
+        1 => Ok(picked.remove(0)),
+        _ => Ok(Rc::new(ListItem(picked)))
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn random_test() {
+    let config = SmartCalcConfig::default();
+    let mut session = Session::new();
+    session.set_random_seed(Some(42));
+
+    for _ in 0..100 {
+        let result = random(&config, &session, &[]).unwrap().get_underlying_number();
+        assert!((0.0..1.0).contains(&result));
+
+        let result = random(&config, &session, &[Rc::new(NumberItem(1.0, NumberType::Decimal)), Rc::new(NumberItem(6.0, NumberType::Decimal))]).unwrap().get_underlying_number();
+        assert!((1.0..=6.0).contains(&result));
+        assert_eq!(result.fract(), 0.0);
+
+        let result = random(&config, &session, &[Rc::new(NumberItem(0.5, NumberType::Decimal)), Rc::new(NumberItem(0.75, NumberType::Decimal))]).unwrap().get_underlying_number();
+        assert!((0.5..0.75).contains(&result));
+    }
+
+    /* Hex stays hex */
+    use crate::compiler::integer::IntegerItem;
+    let result = random(&config, &session, &[Rc::new(IntegerItem(0, NumberType::Hexadecimal)), Rc::new(IntegerItem(255, NumberType::Hexadecimal))]).unwrap();
+    assert_eq!(result.as_any().downcast_ref::<IntegerItem>().unwrap().1, NumberType::Hexadecimal);
+
+    /* Money has cents */
+    use crate::compiler::money::MoneyItem;
+    let usd = config.get_currency("usd".to_string()).unwrap();
+    let result = random(&config, &session, &[Rc::new(NumberItem(10.0, NumberType::Decimal)), Rc::new(MoneyItem(20.0, usd))]).unwrap();
+    assert_eq!(result.type_name(), "MONEY");
+    let cents = result.get_underlying_number() * 100.0;
+    assert!((cents - cents.round()).abs() < 0.0001);
+    assert!((10.0..=20.0).contains(&result.get_underlying_number()));
+
+    assert!(random(&config, &session, &[Rc::new(NumberItem(1.0, NumberType::Decimal))]).is_err());
+
+    /* Same seed, same numbers */
+    let mut other = Session::new();
+    other.set_random_seed(Some(42));
+    session.set_random_seed(Some(42));
+    assert_eq!(random(&config, &session, &[]).unwrap().get_underlying_number(), random(&config, &other, &[]).unwrap().get_underlying_number());
+}
+
+#[cfg(test)]
+#[test]
+fn pick_test() {
+    let config = SmartCalcConfig::default();
+    let mut session = Session::new();
+    session.set_random_seed(Some(7));
+    let numbers: Vec<Rc<dyn DataItem>> = [10.0, 20.0, 30.0].iter().map(|number| Rc::new(NumberItem(*number, NumberType::Decimal)) as Rc<dyn DataItem>).collect();
+
+    let mut arguments = alloc::vec![Rc::new(NumberItem(1.0, NumberType::Decimal)) as Rc<dyn DataItem>];
+    arguments.extend(numbers.iter().cloned());
+    let result = pick(&config, &session, &arguments).unwrap();
+    assert_eq!(result.type_name(), "NUMBER");
+    assert!([10.0, 20.0, 30.0].contains(&result.get_underlying_number()));
+
+    arguments[0] = Rc::new(NumberItem(3.0, NumberType::Decimal));
+    let result = pick(&config, &session, &arguments).unwrap();
+    let mut picked: Vec<f64> = result.as_any().downcast_ref::<ListItem>().unwrap().get_items().iter().map(|item| item.get_underlying_number()).collect();
+    picked.sort_by(|left, right| left.partial_cmp(right).unwrap());
+    assert_eq!(picked, alloc::vec![10.0, 20.0, 30.0]);
+
+    arguments[0] = Rc::new(NumberItem(4.0, NumberType::Decimal));
+    assert!(pick(&config, &session, &arguments).is_err());
+    arguments[0] = Rc::new(NumberItem(0.0, NumberType::Decimal));
+    assert!(pick(&config, &session, &arguments).is_err());
+}
@@ -0,0 +1,226 @@
+/*
+ * smartcalc v1.0.8
+ * Copyright (c) Erhan BARIS (Ruslan Ognyanov Asenov)
+ * Licensed under the GNU General Public License v2.0.
+ */
+
+use core::ops::Deref;
+use alloc::format;
+use alloc::rc::Rc;
+use alloc::vec::Vec;
+use alloc::string::ToString;
+use chrono::Duration;
+
+use crate::compiler::DataItem;
+use crate::compiler::OperationType;
+use crate::compiler::number::NumberItem;
+use crate::compiler::money::get_money_item;
+use crate::compiler::duration::DurationItem;
+use crate::compiler::list::ListItem;
+use crate::config::SmartCalcConfig;
+use crate::error::SmartCalcError;
+use crate::types::NumberType;
+use crate::tools::{do_math, do_divition};
+
+use super::math_functions::map_number;
+
+/* Added with do_math, so the decimal feature gives the same total as adding the lines one by one */
+fn total_of(values: &[f64]) -> f64 {
+    values.iter().fold(0.0, |total, value| do_math(total, *value, OperationType::Add))
+}
+
+/* Lists are opened, "average(prices, 30 usd)" uses every item of the list */
+pub(super) fn flatten_arguments(arguments: &[Rc<dyn DataItem>]) -> Vec<Rc<dyn DataItem>> {
+    let mut items = Vec::with_capacity(arguments.len());
+    for argument in arguments.iter() {
+        match argument.as_any().downcast_ref::<ListItem>() {
+            Some(list) => items.extend(list.get_items().iter().cloned()),
+            None => items.push(argument.clone())
+        };
+    }
+    items
+}
+
+pub(super) fn with_number(item: &Rc<dyn DataItem>, number: f64) -> core::result::Result<Rc<dyn DataItem>, SmartCalcError> {
+    match item.type_name() {
+        "DURATION" => Ok(Rc::new(DurationItem(Duration::milliseconds((number * 1000.0).round() as i64)))),
+        _ => map_number(item, |_| number)
+    }
+}
+
+/* First value with a unit is used for all values, numbers without unit get that unit. "12, 15, 20 usd" is three prices */
+fn get_unit(name: &str, items: &[Rc<dyn DataItem>]) -> core::result::Result<Rc<dyn DataItem>, SmartCalcError> {
+    match items.iter().find(|item| item.type_name() != "NUMBER").or_else(|| items.first()) {
+        Some(unit) => Ok(unit.clone()),
+        None => Err(SmartCalcError::InvalidArgument(format!("{} requires at least 1 value", name)))
+    }
+}
+
+fn check_currency_rate(config: &SmartCalcConfig, unit: &dyn DataItem, item: &dyn DataItem) -> core::result::Result<(), SmartCalcError> {
+    if let (Some(left), Some(right)) = (get_money_item(unit), get_money_item(item)) {
+        for currency in [left.get_currency(), right.get_currency()].iter() {
+            if left.get_currency() != right.get_currency() && !config.currency_rate.contains_key(currency) {
+                return Err(SmartCalcError::MissingCurrencyRate(currency.code.to_string()));
+            }
+        }
+    }
+    Ok(())
+}
+
+/* Values in the unit of the first item, 10 usd and 20 eur are converted to usd with currency rates */
+pub(super) fn get_values(config: &SmartCalcConfig, name: &str, arguments: &[Rc<dyn DataItem>]) -> core::result::Result<(Rc<dyn DataItem>, Vec<f64>), SmartCalcError> {
+    let items = flatten_arguments(arguments);
+    let unit = get_unit(name, &items)?;
+    let base = unit.get_underlying_number();
+
+    let mut values = Vec::with_capacity(items.len());
+    for item in items.iter() {
+        if item.type_name() == "NUMBER" {
+            values.push(item.get_underlying_number());
+            continue;
+        }
+
+        check_currency_rate(config, unit.deref(), item.deref())?;
+        match unit.calculate(config, true, item.deref(), OperationType::Add) {
+            Some(total) if total.type_name() == unit.type_name() => values.push(do_math(total.get_underlying_number(), base, OperationType::Sub)),
+            _ => return Err(SmartCalcError::InvalidArgument(format!("{} and {} could not be used together", unit.type_name(), item.type_name())))
+        };
+    }
+
+    Ok((unit, values))
+}
+
+/* Values are sorted before, percentile is calculated with linear interpolation */
+fn percentile_of(values: &[f64], percentile: f64) -> f64 {
+    let rank = percentile / 100.0 * (values.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    values[lower] + (values[upper] - values[lower]) * (rank - lower as f64)
+}
+
+fn sorted_values(config: &SmartCalcConfig, name: &str, arguments: &[Rc<dyn DataItem>]) -> core::result::Result<(Rc<dyn DataItem>, Vec<f64>), SmartCalcError> {
+    let (unit, mut values) = get_values(config, name, arguments)?;
+    values.sort_by(|left, right| left.partial_cmp(right).unwrap_or(core::cmp::Ordering::Equal));
+    Ok((unit, values))
+}
+
+/* Lists are opened and numbers get the unit of the other values, used by min and max */
+pub fn get_items(name: &str, arguments: &[Rc<dyn DataItem>]) -> core::result::Result<Vec<Rc<dyn DataItem>>, SmartCalcError> {
+    let items = flatten_arguments(arguments);
+    let unit = get_unit(name, &items)?;
+    if unit.type_name() == "NUMBER" {
+        return Ok(items);
+    }
+
+    items.iter().map(|item| match item.type_name() {
+        "NUMBER" => with_number(&unit, item.get_underlying_number()),
+        _ => Ok(item.clone())
+    }).collect()
+}
+
+pub fn list(_: &SmartCalcConfig, arguments: &[Rc<dyn DataItem>]) -> core::result::Result<Rc<dyn DataItem>, SmartCalcError> {
+    Ok(Rc::new(ListItem(get_items("list", arguments)?)))
+}
+
+pub fn sum(config: &SmartCalcConfig, arguments: &[Rc<dyn DataItem>]) -> core::result::Result<Rc<dyn DataItem>, SmartCalcError> {
+    let (unit, values) = get_values(config, "sum", arguments)?;
+    with_number(&unit, total_of(&values))
+}
+
+pub fn average(config: &SmartCalcConfig, arguments: &[Rc<dyn DataItem>]) -> core::result::Result<Rc<dyn DataItem>, SmartCalcError> {
+    let (unit, values) = get_values(config, "average", arguments)?;
+    with_number(&unit, do_divition(total_of(&values), values.len() as f64))
+}
+
+pub fn median(config: &SmartCalcConfig, arguments: &[Rc<dyn DataItem>]) -> core::result::Result<Rc<dyn DataItem>, SmartCalcError> {
+    let (unit, values) = sorted_values(config, "median", arguments)?;
+    with_number(&unit, percentile_of(&values, 50.0))
+}
+
+pub fn count(_: &SmartCalcConfig, arguments: &[Rc<dyn DataItem>]) -> core::result::Result<Rc<dyn DataItem>, SmartCalcError> {
+    Ok(Rc::new(NumberItem(flatten_arguments(arguments).len() as f64, NumberType::Decimal)))
+}
+
+/* Sample standard deviation, same as STDEV of spreadsheets */
+pub fn stddev(config: &SmartCalcConfig, arguments: &[Rc<dyn DataItem>]) -> core::result::Result<Rc<dyn DataItem>, SmartCalcError> {
+    let (unit, values) = get_values(config, "stddev", arguments)?;
+    if values.len() < 2 {
+        return Err(SmartCalcError::InvalidArgument("stddev requires at least 2 values".to_string()));
+    }
+
+    let average = do_divition(total_of(&values), values.len() as f64);
+    let variance = values.iter().map(|value| (value - average).powi(2)).sum::<f64>() / (values.len() - 1) as f64;
+    with_number(&unit, variance.sqrt())
+}
+
+/* First argument is the percentile, percentile(90, 3, 5, 9, 11) */
+pub fn percentile(config: &SmartCalcConfig, arguments: &[Rc<dyn DataItem>]) -> core::result::Result<Rc<dyn DataItem>, SmartCalcError> {
+    let percentile = match arguments.first() {
+        Some(percentile) if percentile.type_name() == "NUMBER" && (0.0..=100.0).contains(&percentile.get_underlying_number()) => percentile.get_underlying_number(),
+        _ => return Err(SmartCalcError::InvalidArgument("percentile must be a number between 0 and 100".to_string()))
+    };
+
+    let (unit, values) = sorted_values(config, "percentile", &arguments[1..])?;
+    with_number(&unit, percentile_of(&values, percentile))
+}
+
+#[cfg(test)]
+#[test]
+fn statistics_test() {
+    let config = SmartCalcConfig::default();
+    let numbers: Vec<Rc<dyn DataItem>> = [3.0, 5.0, 9.0, 11.0].iter().map(|number| Rc::new(NumberItem(*number, NumberType::Decimal)) as Rc<dyn DataItem>).collect();
+
+    assert_eq!(sum(&config, &numbers).unwrap().get_underlying_number(), 28.0);
+    assert_eq!(average(&config, &numbers).unwrap().get_underlying_number(), 7.0);
+    assert_eq!(median(&config, &numbers).unwrap().get_underlying_number(), 7.0);
+    assert_eq!(count(&config, &numbers).unwrap().get_underlying_number(), 4.0);
+    assert_eq!(stddev(&config, &numbers).unwrap().get_underlying_number(), (40.0_f64 / 3.0).sqrt());
+
+    let mut arguments = alloc::vec![Rc::new(NumberItem(75.0, NumberType::Decimal)) as Rc<dyn DataItem>];
+    arguments.push(Rc::new(ListItem(numbers)));
+    assert_eq!(percentile(&config, &arguments).unwrap().get_underlying_number(), 9.5);
+
+    assert!(stddev(&config, &[Rc::new(NumberItem(1.0, NumberType::Decimal))]).is_err());
+    assert!(percentile(&config, &[Rc::new(NumberItem(101.0, NumberType::Decimal))]).is_err());
+    assert!(average(&config, &[]).is_err());
+}
+
+#[cfg(test)]
+#[test]
+fn statistics_unit_test() {
+    use crate::compiler::money::MoneyItem;
+    use crate::tools::round_converted_price;
+
+    let config = SmartCalcConfig::default();
+    let usd = config.get_currency("usd".to_string()).unwrap();
+    let eur = config.get_currency("eur".to_string()).unwrap();
+
+    /* Numbers get the currency of the list */
+    let result = average(&config, &[Rc::new(NumberItem(12.0, NumberType::Decimal)), Rc::new(NumberItem(15.0, NumberType::Decimal)), Rc::new(MoneyItem(21.0, usd.clone()))]).unwrap();
+    assert_eq!(result.type_name(), "MONEY");
+    assert_eq!(result.get_underlying_number(), 16.0);
+
+    let rate = config.currency_rate.get(&eur).unwrap() / config.currency_rate.get(&usd).unwrap();
+    let result = sum(&config, &[Rc::new(MoneyItem(10.0, usd.clone())), Rc::new(MoneyItem(10.0, eur))]).unwrap();
+    assert_eq!(result.as_any().downcast_ref::<MoneyItem>().unwrap().get_currency(), usd);
+    assert!((result.get_underlying_number() - (10.0 + round_converted_price(&config, 10.0 / rate, &usd))).abs() < 0.0001);
+
+    let result = average(&config, &[Rc::new(DurationItem(Duration::hours(1))), Rc::new(DurationItem(Duration::minutes(30)))]).unwrap();
+    assert_eq!(result.type_name(), "DURATION");
+    assert_eq!(result.get_underlying_number(), 2700.0);
+
+    let result = get_items("min", &[Rc::new(NumberItem(5.0, NumberType::Decimal)), Rc::new(MoneyItem(1.0, usd))]).unwrap();
+    assert_eq!(result[0].type_name(), "MONEY");
+}
+
+#[cfg(all(test, feature = "decimal"))]
+#[test]
+fn statistics_decimal_test() {
+    use crate::compiler::money::MoneyItem;
+
+    let config = SmartCalcConfig::default();
+    let usd = config.get_currency("usd".to_string()).unwrap();
+    let prices = [Rc::new(MoneyItem(0.1, usd.clone())) as Rc<dyn DataItem>, Rc::new(MoneyItem(0.2, usd.clone())), Rc::new(MoneyItem(0.3, usd))];
+    assert_eq!(sum(&config, &prices).unwrap().get_underlying_number(), 0.6);
+    assert_eq!(average(&config, &prices).unwrap().get_underlying_number(), 0.2);
+}
@@ -26,20 +26,33 @@ pub(crate) mod token;
 pub(crate) mod config;
 pub(crate) mod smartcalc;
 pub(crate) mod variable;
+pub(crate) mod function;
 pub(crate) mod session;
+pub(crate) mod error;
 
 #[cfg(test)]
 mod tests;
 
 pub use smartcalc::SmartCalc;
 pub use session::Session;
-pub use config::SmartCalcConfig;
+pub use config::{SmartCalcConfig, RoundingMode, CurrencyDisplay, NegativeStyle};
 pub use types::SmartCalcAstType;
 pub use types::FieldType;
 pub use compiler::DataItem;
+pub use error::{SmartCalcError, SmartCalcWarning, ConfigError};
 pub use token::ui_token::UiToken;
 pub use token::ui_token::UiTokenType;
 pub use smartcalc::RuleTrait;
+pub use smartcalc::FunctionTrait;
+pub use smartcalc::CurrencyRateProvider;
+pub use smartcalc::{ExecuteResult, ExecuteLine, ExecuteLineResult, ExecutionLine};
+pub use smartcalc::{ExecuteResultData, ExecuteLineData, ResultUnit, RESULT_SCHEMA_VERSION};
+pub use compiler::OperationType;
+pub use compiler::number::NumberItem;
+pub use compiler::money::MoneyItem;
+pub use compiler::percent::PercentItem;
+pub use compiler::dynamic_type::DynamicTypeItem;
+pub use types::{CurrencyInfo, CurrencyRate, CurrencyConversion};
 pub use types::TokenType;
 pub use types::NumberType;
 pub use types::TimeOffset;
\ No newline at end of file
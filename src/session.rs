@@ -5,20 +5,43 @@
  */
 
 use core::cell::{Cell, RefCell};
-use alloc::collections::BTreeMap;
+use alloc::collections::{BTreeMap, BTreeSet};
 use alloc::string::{String, ToString};
 
 use alloc::{rc::Rc, vec::Vec};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
 use regex::Regex;
 
+use crate::compiler::DataItem;
+use crate::error::{SmartCalcError, SmartCalcWarning};
+use crate::types::{SmartCalcAstType, CurrencyConversion};
 use crate::variable::VariableInfo;
 
+/// Execution information of a line, used by line references and re-execution.
+#[derive(Default, Clone)]
+pub(crate) struct LineInfo {
+    pub(crate) result: Option<Rc<dyn DataItem>>,
+    pub(crate) variable: Option<Rc<VariableInfo>>,
+    pub(crate) used_variables: BTreeSet<String>,
+    pub(crate) used_lines: BTreeSet<usize>,
+    pub(crate) is_line_reference: bool,
+    pub(crate) currency_conversions: Vec<CurrencyConversion>,
+    pub(crate) warnings: Vec<SmartCalcWarning>,
+    pub(crate) error: Option<SmartCalcError>
+}
+
 #[derive(Default)]
 pub struct Session {
     text: String,
     text_parts: Vec<String>,
     language: String,
     position: Cell<usize>,
+    lines: RefCell<Vec<LineInfo>>,
+    dirty_lines: BTreeSet<usize>,
+    random_seed: Option<u64>,
+    random: RefCell<Option<StdRng>>,
+    preferred_currencies: Vec<String>,
 
     pub(crate) variables: RefCell<BTreeMap<String, Rc<VariableInfo>>>
 }
@@ -33,6 +56,11 @@ impl Session {
             text_parts: Vec::new(),
             language: String::new(),
             variables: RefCell::new(BTreeMap::new()),
+            lines: RefCell::new(Vec::new()),
+            dirty_lines: BTreeSet::new(),
+            random_seed: None,
+            random: RefCell::new(None),
+            preferred_currencies: Vec::new(),
             position: Cell::default()
         }
     }
@@ -40,6 +68,8 @@ impl Session {
     /// Set the text to be executed.
     pub fn set_text(&mut self, text: String) {
         self.text = text;
+        self.lines.borrow_mut().clear();
+        self.dirty_lines.clear();
         
         self.text_parts = match Regex::new(r"\r\n|\n") {
             Ok(re) => re.split(&self.text).map(|item| item.to_string()).collect::<Vec<_>>(),
@@ -47,6 +77,31 @@ impl Session {
         };
     }
 
+    /// Set the seed of random numbers and dices. Every line of a seeded session gives the same
+    /// random numbers on each execution, `None` uses a new seed from the system.
+    pub fn set_random_seed(&mut self, seed: Option<u64>) {
+        self.random_seed = seed;
+        *self.random.borrow_mut() = None;
+    }
+
+    pub(crate) fn with_random<T, F: FnOnce(&mut StdRng) -> T>(&self, func: F) -> T {
+        let mut random = self.random.borrow_mut();
+        let random = random.get_or_insert_with(|| match self.random_seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy()
+        });
+        func(random)
+    }
+
+    /// Currencies used for shared symbols like "$" and "kr", they are checked before the preferred currencies of the configuration.
+    pub fn set_preferred_currencies(&mut self, currencies: Vec<String>) {
+        self.preferred_currencies = currencies.iter().map(|code| code.to_uppercase()).collect();
+    }
+
+    pub(crate) fn get_preferred_currencies(&self) -> &[String] {
+        &self.preferred_currencies
+    }
+
     /// Set the language used to interpret input.
     pub fn set_language(&mut self, language: String) {
         self.language = language;
@@ -79,6 +134,203 @@ impl Session {
         self.variables.borrow_mut().insert(variable_info.to_string(), variable_info);
     }
     
+    /// Update text of a single line, changed lines are executed with SmartCalc::reexecute.
+    pub fn update_line(&mut self, index: usize, text: String) -> bool {
+        if text.contains('\n') {
+            return false;
+        }
+
+        match self.text_parts.get_mut(index) {
+            Some(line) => {
+                *line = text;
+                self.text = self.text_parts.join("\n");
+                self.dirty_lines.insert(index);
+                true
+            },
+            None => false
+        }
+    }
+
+    pub(crate) fn set_position(&self, position: usize) {
+        self.position.set(position);
+    }
+
+    pub(crate) fn get_position(&self) -> usize {
+        self.position.get()
+    }
+
+    /// Lines which are updated or never executed.
+    pub(crate) fn is_dirty_line(&self, index: usize) -> bool {
+        self.dirty_lines.contains(&index) || self.lines.borrow().len() <= index
+    }
+
+    pub(crate) fn get_first_dirty_line(&self) -> Option<usize> {
+        (0..self.line_count()).find(|index| self.is_dirty_line(*index))
+    }
+
+    pub(crate) fn clear_dirty_lines(&mut self) {
+        self.dirty_lines.clear();
+    }
+
+    fn update_line_info<F: FnOnce(&mut LineInfo)>(&self, update: F) {
+        let mut lines = self.lines.borrow_mut();
+        let position = self.position.get();
+
+        if lines.len() <= position {
+            lines.resize(position + 1, LineInfo::default());
+        }
+
+        update(&mut lines[position]);
+    }
+
+    pub(crate) fn get_line_info(&self, index: usize) -> LineInfo {
+        self.lines.borrow().get(index).cloned().unwrap_or_default()
+    }
+
+    /// Previous information of the current line is removed before execution.
+    pub(crate) fn start_line(&self) {
+        self.update_line_info(|info| *info = LineInfo::default());
+
+        /* Seed is changed for each line, so re-executing a line does not change the numbers of the other lines */
+        if let Some(seed) = self.random_seed {
+            *self.random.borrow_mut() = Some(StdRng::seed_from_u64(seed ^ (self.position.get() as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15)));
+        }
+    }
+
+    pub(crate) fn set_line_result(&self, result: Option<Rc<dyn DataItem>>) {
+        self.update_line_info(|info| info.result = result);
+    }
+
+    pub(crate) fn set_line_variable(&self, variable: Rc<VariableInfo>) {
+        self.update_line_info(|info| info.variable = Some(variable));
+    }
+
+    pub(crate) fn set_used_variables(&self, used_variables: BTreeSet<String>) {
+        self.update_line_info(|info| info.used_variables = used_variables);
+    }
+
+    pub(crate) fn add_currency_conversion(&self, conversion: CurrencyConversion) {
+        self.update_line_info(|info| {
+            if !info.currency_conversions.contains(&conversion) {
+                info.currency_conversions.push(conversion);
+            }
+        });
+    }
+
+    pub(crate) fn get_currency_conversions(&self) -> Vec<CurrencyConversion> {
+        self.get_line_info(self.position.get()).currency_conversions
+    }
+
+    pub(crate) fn add_warning(&self, warning: SmartCalcWarning) {
+        self.update_line_info(|info| {
+            if !info.warnings.contains(&warning) {
+                info.warnings.push(warning);
+            }
+        });
+    }
+
+    pub(crate) fn get_warnings(&self) -> Vec<SmartCalcWarning> {
+        self.get_line_info(self.position.get()).warnings
+    }
+
+    /// Error found before the execution, like a rule that could not be applied. Only the first error is kept.
+    pub(crate) fn set_line_error(&self, error: SmartCalcError) {
+        self.update_line_info(|info| {
+            info.error.get_or_insert(error);
+        });
+    }
+
+    pub(crate) fn get_line_error(&self) -> Option<SmartCalcError> {
+        self.get_line_info(self.position.get()).error
+    }
+
+    /// Line uses a total or a line reference, so it is not a part of the block totals.
+    pub(crate) fn set_line_reference(&self) {
+        self.update_line_info(|info| info.is_line_reference = true);
+    }
+
+    fn add_used_line(&self, index: usize) {
+        self.update_line_info(|info| {
+            info.used_lines.insert(index);
+        });
+    }
+
+    /// Result of the line at given index, only lines above the current one can be referenced.
+    pub(crate) fn get_line_result(&self, index: usize) -> Option<Rc<dyn DataItem>> {
+        if index >= self.position.get() {
+            return None;
+        }
+
+        self.add_used_line(index);
+        self.lines.borrow().get(index).and_then(|info| info.result.clone())
+    }
+
+    /// Results of the lines above the current one, until an empty line or a comment header. Totals and line references are skipped.
+    pub(crate) fn get_block_results(&self) -> Vec<(usize, Rc<dyn DataItem>)> {
+        let mut results = Vec::new();
+
+        for index in (0..self.position.get()).rev() {
+            let line = self.text_parts[index].trim();
+            if line.is_empty() || line.starts_with('#') {
+                /* Changing the header line changes the block */
+                self.add_used_line(index);
+                break;
+            }
+
+            let result = self.get_line_result(index);
+            if self.lines.borrow().get(index).is_some_and(|info| info.is_line_reference) {
+                continue;
+            }
+
+            if let Some(result) = result {
+                results.insert(0, (index, result));
+            }
+        }
+
+        results
+    }
+
+    pub(crate) fn get_previous_line_result(&self) -> Option<Rc<dyn DataItem>> {
+        self.get_line_result(self.position.get().checked_sub(1)?)
+    }
+
+    /// Variables are rolled back to their values before the given line.
+    pub(crate) fn restore_variables(&self, line: usize) {
+        let lines = self.lines.borrow();
+        let defined_lines = lines.iter().take(line).filter_map(|info| Some((info.variable.as_ref()?.to_string(), info.result.clone()))).collect::<Vec<_>>();
+
+        let mut variables = self.variables.borrow_mut();
+        variables.retain(|name, _| defined_lines.iter().any(|(defined_name, _)| defined_name == name));
+
+        for (name, variable) in variables.iter() {
+            let value = defined_lines.iter().rev().filter(|(defined_name, _)| defined_name == name).find_map(|(_, result)| result.clone());
+            *variable.data.borrow_mut() = match value {
+                Some(item) => Rc::new(SmartCalcAstType::Item(item)),
+                None => Rc::new(SmartCalcAstType::None)
+            };
+        }
+    }
+
+    /// Variable defined by the current line is added back without executing the line.
+    pub(crate) fn restore_line_variable(&self) {
+        let info = self.get_line_info(self.position.get());
+        let variable = match info.variable {
+            Some(variable) => variable,
+            None => return
+        };
+
+        let variable = match self.variables.borrow().get(&variable.to_string()) {
+            Some(current) => current.clone(),
+            None => variable
+        };
+
+        if let Some(result) = info.result {
+            *variable.data.borrow_mut() = Rc::new(SmartCalcAstType::Item(result));
+        }
+
+        self.add_variable(variable);
+    }
+
     /// Returns the language configured for this session.
     pub fn get_language(&self) -> String {
         self.language.to_string()
@@ -6,34 +6,57 @@
 
 use core::borrow::Borrow;
 use core::ops::Deref;
-use alloc::collections::BTreeMap;
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::format;
 use alloc::vec;
 use alloc::vec::Vec;
 use alloc::rc::Rc;
 use alloc::string::{String, ToString};
-use anyhow::anyhow;
 use crate::{Session, TimeOffset};
 use crate::tokinizer::{read_currency, RuleType, small_date};
 
-use crate::compiler::Interpreter;
+use chrono::Utc;
+use serde_derive::Serialize;
+use crate::compiler::{Interpreter, DataItem};
+use crate::compiler::date::DateItem;
+use crate::compiler::date_time::DateTimeItem;
+use crate::compiler::dynamic_type::DynamicTypeItem;
+use crate::compiler::money::get_money_item;
+use crate::compiler::time::TimeItem;
+use crate::compiler::words::WordsItem;
+use crate::error::{SmartCalcError, SmartCalcWarning, ConfigError};
+use crate::function::FunctionType;
 use crate::logger::{LOGGER, initialize_logger};
 use crate::syntax::SyntaxParser;
 use crate::token::ui_token::UiToken;
 use crate::tokinizer::TokenInfo;
 use crate::tokinizer::Tokinizer;
 use crate::tools::parse_timezone;
-use crate::types::{TokenType, ExpressionFunc};
+use crate::types::{TokenType, ExpressionFunc, CurrencyInfo, CurrencyRate, CurrencyConversion};
 use crate::types::SmartCalcAstType;
 use crate::formatter::format_result;
-use crate::config::{SmartCalcConfig, DynamicType};
+use crate::config::{SmartCalcConfig, DynamicType, RoundingMode, CurrencyDisplay, NegativeStyle};
+use crate::constants::{JsonDynamicType, JsonDynamicTypeItem, LanguageRule, MAX_SIGNIFICANT_FIGURES};
 
 pub type ExecutionLine = Option<ExecuteLine>;
 
+/// Version of the serialized execution result, increased on breaking changes.
+pub const RESULT_SCHEMA_VERSION: u32 = 1;
+
 pub trait RuleTrait {
     fn name(&self) -> String;
     fn call(&self, smartcalc: &SmartCalcConfig, fields: &BTreeMap<String, TokenType>) -> Option<TokenType>;
 }
 
+pub trait FunctionTrait {
+    fn call(&self, smartcalc: &SmartCalcConfig, arguments: &[Rc<dyn DataItem>]) -> Result<Rc<dyn DataItem>, SmartCalcError>;
+}
+
+/// Source of currency rates, like a web service or a local rates file.
+pub trait CurrencyRateProvider {
+    fn get_rates(&self) -> Result<Vec<CurrencyRate>, String>;
+}
+
 #[derive(Debug)]
 #[derive(Default)]
 pub struct ExecuteResult {
@@ -55,15 +78,126 @@ impl ExecuteLineResult {
 
 #[derive(Debug)]
 pub struct ExecuteLine {
-    pub result: Result<ExecuteLineResult, String>,
+    pub result: Result<ExecuteLineResult, SmartCalcError>,
     pub raw_tokens: Vec<Rc<TokenType>>,
     pub ui_tokens: Vec<UiToken>,
-    pub calculated_tokens: Vec<Rc<TokenInfo>>
+    pub calculated_tokens: Vec<Rc<TokenInfo>>,
+    pub currency_conversions: Vec<CurrencyConversion>,
+    pub warnings: Vec<SmartCalcWarning>
 }
 
 impl ExecuteLine {
-    pub fn new(result: Result<ExecuteLineResult, String>, ui_tokens: Vec<UiToken>, raw_tokens: Vec<Rc<TokenType>>, calculated_tokens: Vec<Rc<TokenInfo>>) -> Self {
-        ExecuteLine { result, ui_tokens, raw_tokens, calculated_tokens }
+    pub fn new(result: Result<ExecuteLineResult, SmartCalcError>, ui_tokens: Vec<UiToken>, raw_tokens: Vec<Rc<TokenType>>, calculated_tokens: Vec<Rc<TokenInfo>>) -> Self {
+        ExecuteLine { result, ui_tokens, raw_tokens, calculated_tokens, currency_conversions: Vec::new(), warnings: Vec::new() }
+    }
+
+    pub fn to_data(&self) -> ExecuteLineData {
+        let mut data = ExecuteLineData {
+            version: RESULT_SCHEMA_VERSION,
+            output: None,
+            type_name: None,
+            value: None,
+            unit: None,
+            ui_tokens: self.ui_tokens.clone(),
+            currency_conversions: self.currency_conversions.clone(),
+            warnings: self.warnings.clone(),
+            error: None
+        };
+
+        match &self.result {
+            Ok(result) => {
+                data.output = Some(result.output.to_string());
+                if let SmartCalcAstType::Item(item) = result.ast.deref() {
+                    data.type_name = Some(item.type_name().to_string());
+                    data.value = Some(ResultUnit::get_value(item.deref()));
+                    data.unit = ResultUnit::from_item(item.deref());
+                }
+            },
+            Err(error) => data.error = Some(error.clone())
+        };
+
+        data
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum ResultUnit {
+    Currency {
+        code: String
+    },
+    DynamicType {
+        group: String,
+        index: usize
+    },
+    Timezone {
+        name: String,
+        offset: i32
+    }
+}
+
+impl ResultUnit {
+    fn from_item(item: &dyn DataItem) -> Option<ResultUnit> {
+        let timezone = |offset: &TimeOffset| Some(ResultUnit::Timezone { name: offset.name.to_string(), offset: offset.offset });
+
+        match item.type_name() {
+            "MONEY" => Some(ResultUnit::Currency { code: get_money_item(item)?.get_currency().code.to_string() }),
+            "WORDS" => Some(ResultUnit::Currency { code: item.as_any().downcast_ref::<WordsItem>()?.1.as_ref()?.code.to_string() }),
+            "DYNAMIC_TYPE" => {
+                let dynamic_type = item.as_any().downcast_ref::<DynamicTypeItem>()?.get_type();
+                Some(ResultUnit::DynamicType { group: dynamic_type.group_name.to_string(), index: dynamic_type.index })
+            },
+            "TIME" => timezone(&item.as_any().downcast_ref::<TimeItem>()?.1),
+            "DATE" => timezone(&item.as_any().downcast_ref::<DateItem>()?.1),
+            "DATE_TIME" => timezone(&item.as_any().downcast_ref::<DateTimeItem>()?.1),
+            _ => None
+        }
+    }
+
+    /* Date and time items are represented with unix timestamp */
+    fn get_value(item: &dyn DataItem) -> f64 {
+        let timestamp = match item.type_name() {
+            "TIME" => item.as_any().downcast_ref::<TimeItem>().map(|time| time.0.and_utc().timestamp()),
+            "DATE" => item.as_any().downcast_ref::<DateItem>().and_then(|date| date.0.and_hms_opt(0, 0, 0)).map(|date| date.and_utc().timestamp()),
+            "DATE_TIME" => item.as_any().downcast_ref::<DateTimeItem>().map(|date_time| date_time.0.and_utc().timestamp()),
+            _ => None
+        };
+
+        match timestamp {
+            Some(timestamp) => timestamp as f64,
+            None => item.get_underlying_number()
+        }
+    }
+}
+
+/// Serializable form of an executed line.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExecuteLineData {
+    pub version: u32,
+    pub output: Option<String>,
+    pub type_name: Option<String>,
+    pub value: Option<f64>,
+    pub unit: Option<ResultUnit>,
+    pub ui_tokens: Vec<UiToken>,
+    pub currency_conversions: Vec<CurrencyConversion>,
+    pub warnings: Vec<SmartCalcWarning>,
+    pub error: Option<SmartCalcError>
+}
+
+/// Serializable form of an execution, empty lines are null.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExecuteResultData {
+    pub version: u32,
+    pub status: bool,
+    pub lines: Vec<Option<ExecuteLineData>>
+}
+
+impl ExecuteResult {
+    pub fn to_data(&self) -> ExecuteResultData {
+        ExecuteResultData {
+            version: RESULT_SCHEMA_VERSION,
+            status: self.status,
+            lines: self.lines.iter().map(|line| line.as_ref().map(|line| line.to_data())).collect()
+        }
     }
 }
 
@@ -99,6 +233,10 @@ impl SmartCalc {
             Some(_) => false,
             None => {
                 self.config.types.insert(name.borrow().to_string(), BTreeMap::new());
+                self.config.json_data.types.push(JsonDynamicType {
+                    name: name.borrow().to_string(),
+                    items: Vec::new()
+                });
                 true
             }
         }
@@ -124,6 +262,20 @@ impl SmartCalc {
             parse_tokens.push(tokens);
         }
         
+        if let Some(json_type) = self.config.json_data.types.iter_mut().find(|item| item.name == name.borrow()) {
+            json_type.items.push(JsonDynamicTypeItem {
+                index,
+                format: format.borrow().to_string(),
+                parse: parse.iter().map(|item| item.borrow().to_string()).collect(),
+                names: names.clone(),
+                upgrade_code: Some(upgrade_code.borrow().to_string()),
+                downgrade_code: Some(downgrade_code.borrow().to_string()),
+                decimal_digits,
+                use_fract_rounding,
+                remove_fract_if_zero
+            });
+        }
+
         if let Some(dynamic_type) = self.config.types.get_mut(name.borrow()) {            
             dynamic_type.insert(index, Rc::new(DynamicType::new(name.borrow().to_string(), index, format.borrow().to_string(), parse_tokens, upgrade_code.borrow().to_string(), downgrade_code.borrow().to_string(), names, decimal_digits, use_fract_rounding, remove_fract_if_zero)));
         }
@@ -134,19 +286,69 @@ impl SmartCalc {
         self.config.money_config.remove_fract_if_zero = remove_fract_if_zero;
         self.config.money_config.use_fract_rounding = use_fract_rounding;
     }
+
+    /// Rounding mode of money results, half up by default.
+    pub fn set_money_rounding_mode(&mut self, rounding_mode: RoundingMode) {
+        self.config.money_config.rounding_mode = rounding_mode;
+    }
     
+    /// Currency display style and negative money style, a line can change the display with `as code`, `as name` or `as symbol`.
+    pub fn set_currency_display(&mut self, display: CurrencyDisplay, negative_style: NegativeStyle) {
+        self.config.money_config.display = display;
+        self.config.money_config.negative_style = negative_style;
+    }
+
     pub fn set_number_configuration(&mut self, decimal_digits: u8, remove_fract_if_zero: bool, use_fract_rounding: bool) {
         self.config.number_config.decimal_digits = decimal_digits;
         self.config.number_config.remove_fract_if_zero = remove_fract_if_zero;
         self.config.number_config.use_fract_rounding = use_fract_rounding;
     }
+
+    /// Rounding mode of number results and of rounding functions without explicit mode, half up by default.
+    pub fn set_number_rounding_mode(&mut self, rounding_mode: RoundingMode) {
+        self.config.number_config.rounding_mode = rounding_mode;
+    }
     
+    /// Limits for switching decimal numbers to scientific notation, 1e15 by default and no lower limit. Zero disables the limit.
+    pub fn set_scientific_notation(&mut self, upper_exponent: i32, lower_exponent: i32) {
+        self.config.number_config.scientific_upper_exponent = upper_exponent;
+        self.config.number_config.scientific_lower_exponent = lower_exponent;
+    }
+
+    /// Numbers, percentages and dynamic types are shown with the given significant figures, zero uses the decimal digits again.
+    pub fn set_significant_figures(&mut self, significant_figures: u8) -> bool {
+        match significant_figures {
+            0..=MAX_SIGNIFICANT_FIGURES => {
+                self.config.number_config.significant_figures = significant_figures;
+                self.config.percentage_config.significant_figures = significant_figures;
+                true
+            },
+            _ => false
+        }
+    }
+
     pub fn set_percentage_configuration(&mut self, decimal_digits: u8, remove_fract_if_zero: bool, use_fract_rounding: bool) {
         self.config.percentage_config.decimal_digits = decimal_digits;
         self.config.percentage_config.remove_fract_if_zero = remove_fract_if_zero;
         self.config.percentage_config.use_fract_rounding = use_fract_rounding;
     }
 
+    /// Rounding mode of percentage results, half up by default.
+    pub fn set_percentage_rounding_mode(&mut self, rounding_mode: RoundingMode) {
+        self.config.percentage_config.rounding_mode = rounding_mode;
+    }
+
+    pub fn set_integer_configuration(&mut self, bits: u8, signed: bool) -> bool {
+        match bits {
+            0 | 8 | 16 | 32 | 64 => {
+                self.config.integer_config.bits = bits;
+                self.config.integer_config.signed = signed;
+                true
+            },
+            _ => false
+        }
+    }
+
     pub fn set_decimal_seperator(&mut self, decimal_seperator: String) {
         self.config.decimal_seperator = decimal_seperator;
     }
@@ -156,6 +358,13 @@ impl SmartCalc {
     }
     
     pub fn set_date_rule(&mut self, language: &str, rules: Vec<String>) {                
+        if let Some(language_constant) = self.config.json_data.languages.get_mut(language) {
+            language_constant.rules.insert("small_date".to_string(), LanguageRule {
+                rules: rules.clone(),
+                samples: Vec::new()
+            });
+        }
+
         let mut function_items = Vec::new();
         for rule_item in rules {
             let mut session = Session::new();
@@ -222,15 +431,87 @@ impl SmartCalc {
         }
     }
 
+    pub fn try_load_from_json(json_data: &str) -> Result<Self, ConfigError> {
+        Ok(SmartCalc {
+            config: SmartCalcConfig::try_load_from_json(json_data)?
+        })
+    }
+
+    pub fn merge_json(&mut self, json_data: &str) -> Result<(), ConfigError> {
+        self.config.merge_json(json_data)
+    }
+
+    pub fn to_json(&self) -> Result<String, ConfigError> {
+        self.config.to_json()
+    }
+
     pub fn update_currency(&mut self, currency: &str, rate: f64) -> bool {
         match read_currency(&self.config, currency) {
             Some(real_currency) => {
+                self.config.currency_rate_info.remove(&real_currency);
                 self.config.currency_rate.insert(real_currency, rate);
                 true
             },
              _ => false
         }
     }
+
+    /// Custom currency or asset like BTC, rate is the amount of it for one USD, see `SmartCalcConfig::add_currency`.
+    pub fn add_currency(&mut self, currency: CurrencyInfo, rate: f64) -> bool {
+        self.config.add_currency(currency, rate)
+    }
+
+    pub fn add_currency_alias<T: Borrow<str>>(&mut self, alias: T, code: T) -> bool {
+        self.config.add_currency_alias(alias.borrow(), code.borrow())
+    }
+
+    /// Rates of the provider are loaded with their base currency and timestamp, returns the number of updated currencies.
+    /// Nothing is changed if the provider fails or one of the rates is not valid.
+    pub fn update_currency_rates(&mut self, provider: &dyn CurrencyRateProvider) -> Result<usize, ConfigError> {
+        let rates = provider.get_rates().map_err(|message| ConfigError::InvalidValue { path: "rates".to_string(), message })?;
+        self.config.update_currency_rates(&rates)
+    }
+
+    /// Rate and timestamp of the currency, built-in and manually updated rates have no timestamp.
+    pub fn get_currency_rate(&self, currency: &str) -> Option<CurrencyRate> {
+        let currency = read_currency(&self.config, currency)?;
+        self.config.currency_rate_info.get(&currency).cloned()
+    }
+
+    /// Dated rates for conversions like `100 usd to eur on 1 Mar 2024`, see `SmartCalcConfig::load_currency_rate_history_json`.
+    pub fn load_currency_rate_history_json(&mut self, json_data: &str) -> Result<usize, ConfigError> {
+        self.config.load_currency_rate_history_json(json_data)
+    }
+
+    /// Dated rates for conversions like `100 usd to eur on 1 Mar 2024`, see `SmartCalcConfig::load_currency_rate_history_csv`.
+    pub fn load_currency_rate_history_csv(&mut self, csv_data: &str) -> Result<usize, ConfigError> {
+        self.config.load_currency_rate_history_csv(csv_data)
+    }
+
+    /// Shared symbols like "$" and "kr" are read as the first preferred currency using them, see `Session::set_preferred_currencies` for a session.
+    pub fn set_preferred_currencies(&mut self, currencies: Vec<String>) -> bool {
+        self.config.set_preferred_currencies(currencies)
+    }
+
+    /// Lines using a currency rate older than the given seconds get a warning, zero disables the warning.
+    pub fn set_currency_rate_max_age(&mut self, seconds: u64) {
+        self.config.money_config.rate_max_age = seconds;
+    }
+
+    fn get_warnings(&self, conversions: &[CurrencyConversion]) -> Vec<SmartCalcWarning> {
+        let max_age = self.config.money_config.rate_max_age;
+        if max_age == 0 {
+            return Vec::new();
+        }
+
+        let now = Utc::now().timestamp();
+        conversions.iter()
+            .filter(|conversion| !conversion.historical)
+            .filter_map(|conversion| Some((conversion, conversion.timestamp?)))
+            .filter(|(_, timestamp)| now.saturating_sub(*timestamp) > max_age as i64)
+            .map(|(conversion, timestamp)| SmartCalcWarning::StaleCurrencyRate { from: conversion.from.to_string(), to: conversion.to.to_string(), timestamp })
+            .collect()
+    }
     
     pub fn delete_rule(&mut self, language: String, rule_name: String) -> bool {
         match self.config.rule.get_mut(&language) {
@@ -275,6 +556,32 @@ impl SmartCalc {
         true
     }
     
+    pub fn add_function<T: Borrow<str>>(&mut self, name: T, function: Rc<dyn FunctionTrait>) -> bool {
+        let name = name.borrow().to_lowercase();
+        let mut chars = name.chars();
+
+        /* Function name should be parsable by function tokinizer */
+        let is_valid_name = match chars.next() {
+            Some(first) => (first.is_alphabetic() || first == '_') && chars.all(|item| item.is_alphanumeric() || item == '_'),
+            None => false
+        };
+
+        if !is_valid_name || self.config.functions.contains_key(&name) {
+            return false;
+        }
+
+        self.config.functions.insert(name, FunctionType::Api { function });
+        true
+    }
+
+    pub fn delete_function<T: Borrow<str>>(&mut self, name: T) -> bool {
+        let name = name.borrow().to_lowercase();
+        match self.config.functions.get(&name) {
+            Some(FunctionType::Api { .. }) => self.config.functions.remove(&name).is_some(),
+            _ => false
+        }
+    }
+
     pub fn format_result(&self, session: &Session, result: Rc<SmartCalcAstType>) -> String {
         format_result(&self.config, session, result)
     }
@@ -301,12 +608,34 @@ impl SmartCalc {
             return None;
         }
 
+        session.set_used_variables(tokinizer.token_infos.iter().filter_map(|token| match token.token_type.borrow().deref() {
+            Some(TokenType::Variable(variable)) => Some(variable.to_string()),
+            _ => None
+        }).collect());
+
         let mut syntax = SyntaxParser::new(session, &tokinizer);
         log::debug!(" > parse starting");
 
-        let execution_result = match syntax.parse() {
+        let execution_result = match session.get_line_error() {
+            Some(error) => Err(error),
+            None => self.execute_tokens(session, &mut syntax)
+        };
+
+        let mut line = ExecuteLine::new(execution_result, tokinizer.ui_tokens.get_tokens(), tokinizer.tokens, tokinizer.token_infos.clone());
+        line.currency_conversions = session.get_currency_conversions();
+        line.warnings = session.get_warnings();
+        line.warnings.extend(self.get_warnings(&line.currency_conversions));
+        Some(line)
+    }
+
+    fn execute_tokens(&self, session: &Session, syntax: &mut SyntaxParser) -> Result<ExecuteLineResult, SmartCalcError> {
+        match syntax.parse() {
             Ok(ast) => {
                 log::debug!(" > parse Ok {:?}", ast);
+                if let SmartCalcAstType::Assignment { variable, .. } = &ast {
+                    session.set_line_variable(variable.clone());
+                }
+
                 let ast_rc = Rc::new(ast);
 
                 match Interpreter::execute(&self.config, ast_rc, session) {
@@ -314,14 +643,12 @@ impl SmartCalc {
                     Err(error) => Err(error)
                 }
             },
-            Err((error, _, _)) => {
+            Err(error) => {
                 log::debug!(" > parse Err");
                 log::info!("Syntax parse error, {}", error);
-                Err(error.to_string())
+                Err(error)
             }
-        };
-        
-        Some(ExecuteLine::new(execution_result, tokinizer.ui_tokens.get_tokens(), tokinizer.tokens, tokinizer.token_infos.clone()))
+        }
     }
 
     pub fn execute<Tlan: Borrow<str>, Tdata: Borrow<str>>(&self, language: Tlan, data: Tdata) -> ExecuteResult {
@@ -332,28 +659,28 @@ impl SmartCalc {
         self.execute_session(&session)
     }
 
-    pub fn basic_execute<T: Borrow<str>>(data: T, config: &SmartCalcConfig) -> anyhow::Result<f64> {
+    pub fn basic_execute<T: Borrow<str>>(data: T, config: &SmartCalcConfig) -> Result<f64, SmartCalcError> {
         let mut session = Session::new();
 
         session.set_text(data.borrow().to_string());
         session.set_language("en".borrow().to_string());
         
         if session.line_count() != 1 {
-            return Err(anyhow!("Multiline calculation not supported"));
+            return Err(SmartCalcError::MultilineNotSupported);
         }
         
         if !session.has_value() {
-            return Err(anyhow!("No data found"));
+            return Err(SmartCalcError::EmptyCalculation);
         }
 
         log::debug!("> {}", session.current_line());
         if session.current_line().is_empty() {
-            return Err(anyhow!("Calculation empty"));
+            return Err(SmartCalcError::EmptyCalculation);
         }
 
         let mut tokinizer = Tokinizer::new(config, &session);
         if !tokinizer.basic_tokinize() {
-            return Err(anyhow!("Syntax error"));
+            return Err(SmartCalcError::syntax("Syntax error", 0, session.current_line().chars().count()));
         }
 
         let mut syntax = SyntaxParser::new(&session, &tokinizer);
@@ -368,27 +695,49 @@ impl SmartCalc {
                     Ok(ast) => {
                         match ast.deref() {
                             SmartCalcAstType::Item(item) => Ok(item.get_underlying_number()),
-                            _ => Err(anyhow!("Number not found"))
+                            _ => Err(SmartCalcError::NumberNotFound)
                         }
                     },
-                    Err(error) => Err(anyhow!(error))
+                    Err(error) => Err(error)
                 }
             },
-            Err((error, _, _)) => {
+            Err(error) => {
                 log::debug!(" > parse Err");
                 log::info!("Syntax parse error, {}", error);
-                Err(anyhow!(error))
+                Err(error)
             }
         }
     }
 
+    fn get_line_item(line: &ExecutionLine) -> Option<Rc<dyn DataItem>> {
+        match line.as_ref()?.result.as_ref().ok()?.ast.deref() {
+            SmartCalcAstType::Item(item) => Some(item.clone()),
+            _ => None
+        }
+    }
+
+    fn execute_line(&self, session: &Session) -> ExecutionLine {
+        session.start_line();
+        let line_result = self.execute_text(session);
+        session.set_line_result(Self::get_line_item(&line_result));
+        line_result
+    }
+
+    fn is_same_result(left: &Option<Rc<dyn DataItem>>, right: &Option<Rc<dyn DataItem>>) -> bool {
+        match (left, right) {
+            (Some(left), Some(right)) => format!("{:?}", left) == format!("{:?}", right),
+            (None, None) => true,
+            _ => false
+        }
+    }
+
     pub fn execute_session(&self, session: &Session) -> ExecuteResult {
         let mut results = ExecuteResult::default();
 
         if session.has_value() {
             results.status = true;
             loop {
-                let line_result = self.execute_text(session);
+                let line_result = self.execute_line(session);
                 results.lines.push(line_result);
                 if session.next_line().is_none() {
                     break;
@@ -398,6 +747,61 @@ impl SmartCalc {
 
         results
     }
+
+    /// Execute lines changed with Session::update_line and the lines depending on them.
+    /// Returns the updated lines and the lines whose result changed.
+    pub fn reexecute(&self, session: &mut Session) -> BTreeMap<usize, ExecutionLine> {
+        let mut changed_lines = BTreeMap::new();
+        let first_line = match session.get_first_dirty_line() {
+            Some(first_line) => first_line,
+            None => return changed_lines
+        };
+
+        session.restore_variables(first_line);
+        session.set_position(first_line);
+
+        let mut changed_results = BTreeSet::new();
+        let mut changed_variables = BTreeSet::new();
+        let mut execute_all = false;
+
+        loop {
+            let index = session.get_position();
+            let line_info = session.get_line_info(index);
+            let is_dirty = session.is_dirty_line(index);
+
+            let should_execute = is_dirty || execute_all
+                || !line_info.used_lines.is_disjoint(&changed_results)
+                || !line_info.used_variables.is_disjoint(&changed_variables);
+
+            if should_execute {
+                let line_result = self.execute_line(session);
+                let new_info = session.get_line_info(index);
+
+                let old_variable = line_info.variable.as_ref().map(|variable| variable.to_string());
+                let new_variable = new_info.variable.as_ref().map(|variable| variable.to_string());
+
+                /* New or removed variable can change the tokens of any line below */
+                if old_variable != new_variable {
+                    execute_all = true;
+                }
+
+                if is_dirty || !Self::is_same_result(&line_info.result, &new_info.result) {
+                    changed_results.insert(index);
+                    changed_variables.extend(new_variable);
+                    changed_lines.insert(index, line_result);
+                }
+            } else {
+                session.restore_line_variable();
+            }
+
+            if session.next_line().is_none() {
+                break;
+            }
+        }
+
+        session.clear_dirty_lines();
+        changed_lines
+    }
 }
 
 #[cfg(test)]
@@ -405,7 +809,10 @@ mod test {
     use core::ops::Deref;
     use alloc::{collections::BTreeMap, string::{String, ToString}, vec, rc::Rc};
 
-    use crate::{SmartCalc, types::{TokenType, NumberType}, RuleTrait, SmartCalcConfig};
+    use crate::{SmartCalc, types::{TokenType, NumberType}, RuleTrait, FunctionTrait, SmartCalcConfig, SmartCalcError, RoundingMode};
+    use crate::compiler::DataItem;
+    use crate::compiler::money::MoneyItem;
+    use crate::tools::round_converted_price;
 
     #[derive(Default)]
     pub struct Test1;
@@ -549,7 +956,7 @@ mod test {
     }
 
     #[test]
-    fn basic_test_1() ->  anyhow::Result<()> {
+    fn basic_test_1() ->  Result<(), SmartCalcError> {
         let config = SmartCalcConfig::default();
         let result = SmartCalc::basic_execute("1024", &config)?;
         assert_eq!(result, 1024.0);
@@ -557,7 +964,7 @@ mod test {
     }
     
     #[test]
-    fn basic_test_2() ->  anyhow::Result<()> {
+    fn basic_test_2() ->  Result<(), SmartCalcError> {
         let config = SmartCalcConfig::default();
         let result = SmartCalc::basic_execute("1024 * 2", &config)?;
         assert_eq!(result, 2048.0);
@@ -565,7 +972,7 @@ mod test {
     }
     
     #[test]
-    fn basic_test_3() ->  anyhow::Result<()> {
+    fn basic_test_3() ->  Result<(), SmartCalcError> {
         let config = SmartCalcConfig::default();
         let error = match SmartCalc::basic_execute("a + 1024 * 2", &config) {
             Ok(_) => return Ok(()),
@@ -576,7 +983,7 @@ mod test {
     }
     
     #[test]
-    fn basic_test_4() ->  anyhow::Result<()> {
+    fn basic_test_4() ->  Result<(), SmartCalcError> {
         let config = SmartCalcConfig::default();
         let error = match SmartCalc::basic_execute("+ 1024 * 2", &config) {
             Ok(_) => return Ok(()),
@@ -587,7 +994,7 @@ mod test {
     }
     
     #[test]
-    fn basic_test_5() ->  anyhow::Result<()> {
+    fn basic_test_5() ->  Result<(), SmartCalcError> {
         let config = SmartCalcConfig::default();
         let error = match SmartCalc::basic_execute(r#"1+ 1024 * 2
 "#, &config) {
@@ -599,7 +1006,7 @@ mod test {
     }
     
     #[test]
-    fn basic_test_6() ->  anyhow::Result<()> {
+    fn basic_test_6() ->  Result<(), SmartCalcError> {
         let config = SmartCalcConfig::default();
         let error = match SmartCalc::basic_execute(r#""#, &config) {
             Ok(_) => return Ok(()),
@@ -663,7 +1070,465 @@ mod test {
         let test1 = Rc::new(Coin::default());
         calculater.add_rule("en".to_string(), vec!["{NUMBER:count} {TEXT:coin}".to_string()], test1.clone());
         let result = calculater.execute("en".to_string(), "10 eth to dkk");
-        check_basic_rule_output!(result, TokenType::Money(49644.9970792, calculater.config.get_currency("dkk".to_string()).unwrap()));
+        let dkk = calculater.config.get_currency("dkk".to_string()).unwrap();
+        check_basic_rule_output!(result, TokenType::Money(round_converted_price(&calculater.config, 49644.9970792, &dkk), dkk));
+        Ok(())
+    }
+    
+    #[derive(Default)]
+    pub struct Vat;
+
+    impl FunctionTrait for Vat {
+        fn call(&self, _: &SmartCalcConfig, arguments: &[Rc<dyn DataItem>]) -> Result<Rc<dyn DataItem>, SmartCalcError> {
+            match arguments.get(0) {
+                Some(argument) if arguments.len() == 1 => match argument.as_any().downcast_ref::<MoneyItem>() {
+                    Some(money) => Ok(Rc::new(MoneyItem(money.get_price() * 1.2, money.get_currency()))),
+                    None => Err(SmartCalcError::InvalidArgument("vat requires money".to_string()))
+                },
+                _ => Err(SmartCalcError::InvalidArgument("vat requires 1 argument".to_string()))
+            }
+        }
+    }
+
+    #[test]
+    fn add_function_1() ->  Result<(), ()> {
+        let mut calculater = SmartCalc::default();
+        assert!(calculater.add_function("Vat", Rc::new(Vat::default())));
+        assert!(!calculater.add_function("vat", Rc::new(Vat::default())));
+        assert!(!calculater.add_function("sqrt", Rc::new(Vat::default())));
+        assert!(!calculater.add_function("1vat", Rc::new(Vat::default())));
+
+        let result = calculater.execute("en".to_string(), "vat(100 eur) + 10 eur\nvat(100)");
+        assert_eq!(result.lines[0].as_ref().unwrap().result.as_ref().unwrap().output, "130,00 €".to_string());
+        assert_eq!(result.lines[1].as_ref().unwrap().result.as_ref().unwrap_err(), &SmartCalcError::InvalidArgument("vat requires money".to_string()));
+
+        assert!(!calculater.delete_function("sqrt"));
+        assert!(calculater.delete_function("vat"));
+        assert!(!calculater.delete_function("vat"));
+        Ok(())
+    }
+
+    #[test]
+    fn line_reference_1() ->  Result<(), ()> {
+        let mut calculater = SmartCalc::default();
+        calculater.update_currency("usd", 1.0);
+        calculater.update_currency("eur", 0.5);
+
+        let result = calculater.execute("en".to_string(), "# groceries\n10 usd\n10 eur\nsum\n# rent\n5\ntotal\nline4 + prev");
+        let outputs = result.lines.iter().map(|line| line.as_ref().map(|line| line.result.as_ref().unwrap().output.to_string())).collect::<alloc::vec::Vec<_>>();
+        assert_eq!(outputs, vec![None, Some("$10,00".to_string()), Some("10,00 €".to_string()), Some("$30,00".to_string()), None, Some("5".to_string()), Some("5".to_string()), Some("$35,00".to_string())]);
+        Ok(())
+    }
+
+    #[test]
+    fn line_reference_2() ->  Result<(), ()> {
+        use crate::error::SmartCalcWarning;
+        let calculater = SmartCalc::default();
+
+        /* Numbers are not added to a money total and money is not added to a number total, both are reported */
+        let result = calculater.execute("en".to_string(), "10 usd\n5\n20 usd\nsum\n\n3\n10 usd\n4\nsum");
+        let line = result.lines[3].as_ref().unwrap();
+        assert_eq!(line.result.as_ref().unwrap().output, "$30,00".to_string());
+        assert_eq!(line.warnings, vec![SmartCalcWarning::SkippedTotalLines { lines: vec![2] }]);
+
+        let line = result.lines[8].as_ref().unwrap();
+        assert_eq!(line.result.as_ref().unwrap().output, "7".to_string());
+        assert_eq!(line.warnings, vec![SmartCalcWarning::SkippedTotalLines { lines: vec![7] }]);
+        assert_eq!(line.warnings[0].to_string(), "Lines not added to the total: 7".to_string());
+        assert!(result.lines[2].as_ref().unwrap().warnings.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn reexecute_1() ->  Result<(), ()> {
+        use crate::Session;
+        let calculater = SmartCalc::default();
+        let mut session = Session::new();
+        session.set_language("en".to_string());
+        session.set_text("x = 10\ny = 5\nx * 2\ny * 2\nsum\n\nline2 + 1".to_string());
+        calculater.execute_session(&session);
+
+        let output = |line: &super::ExecutionLine| line.as_ref().unwrap().result.as_ref().unwrap().output.to_string();
+
+        assert!(session.update_line(0, "x = 20".to_string()));
+        let changed = calculater.reexecute(&mut session);
+        assert_eq!(changed.keys().cloned().collect::<alloc::vec::Vec<_>>(), vec![0, 2, 4]);
+        assert_eq!(output(&changed[&2]), "40".to_string());
+        assert_eq!(output(&changed[&4]), "75".to_string());
+        assert!(calculater.reexecute(&mut session).is_empty());
+
+        /* Same result does not change the lines below */
+        assert!(session.update_line(1, "y = 2 + 3".to_string()));
+        assert_eq!(calculater.reexecute(&mut session).keys().cloned().collect::<alloc::vec::Vec<_>>(), vec![1]);
+
+        assert!(session.update_line(1, "y = 6".to_string()));
+        let changed = calculater.reexecute(&mut session);
+        assert_eq!(changed.keys().cloned().collect::<alloc::vec::Vec<_>>(), vec![1, 3, 4, 6]);
+        assert_eq!(output(&changed[&3]), "12".to_string());
+        assert_eq!(output(&changed[&4]), "78".to_string());
+        assert_eq!(output(&changed[&6]), "7".to_string());
+
+        /* Removed variable is not visible to the lines below anymore */
+        assert!(session.update_line(1, "z = 6".to_string()));
+        let changed = calculater.reexecute(&mut session);
+        assert_eq!(changed.keys().cloned().collect::<alloc::vec::Vec<_>>(), vec![1, 3, 4]);
+        assert_eq!(output(&changed[&3]), "0".to_string());
+
+        assert!(!session.update_line(10, "1".to_string()));
+        Ok(())
+    }
+
+    #[test]
+    fn execute_data_1() ->  Result<(), ()> {
+        use crate::{ResultUnit, RESULT_SCHEMA_VERSION};
+        let calculater = SmartCalc::default();
+        let data = calculater.execute("en".to_string(), "10 usd\n\n1 / 0").to_data();
+
+        assert_eq!(data.version, RESULT_SCHEMA_VERSION);
+        assert_eq!(data.lines.len(), 3);
+        assert!(data.lines[1].is_none());
+
+        let money = data.lines[0].as_ref().unwrap();
+        assert_eq!(money.output, Some("$10,00".to_string()));
+        assert_eq!(money.type_name, Some("MONEY".to_string()));
+        assert_eq!(money.value, Some(10.0));
+        assert_eq!(money.unit, Some(ResultUnit::Currency { code: "USD".to_string() }));
+
+        let error = data.lines[2].as_ref().unwrap();
+        assert_eq!(error.output, None);
+        assert_eq!(error.error, Some(SmartCalcError::DivisionByZero));
+
+        let json = serde_json::to_string(error).unwrap();
+        assert!(json.starts_with(r#"{"version":1,"output":null,"type_name":null,"value":null,"unit":null,"ui_tokens":[{"start":0,"end":1,"ui_type":"Number"}"#));
+        assert!(json.ends_with(r#""error":"DivisionByZero"}"#));
+        Ok(())
+    }
+
+    #[test]
+    fn to_json_1() ->  Result<(), ()> {
+        let mut calculater = SmartCalc::default();
+        calculater.update_currency("eur", 2.0);
+        calculater.set_number_configuration(4, true, true);
+        calculater.set_date_rule("en", vec!["{NUMBER:day} of {MONTH:month} {NUMBER:year}".to_string()]);
+        assert!(calculater.add_rule("en".to_string(), vec!["{TEXT:name} {TEXT:surname}".to_string()], Rc::new(Test1::default())));
+        assert!(calculater.add_dynamic_type("test1"));
+        assert!(calculater.add_dynamic_type_item("test1", 1, "{value} a", vec!["{NUMBER:value} {TEXT:type:a}"], "{value} / 2", "{value}", vec!["a".to_string()], None, None, None));
+        assert!(calculater.add_dynamic_type_item("test1", 2, "{value} b", vec!["{NUMBER:value} {TEXT:type:b}"], "{value} / 2", "{value} * 2", vec!["b".to_string()], None, None, None));
+
+        let json = calculater.to_json().unwrap();
+        assert!(json.contains(r#""api_rules":{"en":["test1"]}"#));
+
+        let calculater = SmartCalc::try_load_from_json(&json).unwrap();
+        let result = calculater.execute("en".to_string(), "1 usd to eur\n10 / 3\n10 a to b\n12 of february 2022");
+        assert_eq!(result.lines[0].as_ref().unwrap().result.as_ref().unwrap().output, "2,00 €".to_string());
+        assert_eq!(result.lines[1].as_ref().unwrap().result.as_ref().unwrap().output, "3,3333".to_string());
+        assert_eq!(result.lines[2].as_ref().unwrap().result.as_ref().unwrap().output, "5 b".to_string());
+        assert_eq!(result.lines[3].as_ref().unwrap().result.as_ref().unwrap().output, "12 Feb 2022".to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn integer_configuration_1() -> Result<(), ()> {
+        let mut calculater = SmartCalc::default();
+        let result = calculater.execute("en".to_string(), "0xF0 | 0x0F\n12 | 3\n0xFF + 0x1");
+        assert_eq!(result.lines[0].as_ref().unwrap().result.as_ref().unwrap().output, "0xFF".to_string());
+        assert_eq!(result.lines[1].as_ref().unwrap().result.as_ref().unwrap().output, "15".to_string());
+        assert_eq!(result.lines[2].as_ref().unwrap().result.as_ref().unwrap().output, "0x100".to_string());
+
+        assert!(!calculater.set_integer_configuration(12, false));
+        assert!(calculater.set_integer_configuration(8, false));
+        let result = calculater.execute("en".to_string(), "0xFF + 0x1\n~0x0F\n0x1 << 8");
+        assert_eq!(result.lines[0].as_ref().unwrap().result.as_ref().unwrap().output, "0x0".to_string());
+        assert_eq!(result.lines[1].as_ref().unwrap().result.as_ref().unwrap().output, "0xF0".to_string());
+        assert_eq!(result.lines[2].as_ref().unwrap().result.as_ref().unwrap().output, "0x0".to_string());
+
+        assert!(calculater.set_integer_configuration(0, true));
+        let result = calculater.execute("en".to_string(), "0x0 - 0xFF");
+        assert_eq!(result.lines[0].as_ref().unwrap().result.as_ref().unwrap().output, "-0xFF".to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn scientific_notation_1() -> Result<(), ()> {
+        let mut calculater = SmartCalc::default();
+        calculater.set_scientific_notation(6, 0);
+        let result = calculater.execute("en".to_string(), "1000000\n999999\n0,001");
+        assert_eq!(result.lines[0].as_ref().unwrap().result.as_ref().unwrap().output, "1e6".to_string());
+        assert_eq!(result.lines[1].as_ref().unwrap().result.as_ref().unwrap().output, "999.999".to_string());
+        assert_eq!(result.lines[2].as_ref().unwrap().result.as_ref().unwrap().output, "0".to_string());
+
+        calculater.set_scientific_notation(6, -2);
+        let result = calculater.execute("en".to_string(), "0,001
+0,01");
+        assert_eq!(result.lines[0].as_ref().unwrap().result.as_ref().unwrap().output, "1e-3".to_string());
+        assert_eq!(result.lines[1].as_ref().unwrap().result.as_ref().unwrap().output, "0,01".to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn rounding_configuration_1() -> Result<(), ()> {
+        let mut calculater = SmartCalc::default();
+        calculater.set_money_configuration(false, true);
+        calculater.set_money_rounding_mode(RoundingMode::HalfEven);
+        calculater.set_number_configuration(2, true, true);
+        calculater.set_number_rounding_mode(RoundingMode::Truncate);
+        calculater.set_percentage_configuration(1, true, true);
+        calculater.set_percentage_rounding_mode(RoundingMode::Ceiling);
+        let result = calculater.execute("en".to_string(), "12,345 eur\n12,355 eur\n20 / 3\n%12,01\nround 12,345 to 2 digits");
+        assert_eq!(result.lines[0].as_ref().unwrap().result.as_ref().unwrap().output, "12,34 €".to_string());
+        assert_eq!(result.lines[1].as_ref().unwrap().result.as_ref().unwrap().output, "12,36 €".to_string());
+        assert_eq!(result.lines[2].as_ref().unwrap().result.as_ref().unwrap().output, "6,66".to_string());
+        assert_eq!(result.lines[3].as_ref().unwrap().result.as_ref().unwrap().output, "%12,1".to_string());
+        assert_eq!(result.lines[4].as_ref().unwrap().result.as_ref().unwrap().output, "12,34".to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn significant_figures_1() -> Result<(), ()> {
+        let mut calculater = SmartCalc::default();
+        assert!(!calculater.set_significant_figures(18));
+        assert!(calculater.set_significant_figures(3));
+        let result = calculater.execute("en".to_string(), "0,000123456\n%12,345\n1234,5 km\n10 / 3");
+        assert_eq!(result.lines[0].as_ref().unwrap().result.as_ref().unwrap().output, "0,000123".to_string());
+        assert_eq!(result.lines[1].as_ref().unwrap().result.as_ref().unwrap().output, "%12,3".to_string());
+        assert_eq!(result.lines[2].as_ref().unwrap().result.as_ref().unwrap().output, "1.230 Kilometer".to_string());
+        assert_eq!(result.lines[3].as_ref().unwrap().result.as_ref().unwrap().output, "3,33".to_string());
+
+        calculater.set_scientific_notation(15, -2);
+        let result = calculater.execute("en".to_string(), "0,000123456");
+        assert_eq!(result.lines[0].as_ref().unwrap().result.as_ref().unwrap().output, "1,23e-4".to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn number_notation_1() -> Result<(), ()> {
+        let calculater = SmartCalc::default();
+        let result = calculater.execute("tr".to_string(), "5 bin tl\n2 milyon + iki bin\n3 düzine");
+        assert_eq!(result.lines[0].as_ref().unwrap().result.as_ref().unwrap().output, "₺5.000,00".to_string());
+        assert_eq!(result.lines[1].as_ref().unwrap().result.as_ref().unwrap().output, "2.002.000".to_string());
+        assert_eq!(result.lines[2].as_ref().unwrap().result.as_ref().unwrap().output, "36".to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn statistics_1() -> Result<(), ()> {
+        let calculater = SmartCalc::default();
+        let result = calculater.execute("tr".to_string(), "ortalama 2 4 6\nmedyan 10 tl, 30 tl, 20 tl\n3\n4\ntoplam");
+        assert_eq!(result.lines[0].as_ref().unwrap().result.as_ref().unwrap().output, "4".to_string());
+        assert_eq!(result.lines[1].as_ref().unwrap().result.as_ref().unwrap().output, "₺20,00".to_string());
+        assert_eq!(result.lines[4].as_ref().unwrap().result.as_ref().unwrap().output, "11".to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn number_words_1() -> Result<(), ()> {
+        let calculater = SmartCalc::default();
+        let result = calculater.execute("tr".to_string(), "yüz yirmi beş + on\n1250 avro yazıyla");
+        assert_eq!(result.lines[0].as_ref().unwrap().result.as_ref().unwrap().output, "135".to_string());
+        assert_eq!(result.lines[1].as_ref().unwrap().result.as_ref().unwrap().output, "bin iki yüz elli avro".to_string());
+
+        /* Turkish "İ" is lowercased to "i" without changing the positions of the next words */
+        let result = calculater.execute("tr".to_string(), "YÜZ YİRMİ BEŞ\nİKİ YÜZ + 5\nBİN İKİ YÜZ ELLİ AVRO");
+        assert_eq!(result.lines[0].as_ref().unwrap().result.as_ref().unwrap().output, "125".to_string());
+        assert_eq!(result.lines[1].as_ref().unwrap().result.as_ref().unwrap().output, "205".to_string());
+        assert_eq!(result.lines[2].as_ref().unwrap().result.as_ref().unwrap().output, "1.250,00 €".to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn random_1() -> Result<(), ()> {
+        use crate::session::Session;
+        let calculater = SmartCalc::default();
+        let execute = |seed: u64| {
+            let mut session = Session::new();
+            session.set_language("en".to_string());
+            session.set_text("random between 1 and 100\n3d6\nd20\npick one of a, b, c\nrandom between 10 and 20 usd\nrandom 0x00 to 0xff".to_string());
+            session.set_random_seed(Some(seed));
+            calculater.execute_session(&session).lines.iter().map(|line| line.as_ref().unwrap().result.as_ref().unwrap().output.to_string()).collect::<alloc::vec::Vec<_>>()
+        };
+
+        /* Shared document gives the same numbers with the same seed */
+        let outputs = execute(42);
+        assert_eq!(outputs, execute(42));
+
+        let number = |output: &str| output.trim_start_matches('$').replace(',', ".").parse::<f64>().unwrap();
+        assert!((1.0..=100.0).contains(&number(&outputs[0])));
+        assert!((3.0..=18.0).contains(&number(&outputs[1])));
+        assert!((1.0..=20.0).contains(&number(&outputs[2])));
+        assert!(["a", "b", "c"].contains(&outputs[3].as_str()));
+        assert!(outputs[4].starts_with('$') && (10.0..=20.0).contains(&number(&outputs[4])));
+        assert!(outputs[5].starts_with("0x"));
+        Ok(())
+    }
+
+    #[test]
+    fn currency_rate_provider_1() -> Result<(), ()> {
+        use chrono::Utc;
+        use crate::types::CurrencyRate;
+        use crate::error::SmartCalcWarning;
+        use super::CurrencyRateProvider;
+
+        struct RatesFile(alloc::vec::Vec<CurrencyRate>);
+        impl CurrencyRateProvider for RatesFile {
+            fn get_rates(&self) -> Result<alloc::vec::Vec<CurrencyRate>, String> {
+                Ok(self.0.clone())
+            }
+        }
+
+        let now = Utc::now().timestamp();
+        let rate = |currency: &str, rate: f64, timestamp: i64| CurrencyRate { currency: currency.to_string(), base: "EUR".to_string(), rate, timestamp };
+        let mut calculater = SmartCalc::default();
+        assert_eq!(calculater.update_currency_rates(&RatesFile(vec![rate("gbp", 0.85, 0), rate("TRY", 30.0, now)])), Ok(2));
+        assert_eq!(calculater.get_currency_rate("gbp"), Some(rate("gbp", 0.85, 0)));
+        assert_eq!(calculater.get_currency_rate("usd"), None);
+
+        let result = calculater.execute("en", "10 eur to gbp\n10 eur + 30 try\n10 gbp + 10 gbp");
+        let line = result.lines[0].as_ref().unwrap();
+        assert_eq!(line.result.as_ref().unwrap().output, "£8,50".to_string());
+        assert_eq!(line.currency_conversions.len(), 1);
+        assert!((line.currency_conversions[0].rate - 0.85).abs() < 0.000001);
+        assert_eq!(line.currency_conversions[0].timestamp, Some(0));
+        assert_eq!(line.warnings, vec![SmartCalcWarning::StaleCurrencyRate { from: "EUR".to_string(), to: "GBP".to_string(), timestamp: 0 }]);
+
+        let line = result.lines[1].as_ref().unwrap();
+        assert_eq!(line.result.as_ref().unwrap().output, "11,00 €".to_string());
+        assert_eq!(line.currency_conversions[0].from, "TRY".to_string());
+        assert_eq!(line.currency_conversions[0].timestamp, Some(now));
+        assert!(line.warnings.is_empty());
+        assert!(result.lines[2].as_ref().unwrap().currency_conversions.is_empty());
+
+        /* Warning can be disabled */
+        calculater.set_currency_rate_max_age(0);
+        assert!(calculater.execute("en", "10 eur to gbp").lines[0].as_ref().unwrap().warnings.is_empty());
+
+        /* Nothing is changed with an unknown currency */
+        assert!(calculater.update_currency_rates(&RatesFile(vec![rate("gbp", 0.9, now), rate("xyz", 1.0, now)])).is_err());
+        assert_eq!(calculater.get_currency_rate("gbp"), Some(rate("gbp", 0.85, 0)));
+
+        /* Manual rate has no timestamp */
+        calculater.update_currency("gbp", 0.8);
+        assert_eq!(calculater.get_currency_rate("gbp"), None);
+        Ok(())
+    }
+
+    #[test]
+    fn currency_rate_history_1() -> Result<(), ()> {
+        use crate::error::SmartCalcError;
+        let mut calculater = SmartCalc::default();
+        assert_eq!(calculater.load_currency_rate_history_json(r#"{"base": "USD", "rates": {"2024-02-29": {"EUR": 0.92}, "2024-03-04": {"EUR": 0.95}}}"#), Ok(2));
+
+        let result = calculater.execute("en", "100 usd to eur on 1 Mar 2024\n100 usd eur on 4 Mar 2024\n100 usd to eur on 1 Jan 2024\n100 usd to eur on 1 Jan 2025");
+        let line = result.lines[0].as_ref().unwrap();
+        assert_eq!(line.result.as_ref().unwrap().output, "92,00 €".to_string());
+        assert!(line.currency_conversions[0].historical);
+        assert!(line.warnings.is_empty());
+        assert_eq!(result.lines[1].as_ref().unwrap().result.as_ref().unwrap().output, "95,00 €".to_string());
+        assert_eq!(result.lines[2].as_ref().unwrap().result.as_ref().unwrap_err(), &SmartCalcError::HistoricalRateNotFound { from: "USD".to_string(), to: "EUR".to_string(), date: "2024-01-01".to_string() });
+        assert_eq!(result.lines[3].as_ref().unwrap().result.as_ref().unwrap().output, "95,00 €".to_string());
+
+        let result = calculater.execute("tr", "100 usd avro tarihinde 1 Mart 2024");
+        assert_eq!(result.lines[0].as_ref().unwrap().result.as_ref().unwrap().output, "92,00 €".to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn add_currency_1() -> Result<(), ()> {
+        use crate::types::CurrencyInfo;
+        let currency = |code: &str, symbol: &str, decimal_digits: u8| CurrencyInfo {
+            code: code.to_string(),
+            symbol: symbol.to_string(),
+            thousands_separator: ".".to_string(),
+            decimal_separator: ",".to_string(),
+            symbol_on_left: true,
+            space_between_amount_and_symbol: false,
+            decimal_digits
+        };
+
+        let mut calculater = SmartCalc::default();
+        assert!(calculater.add_currency(currency("BTC", "₿", 8), 0.00002));
+        assert!(calculater.add_currency(currency("ETH", "Ξ", 8), 0.0004));
+        assert!(!calculater.add_currency(currency("usd", "$", 2), 1.0));
+        assert!(!calculater.add_currency(currency("X1", "x", 2), 1.0));
+        assert!(!calculater.add_currency(currency("PTS", "pts", 0), 0.0));
+        assert!(calculater.add_currency_alias("bitcoin", "btc"));
+        assert!(!calculater.add_currency_alias("bitcoin", "eth"));
+        assert!(!calculater.add_currency_alias("points", "pts"));
+
+        let result = calculater.execute("en", "1 btc to usd\n₿0,5 + 1 thousand bitcoin\n2 Ξ to btc\n10 eth + $1000");
+        assert_eq!(result.lines[0].as_ref().unwrap().result.as_ref().unwrap().output, "$50.000,00".to_string());
+        assert_eq!(result.lines[1].as_ref().unwrap().result.as_ref().unwrap().output, "₿1.000,50000000".to_string());
+        assert_eq!(result.lines[2].as_ref().unwrap().result.as_ref().unwrap().output, "₿0,10000000".to_string());
+        assert_eq!(result.lines[3].as_ref().unwrap().result.as_ref().unwrap().output, "Ξ10,40000000".to_string());
+
+        /* Registered currencies are kept in the exported configuration */
+        let calculater = SmartCalc::try_load_from_json(&calculater.to_json().unwrap()).unwrap();
+        let result = calculater.execute("en", "Ξ1 to btc");
+        assert_eq!(result.lines[0].as_ref().unwrap().result.as_ref().unwrap().output, "₿0,05000000".to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn preferred_currency_1() -> Result<(), ()> {
+        use crate::session::Session;
+        use crate::error::SmartCalcWarning;
+        let mut calculater = SmartCalc::default();
+        let result = calculater.execute("en", "$10\n100 kr\n10 eur");
+        let line = result.lines[0].as_ref().unwrap();
+        assert_eq!(line.result.as_ref().unwrap().output, "$10,00".to_string());
+        assert_eq!(line.warnings, vec![SmartCalcWarning::AmbiguousCurrency { symbol: "$".to_string(), currency: "USD".to_string(), alternatives: vec!["CAD".to_string(), "AUD".to_string(), "NZD".to_string(), "SGD".to_string(), "HKD".to_string(), "MXN".to_string()] }]);
+        assert_eq!(result.lines[1].as_ref().unwrap().warnings.len(), 1);
+        assert!(result.lines[2].as_ref().unwrap().warnings.is_empty());
+
+        assert!(!calculater.set_preferred_currencies(vec!["xyz".to_string()]));
+        assert!(calculater.set_preferred_currencies(vec!["sek".to_string()]));
+        let result = calculater.execute("en", "100 kr\n10 eur to kr\n$10");
+        let line = result.lines[0].as_ref().unwrap();
+        assert_eq!(line.result.as_ref().unwrap().output, "100,00 kr".to_string());
+        assert!(line.warnings.is_empty());
+        assert_eq!(result.lines[1].as_ref().unwrap().currency_conversions[0].to, "SEK".to_string());
+        assert_eq!(result.lines[2].as_ref().unwrap().warnings.len(), 1);
+
+        /* Session preference is checked first */
+        let mut session = Session::new();
+        session.set_language("en".to_string());
+        session.set_text("$10\n100 kr".to_string());
+        session.set_preferred_currencies(vec!["cad".to_string(), "nok".to_string()]);
+        let result = calculater.execute_session(&session);
+        assert_eq!(result.to_data().lines[0].as_ref().unwrap().unit, Some(super::ResultUnit::Currency { code: "CAD".to_string() }));
+        assert_eq!(result.to_data().lines[1].as_ref().unwrap().unit, Some(super::ResultUnit::Currency { code: "NOK".to_string() }));
+        assert!(result.lines[0].as_ref().unwrap().warnings.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn currency_display_1() -> Result<(), ()> {
+        use crate::config::{CurrencyDisplay, NegativeStyle};
+        use crate::error::SmartCalcWarning;
+        let mut calculater = SmartCalc::default();
+        let outputs = |calculater: &SmartCalc, text: &str| calculater.execute("en", text).lines.iter().map(|line| line.as_ref().unwrap().result.as_ref().unwrap().output.to_string()).collect::<alloc::vec::Vec<_>>();
+
+        assert_eq!(outputs(&calculater, "12 eur as name\n1 eur as name\n1200 usd as code\n10 cad as narrow symbol\n10 cad as code + 5\n-5 usd"),
+            vec!["12,00 euros", "1,00 euro", "USD 1.200,00", "$10,00", "CAD 15,00", "$-5,00"]);
+
+        /* Letters of a symbol are kept, only a country prefix of a currency sign is removed */
+        assert_eq!(outputs(&calculater, "100 czk as narrow symbol\n100 huf as narrow symbol\n10 hkd as narrow symbol"),
+            vec!["100,00 Kč", "100,00 Ft", "$10,00"]);
+
+        calculater.update_currency("eur", 0.5);
+        calculater.set_currency_display(CurrencyDisplay::Code, NegativeStyle::Accounting);
+        assert_eq!(outputs(&calculater, "1200 eur\n-1200 usd\n10 usd as symbol\n-0,001 usd\n10 usd to eur as name"),
+            vec!["1.200,00 EUR", "(USD 1.200,00)", "$10,00", "USD 0,00", "5,00 euros"]);
+
+        /* Style is kept with the value, the same currency on other lines is not changed */
+        let result = calculater.execute("en", "a = 10 usd as symbol
+a * 2
+10 usd
+10 chf as name");
+        let lines = result.lines.iter().map(|line| line.as_ref().unwrap()).collect::<alloc::vec::Vec<_>>();
+        assert_eq!(lines[1].result.as_ref().unwrap().output, "$20,00".to_string());
+        assert_eq!(lines[2].result.as_ref().unwrap().output, "USD 10,00".to_string());
+        assert_eq!(lines[3].result.as_ref().unwrap().output, "10,00 CHF".to_string());
+        assert_eq!(lines[3].warnings, vec![SmartCalcWarning::MissingCurrencyName { currency: "CHF".to_string(), language: "en".to_string() }]);
         Ok(())
     }
 }
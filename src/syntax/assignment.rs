@@ -10,7 +10,7 @@ use crate::types::*;
 use crate::syntax::{SyntaxParser, SyntaxParserTrait};
 use crate::variable::VariableInfo;
 use alloc::rc::Rc;
-use crate::syntax::binary::AddSubtractParser;
+use crate::syntax::binary::BitwiseOrParser;
 use core::ops::Deref;
 use crate::alloc::string::ToString;
 
@@ -48,7 +48,7 @@ impl SyntaxParserTrait for AssignmentParser {
 
             end = parser.get_index() - 1;
 
-            let expression = AddSubtractParser::parse(parser);
+            let expression = BitwiseOrParser::parse(parser);
             match expression {
                 Ok(SmartCalcAstType::None) => return expression,
                 Ok(_)  => (),
@@ -5,13 +5,87 @@
  */
 
 use crate::types::*;
+use crate::constants::{XOR_OPERATOR, SHIFT_LEFT_OPERATOR, SHIFT_RIGHT_OPERATOR};
 use crate::syntax::{SyntaxParser, SyntaxParserTrait};
 use crate::syntax::unary::UnaryParser;
+use crate::compiler::number::NumberItem;
+use crate::compiler::integer::IntegerItem;
 use alloc::rc::Rc;
 
+pub struct PowerParser;
 pub struct ModuloParser;
 pub struct MultiplyDivideParser;
 pub struct AddSubtractParser;
+pub struct ShiftParser;
+pub struct BitwiseAndParser;
+pub struct BitwiseXorParser;
+pub struct BitwiseOrParser;
+
+impl PowerParser {
+    /* "-2" and "- 2" are negative number literals, "(-2)" is not */
+    fn is_negative_literal(parser: &SyntaxParser) -> bool {
+        let index = parser.get_index();
+        let is_negative = match parser.peek_token().as_deref() {
+            Ok(TokenType::Number(number, _)) => number.is_sign_negative(),
+            Ok(TokenType::Integer(number, _)) => *number < 0,
+            Ok(TokenType::Operator('-')) => {
+                parser.set_index(index + 1);
+                matches!(parser.peek_token().as_deref(), Ok(TokenType::Number(number, _)) if number.is_sign_positive()) || matches!(parser.peek_token().as_deref(), Ok(TokenType::Integer(number, _)) if *number >= 0)
+            },
+            _ => false
+        };
+
+        parser.set_index(index);
+        is_negative
+    }
+
+    /* Sign of a literal is applied after the exponent, -2 ^ 2 equals to -(2 ^ 2) */
+    fn negate_base(left_expr: &SmartCalcAstType) -> Option<SmartCalcAstType> {
+        match left_expr {
+            SmartCalcAstType::Item(item) => match item.as_any().downcast_ref::<IntegerItem>() {
+                Some(integer) => Some(SmartCalcAstType::Item(Rc::new(IntegerItem(integer.0.wrapping_neg(), integer.1)))),
+                None => item.as_any().downcast_ref::<NumberItem>().map(|number| SmartCalcAstType::Item(Rc::new(NumberItem(-number.0, number.1))))
+            },
+            _ => None
+        }
+    }
+}
+
+impl SyntaxParserTrait for PowerParser {
+    fn parse(parser: &mut SyntaxParser) -> AstResult {
+        let is_negative_literal = PowerParser::is_negative_literal(parser);
+        let left_expr = UnaryParser::parse(parser)?;
+        
+        if let SmartCalcAstType::None = left_expr {
+            return Ok(left_expr)
+        }
+
+        let index_backup = parser.get_index();
+
+        /* Exponent is right associative, 2 ^ 3 ^ 2 equals to 2 ^ (3 ^ 2) */
+        if let Some(operator) = parser.match_operator(&['^']) {
+            match PowerParser::parse(parser)? {
+                SmartCalcAstType::None => parser.set_index(index_backup),
+                right_expr => {
+                    let base = PowerParser::negate_base(&left_expr).filter(|_| is_negative_literal);
+                    let is_negated = base.is_some();
+                    let power = SmartCalcAstType::Binary {
+                        left: Rc::new(base.unwrap_or(left_expr)),
+                        operator,
+                        right: Rc::new(right_expr)
+                    };
+
+                    return Ok(match is_negated {
+                        true => SmartCalcAstType::PrefixUnary('-', Rc::new(power)),
+                        false => power
+                    });
+                }
+            };
+        }
+
+        Ok(left_expr)
+    }
+}
 
 impl SyntaxParserTrait for ModuloParser {
     fn parse(parser: &mut SyntaxParser) -> AstResult {
@@ -21,7 +95,7 @@ impl SyntaxParserTrait for ModuloParser {
 
 impl SyntaxParserTrait for MultiplyDivideParser {
     fn parse(parser: &mut SyntaxParser) -> AstResult {
-        parse_binary::<UnaryParser>(parser, &['*', '/'])
+        parse_binary::<PowerParser>(parser, &['*', '/'])
     }
 }
 
@@ -31,6 +105,30 @@ impl SyntaxParserTrait for AddSubtractParser {
     }
 }
 
+impl SyntaxParserTrait for ShiftParser {
+    fn parse(parser: &mut SyntaxParser) -> AstResult {
+        parse_binary::<AddSubtractParser>(parser, &[SHIFT_LEFT_OPERATOR, SHIFT_RIGHT_OPERATOR])
+    }
+}
+
+impl SyntaxParserTrait for BitwiseAndParser {
+    fn parse(parser: &mut SyntaxParser) -> AstResult {
+        parse_binary::<ShiftParser>(parser, &['&'])
+    }
+}
+
+impl SyntaxParserTrait for BitwiseXorParser {
+    fn parse(parser: &mut SyntaxParser) -> AstResult {
+        parse_binary::<BitwiseAndParser>(parser, &[XOR_OPERATOR])
+    }
+}
+
+impl SyntaxParserTrait for BitwiseOrParser {
+    fn parse(parser: &mut SyntaxParser) -> AstResult {
+        parse_binary::<BitwiseXorParser>(parser, &['|'])
+    }
+}
+
 pub fn parse_binary<T: SyntaxParserTrait>(parser: &mut SyntaxParser, operators: &[char]) -> AstResult {
     let mut left_expr = T::parse(parser)?;
     
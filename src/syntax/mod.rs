@@ -19,8 +19,9 @@ use crate::tokinizer::Tokinizer;
 use crate::types::*;
 use alloc::rc::Rc;
 use crate::session::Session;
+use crate::error::SmartCalcError;
 use crate::syntax::assignment::AssignmentParser;
-use crate::syntax::binary::AddSubtractParser;
+use crate::syntax::binary::BitwiseOrParser;
 use core::ops::Deref;
 
 pub type ParseType = fn(parser: &mut SyntaxParser) -> AstResult;
@@ -45,10 +46,21 @@ impl<'a> SyntaxParser<'a> {
     }
 
     pub fn parse(&mut self) -> AstResult {
-        let ast = map_parser(self, &[AssignmentParser::parse, AddSubtractParser::parse])?;
+        let ast = map_parser(self, &[AssignmentParser::parse, BitwiseOrParser::parse])?;
         Ok(ast)
     }
 
+    /// Syntax error which covers tokens from start_index to the current token.
+    pub fn syntax_error(&self, message: &str, start_index: usize) -> SmartCalcError {
+        let line_end = self.tokinizer.data.len();
+        let start = self.tokinizer.token_locations.get(start_index).map_or(line_end, |(start, _)| *start);
+        let end = self.tokinizer.token_locations.get(self.get_index()).map_or(line_end, |(_, end)| *end);
+
+        /* Token locations are byte based, error should point to characters */
+        let char_position = |position: usize| self.tokinizer.data.get(..position).map_or(self.tokinizer.total, |text| text.chars().count());
+        SmartCalcError::syntax(message, char_position(start), char_position(end.max(start)))
+    }
+
     pub fn set_index(&self, index: usize) {
         self.index.set(index);
     }
@@ -6,19 +6,25 @@
 
 
 use alloc::rc::Rc;
+use alloc::vec::Vec;
+use alloc::string::ToString;
 
 use crate::compiler::date::DateItem;
 use crate::compiler::date_time::DateTimeItem;
 use crate::compiler::duration::DurationItem;
-use crate::compiler::money::MoneyItem;
+use crate::compiler::money::{MoneyItem, DisplayMoneyItem};
 use crate::compiler::dynamic_type::DynamicTypeItem;
 use crate::compiler::number::NumberItem;
+use crate::compiler::integer::IntegerItem;
+use crate::compiler::fraction::FractionItem;
 use crate::compiler::percent::PercentItem;
 use crate::compiler::time::TimeItem;
+use crate::compiler::words::WordsItem;
+use crate::compiler::list::ListItem;
 use crate::types::*;
 use crate::syntax::util::*;
 use crate::syntax::{SyntaxParser, SyntaxParserTrait};
-use crate::syntax::binary::AddSubtractParser;
+use crate::syntax::binary::BitwiseOrParser;
 use core::ops::Deref;
 
 pub struct PrimativeParser;
@@ -29,7 +35,7 @@ impl PrimativeParser {
         let token = parser.peek_token();
 
         if token.is_err() {
-            return Err(("No more token", 0, 0));
+            return Err(parser.syntax_error("No more token", index_backup));
         }
 
         let result = match token.unwrap().deref() {
@@ -40,17 +46,23 @@ impl PrimativeParser {
             },
             TokenType::DynamicType(number, dynamic_type)     => Ok(SmartCalcAstType::Item(Rc::new(DynamicTypeItem(*number, dynamic_type.clone())))),
             TokenType::Money(price, currency)     => Ok(SmartCalcAstType::Item(Rc::new(MoneyItem(*price, currency.clone())))),
+            TokenType::DisplayMoney(price, currency, display) => Ok(SmartCalcAstType::Item(Rc::new(DisplayMoneyItem(*price, currency.clone(), *display)))),
             TokenType::Number(double, number_type)     => Ok(SmartCalcAstType::Item(Rc::new(NumberItem(*double, *number_type)))),
+            TokenType::Integer(number, number_type)    => Ok(SmartCalcAstType::Item(Rc::new(IntegerItem(*number, *number_type)))),
+            TokenType::Fraction(numerator, denominator) => Ok(SmartCalcAstType::Item(Rc::new(FractionItem(*numerator, *denominator)))),
             TokenType::Field(field_type)  => Ok(SmartCalcAstType::Field(field_type.clone())),
             TokenType::Percent(percent)   => Ok(SmartCalcAstType::Item(Rc::new(PercentItem(*percent)))),
             TokenType::Time(time, tz)         => Ok(SmartCalcAstType::Item(Rc::new(TimeItem(*time, tz.clone())))),
             TokenType::Date(date, tz)         => Ok(SmartCalcAstType::Item(Rc::new(DateItem(*date, tz.clone())))),
             TokenType::DateTime(date_time, tz)         => Ok(SmartCalcAstType::Item(Rc::new(DateTimeItem(*date_time, tz.clone())))),
             TokenType::Duration(duration)         => Ok(SmartCalcAstType::Item(Rc::new(DurationItem(*duration)))),
+            TokenType::Words(number, currency)    => Ok(SmartCalcAstType::Item(Rc::new(WordsItem(*number, currency.clone())))),
+            TokenType::List(items)                => Ok(SmartCalcAstType::Item(Rc::new(ListItem(items.clone())))),
             TokenType::Variable(variable) => Ok(SmartCalcAstType::Variable(variable.clone())),
             _ => {
+                let error = parser.syntax_error("No more token", index_backup);
                 parser.consume_token();
-                return Err(("No more token", 0, 0));
+                return Err(error);
             }
         };
 
@@ -63,23 +75,68 @@ impl PrimativeParser {
                 parser.consume_token();
                 Ok(ast)
             },
-            Err((message, line, column)) => Err((message, line, column))
+            Err(error) => Err(error)
         }
     }
 
+    pub fn parse_call(parser: &mut SyntaxParser) -> AstResult {
+        let index_backup = parser.get_index();
+        let name = match parser.peek_token() {
+            Ok(token) => match token.deref() {
+                TokenType::Function(name) => name.to_string(),
+                _ => return Ok(SmartCalcAstType::None)
+            },
+            Err(_) => return Ok(SmartCalcAstType::None)
+        };
+
+        parser.consume_token();
+        if parser.match_operator(&['(']).is_none() {
+            parser.set_index(index_backup);
+            return Err(parser.syntax_error("Function call requires parentheses", index_backup));
+        }
+
+        let mut args = Vec::new();
+        if parser.match_operator(&[')']).is_none() {
+            loop {
+                let ast = BitwiseOrParser::parse(parser);
+                if is_ast_empty(&ast) {
+                    let error = err_or_message(parser, &ast, "Invalid function argument", index_backup);
+                    parser.set_index(index_backup);
+                    return error;
+                }
+
+                args.push(Rc::new(ast.unwrap()));
+
+                match parser.match_operator(&[',', ';', ')']) {
+                    Some(')') => break,
+                    Some(_) => (),
+                    None => {
+                        let error = parser.syntax_error("Parentheses not closed", index_backup);
+                        parser.set_index(index_backup);
+                        return Err(error);
+                    }
+                };
+            }
+        }
+
+        Ok(SmartCalcAstType::Call { name, args })
+    }
+
     pub fn parse_parenthesis(parser: &mut SyntaxParser) -> AstResult {
         let index_backup = parser.get_index();
         if parser.match_operator(&['(']).is_some() {
             
-            let ast = AddSubtractParser::parse(parser);
+            let ast = BitwiseOrParser::parse(parser);
             if is_ast_empty(&ast) {
+                let error = err_or_message(parser, &ast, "Invalid expression", index_backup);
                 parser.set_index(index_backup);
-                return err_or_message(&ast, "Invalid expression");
+                return error;
             }
 
             if parser.match_operator(&[')']).is_none() {
+                let error = parser.syntax_error("Parentheses not closed", index_backup);
                 parser.set_index(index_backup);
-                return Err(("Parentheses not closed", 0, 0));
+                return Err(error);
             }
 
             return Ok(ast.unwrap());
@@ -92,6 +149,6 @@ impl PrimativeParser {
 
 impl SyntaxParserTrait for PrimativeParser {
     fn parse(parser: &mut SyntaxParser) -> AstResult {
-        map_parser(parser, &[Self::parse_parenthesis, Self::parse_basic_primatives])
+        map_parser(parser, &[Self::parse_call, Self::parse_parenthesis, Self::parse_basic_primatives])
     }
 }
\ No newline at end of file
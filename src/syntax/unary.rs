@@ -4,8 +4,10 @@
  * Licensed under the GNU General Public License v2.0.
  */
 
-use crate::compiler::money::MoneyItem;
+use crate::compiler::money::{MoneyItem, DisplayMoneyItem};
 use crate::compiler::number::NumberItem;
+use crate::compiler::integer::IntegerItem;
+use crate::compiler::fraction::FractionItem;
 use crate::compiler::percent::PercentItem;
 use crate::types::*;
 use crate::syntax::{SyntaxParser, SyntaxParserTrait};
@@ -30,7 +32,11 @@ impl UnaryParser {
     fn parse_prefix_unary(parser: &mut SyntaxParser) -> AstResult {
         let index_backup = parser.get_index();
 
-        if let Some(operator) = parser.match_operator(&['-', '+']) {
+        if let Some(operator) = parser.match_operator(&['-', '+', '~']) {
+            if operator == '~' {
+                return Self::parse_bitwise_not(parser, index_backup);
+            }
+
             match parser.peek_token() {
                 Ok(token) => {
                     let opt = match operator {
@@ -41,12 +47,16 @@ impl UnaryParser {
 
                     match token.deref() {
                         TokenType::Number(double, number_type)         => return Ok(SmartCalcAstType::Item(Rc::new(NumberItem(double * opt, *number_type)))),
+                        TokenType::Integer(number, number_type)        => return Ok(SmartCalcAstType::Item(Rc::new(IntegerItem(if opt < 0.0 { number.wrapping_neg() } else { *number }, *number_type)))),
                         TokenType::Variable(variable)     => return Ok(SmartCalcAstType::PrefixUnary(operator, Rc::new(SmartCalcAstType::Variable(variable.clone())))),
+                        TokenType::Fraction(numerator, denominator) => return Ok(SmartCalcAstType::PrefixUnary(operator, Rc::new(SmartCalcAstType::Item(Rc::new(FractionItem(*numerator, *denominator)))))),
                         TokenType::Percent(percent)       => return Ok(SmartCalcAstType::PrefixUnary(operator, Rc::new(SmartCalcAstType::Item(Rc::new(PercentItem(*percent)))))),
                         TokenType::Money(money, currency) => return Ok(SmartCalcAstType::PrefixUnary(operator, Rc::new(SmartCalcAstType::PrefixUnary(operator, Rc::new(SmartCalcAstType::Item(Rc::new(MoneyItem(*money, currency.clone())))))))),
+                        TokenType::DisplayMoney(money, currency, display) => return Ok(SmartCalcAstType::PrefixUnary(operator, Rc::new(SmartCalcAstType::PrefixUnary(operator, Rc::new(SmartCalcAstType::Item(Rc::new(DisplayMoneyItem(*money, currency.clone(), *display)))))))),
                         _ => {
+                            let error = parser.syntax_error("Unary works with number", index_backup);
                             parser.set_index(index_backup);
-                            return Err(("Unary works with number", 0, 0));
+                            return Err(error);
                         }
                     };
                 },
@@ -56,4 +66,15 @@ impl UnaryParser {
 
         Ok(SmartCalcAstType::None)
     }
+
+    fn parse_bitwise_not(parser: &mut SyntaxParser, index_backup: usize) -> AstResult {
+        match UnaryParser::parse(parser)? {
+            SmartCalcAstType::None => {
+                let error = parser.syntax_error("Bitwise not works with number", index_backup);
+                parser.set_index(index_backup);
+                Err(error)
+            },
+            ast => Ok(SmartCalcAstType::PrefixUnary('~', Rc::new(ast)))
+        }
+    }
 }
\ No newline at end of file
@@ -28,10 +28,10 @@ pub fn is_ast_empty(ast: &AstResult) -> bool {
     }
 }
 
-pub fn err_or_message(ast: &AstResult, message: &'static str) -> AstResult {
+pub fn err_or_message(parser: &SyntaxParser, ast: &AstResult, message: &'static str, start_index: usize) -> AstResult {
     match &ast {
-        Ok(SmartCalcAstType::None) => Err((message, 0, 0,)),
+        Ok(SmartCalcAstType::None) => Err(parser.syntax_error(message, start_index)),
         Ok(_) => Ok(SmartCalcAstType::None),
-        Err((m, l, c)) => Err((m, *l, *c))
+        Err(error) => Err(error.clone())
     }
 }
@@ -0,0 +1,100 @@
+/*
+ * smartcalc v1.0.8
+ * Copyright (c) Erhan BARIS (Ruslan Ognyanov Asenov)
+ * Licensed under the GNU General Public License v2.0.
+ */
+
+use alloc::string::{String, ToString};
+use serde_json::{Value, json};
+use crate::{SmartCalc, ConfigError};
+use crate::config::SmartCalcConfig;
+use crate::constants::JSON_DATA;
+
+fn load_modified<F: Fn(&mut Value)>(modify: F) -> Result<SmartCalcConfig, ConfigError> {
+    let mut json_data: Value = serde_json::from_str(JSON_DATA).unwrap();
+    modify(&mut json_data);
+    SmartCalcConfig::try_load_from_json(&serde_json::to_string(&json_data).unwrap())
+}
+
+#[test]
+fn default_config_test() {
+    assert!(SmartCalcConfig::try_load_from_json(JSON_DATA).is_ok());
+    assert!(SmartCalc::try_load_from_json(JSON_DATA).is_ok());
+}
+
+#[test]
+fn json_error_test() {
+    match SmartCalc::try_load_from_json("{\n  \"parse\": ") {
+        Err(ConfigError::Json { line, .. }) => assert_eq!(line, 2),
+        _ => panic!("JSON error expected")
+    };
+}
+
+#[test]
+fn invalid_regex_test() {
+    let error = load_modified(|json_data| json_data["parse"]["number"][0] = json!("(?P<NUMBER>[0-9")).err().unwrap();
+    match error {
+        ConfigError::InvalidRegex { path, .. } => assert_eq!(path, "parse.number[0]"),
+        _ => panic!("Invalid regex error expected")
+    };
+}
+
+#[test]
+fn unknown_rule_function_test() {
+    let error = load_modified(|json_data| json_data["languages"]["en"]["rules"]["not_exist"] = json!({ "samples": [], "rules": ["{NUMBER:number}"] })).err().unwrap();
+    assert_eq!(error, ConfigError::UnknownRuleFunction { path: "languages.en.rules.not_exist".to_string() });
+}
+
+#[test]
+fn unknown_currency_test() {
+    let error = load_modified(|json_data| json_data["currency_alias"]["dogecoin"] = json!("doge")).err().unwrap();
+    assert_eq!(error, ConfigError::UnknownCurrency { path: "currency_alias.dogecoin".to_string(), currency: "doge".to_string() });
+}
+
+#[test]
+fn unknown_type_test() {
+    let error = load_modified(|json_data| json_data["type_conversion"][0]["source"]["name"] = json!("not-exist")).err().unwrap();
+    assert_eq!(error, ConfigError::UnknownType { path: "type_conversion[0].source".to_string(), name: "not-exist".to_string(), index: 1 });
+    assert_eq!(error.to_string(), String::from("type_conversion[0].source: not-exist:1 type not defined"));
+}
+
+fn execute_output(calculater: &SmartCalc, data: &str) -> String {
+    let results = calculater.execute("en", data);
+    results.lines[0].as_ref().unwrap().result.as_ref().unwrap().output.to_string()
+}
+
+#[test]
+fn merge_json_test() {
+    let mut calculater = SmartCalc::default();
+    calculater.update_currency("eur", 2.0);
+
+    calculater.merge_json(r#"{
+        "currencies": {
+            "DOGE": { "code": "DOGE", "symbol": "Ð", "thousandsSeparator": ".", "decimalSeparator": ",", "symbolOnLeft": true, "spaceBetweenAmountAndSymbol": false, "decimalDigits": 2 }
+        },
+        "currency_alias": { "dogecoin": "doge" },
+        "currency_rates": { "doge": 10.0 },
+        "types": [{
+            "name": "metric-length",
+            "items": [{ "index": 8, "format": "{value} Megameter", "parse": ["{NUMBER:value} {TEXT:type:megameter}"], "names": ["megameter"], "upgrade_code": "{value}", "downgrade_code": "{value} * 1000" }]
+        }]
+    }"#).unwrap();
+
+    assert_eq!(execute_output(&calculater, "1 usd to dogecoin"), "Ð10,00");
+    assert_eq!(execute_output(&calculater, "1 usd to eur"), "2,00 €");
+    assert_eq!(execute_output(&calculater, "2 megameter to km"), "2.000 Kilometer");
+    assert_eq!(execute_output(&calculater, "April 12, 2022"), "12 Apr 2022");
+}
+
+#[test]
+fn merge_json_error_test() {
+    let mut calculater = SmartCalc::default();
+    let error = calculater.merge_json(r#"{ "currency_alias": { "dogecoin": "doge" } }"#).err().unwrap();
+    assert_eq!(error, ConfigError::UnknownCurrency { path: "currency_alias.dogecoin".to_string(), currency: "doge".to_string() });
+
+    let error = calculater.merge_json(r#"{ "languages": { "de": { "alias": { "und": "+" } } } }"#).err().unwrap();
+    assert_eq!(error, ConfigError::InvalidValue { path: "languages.de.format".to_string(), message: "format is required for a new language".to_string() });
+
+    /* Configuration is not changed */
+    assert_eq!(execute_output(&calculater, "10 + 5"), "15");
+}
@@ -0,0 +1,55 @@
+/*
+ * smartcalc v1.0.8
+ * Copyright (c) Erhan BARIS (Ruslan Ognyanov Asenov)
+ * Licensed under the GNU General Public License v2.0.
+ */
+
+use crate::SmartCalc;
+use crate::SmartCalcError;
+use alloc::string::ToString;
+
+fn execute_error(data: &str) -> SmartCalcError {
+    let calculater = SmartCalc::default();
+    let results = calculater.execute("en".to_string(), data);
+    results.lines[0].as_ref().unwrap().result.as_ref().unwrap_err().clone()
+}
+
+#[test]
+fn syntax_error_test() {
+    assert_eq!(execute_error("(1 + 2"), SmartCalcError::syntax("Parentheses not closed", 0, 6));
+    assert_eq!(execute_error("10 + sqrt(1 + 2"), SmartCalcError::syntax("Parentheses not closed", 5, 15));
+    assert_eq!(execute_error("3 * (1 + )"), SmartCalcError::syntax("No more token", 9, 10));
+}
+
+#[test]
+fn calculation_error_test() {
+    assert_eq!(execute_error("10 / 0"), SmartCalcError::DivisionByZero);
+    assert_eq!(execute_error("10 usd mod 0"), SmartCalcError::DivisionByZero);
+    assert_eq!(execute_error("10 ^ 400"), SmartCalcError::Overflow);
+    assert_eq!(execute_error("1e400"), SmartCalcError::Overflow);
+    assert_eq!(execute_error("1e400 usd"), SmartCalcError::Overflow);
+    assert_eq!(execute_error("0x1FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF"), SmartCalcError::Overflow);
+    assert_eq!(execute_error("10 usd + 5 cad"), SmartCalcError::MissingCurrencyRate("CAD".to_string()));
+    assert_eq!(execute_error("10 ^ 2 usd"), SmartCalcError::IncompatibleTypes { operator: '^', left: "NUMBER".to_string(), right: "MONEY".to_string() });
+    assert_eq!(execute_error("10 usd ^ 2"), SmartCalcError::IncompatibleTypes { operator: '^', left: "MONEY".to_string(), right: "NUMBER".to_string() });
+    assert_eq!(execute_error("3 km ^ 2"), SmartCalcError::IncompatibleTypes { operator: '^', left: "DYNAMIC_TYPE".to_string(), right: "NUMBER".to_string() });
+    assert_eq!(execute_error("(-8) ^ (1/3)"), SmartCalcError::InvalidArgument("Result is not a real number".to_string()));
+    assert_eq!(execute_error("sqrt(1, 2)").to_string(), "sqrt requires 1 argument(s)".to_string());
+    assert_eq!(execute_error("sqrt 16"), SmartCalcError::InvalidArgument("sqrt requires parentheses, sqrt(...)".to_string()));
+    assert_eq!(execute_error("10 + log 100 usd"), SmartCalcError::InvalidArgument("log requires parentheses, log(...)".to_string()));
+    assert_eq!(execute_error("pick a, b, c").to_string(), "pick count must be a positive integer".to_string());
+    assert_eq!(execute_error("pick one of a, b, c + 1"), SmartCalcError::IncompatibleTypes { operator: '+', left: "TEXT".to_string(), right: "NUMBER".to_string() });
+    assert_eq!(execute_error("round 12,345 to 20 digits"), SmartCalcError::InvalidArgument("Decimal digits not valid".to_string()));
+    assert_eq!(execute_error("round 12,3 to nearest 0"), SmartCalcError::InvalidArgument("Rounding step not valid".to_string()));
+    assert_eq!(execute_error("10:07 to nearest 0 minutes"), SmartCalcError::InvalidArgument("Rounding step not valid".to_string()));
+    assert_eq!(execute_error("7/2 to hex"), SmartCalcError::InvalidArgument("Fraction is not an integer".to_string()));
+}
+
+#[test]
+fn error_serialize_test() {
+    assert_eq!(serde_json::to_string(&SmartCalcError::DivisionByZero).unwrap(), r#""DivisionByZero""#);
+    assert_eq!(serde_json::to_string(&SmartCalcError::syntax("Parentheses not closed", 0, 6)).unwrap(), r#"{"Syntax":{"message":"Parentheses not closed","start":0,"end":6}}"#);
+
+    let error: SmartCalcError = serde_json::from_str(r#"{"MissingCurrencyRate":"CAD"}"#).unwrap();
+    assert_eq!(error, SmartCalcError::MissingCurrencyRate("CAD".to_string()));
+}
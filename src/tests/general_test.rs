@@ -192,3 +192,237 @@ fn weight_height_tests() {
 10 pound to ounce        |   160 Ounce
 "#.to_string(), ".".to_string(), ",".to_string(), "CET".to_string());        
 }
+
+#[test]
+fn modulo_and_exponent_tests() {
+    execute(r#"
+17 % 5                   | 2
+17 mod 5                 | 2
+2 ^ 10                   | 1.024
+2^10                     | 1.024
+2 ^ 3 ^ 2                | 512
+2 * 3 ^ 2                | 18
+(2 + 1) ^ 2              | 9
+4 ^ 0,5                  | 2
+-2 ^ 2                   | -4
+3 - 2 ^ 2                | -1
+5 * -2 ^ 2               | -20
+2 ^ -2                   | 0,25
+17 usd mod 5             | $2,00
+10 km mod 3 km           | 1 Kilometer
+"#.to_string(), ",".to_string(), ".".to_string(), "UTC".to_string());
+}
+
+#[test]
+fn function_call_tests() {
+    execute(r#"
+sqrt(16)                      | 4
+sqrt(16) + 2 * abs(-3)        | 10
+round(12,345 usd, 1)          | $12,30
+round(12,345)                 | 12
+floor(12,345; 2)              | 12,34
+ceil(12,345 usd)              | $13,00
+max(3 km, 2000 m)             | 3 Kilometer
+min(3 km, 2000 m)             | 2.000 Meter
+max(1, 5, 3)                  | 5
+ln(1)                         | 0
+log10(1000)                   | 3
+log(8, 2)                     | 3
+sin(0) + cos(0)               | 1
+factorial(5)                  | 120
+x = 9                         | 9
+sqrt(x) ^ 2                   | 9
+"#.to_string(), ",".to_string(), ".".to_string(), "UTC".to_string());
+}
+
+#[test]
+fn line_reference_tests() {
+    execute(r#"
+10                            | 10
+20                            | 20
+sum                           | 30
+prev * 2                      | 60
+ans + line2                   | 70
+line3 / 4                     | 5
+
+100 usd                       | $100,00
+50 usd                        | $50,00
+total                         | $150,00
+total expenses = 20           | 20
+total expenses * 2            | 40
+sum + 10                      | $160,00
+total * 2                     | $300,00
+
+5 sum 3                       | 8
+sum                           | 8
+"#.to_string(), ",".to_string(), ".".to_string(), "UTC".to_string());
+}
+
+#[test]
+fn programmer_mode_tests() {
+    execute(r#"
+-5 to binary 8 bit            | 0b11111011
+300 to hex 8 bit              | 0x2C
+0xFB to decimal 8 bit signed  | -5
+0xFB to decimal 8 bit         | 251
+0xF0 & 0x3C                   | 0x30
+0xF0 xor 0x3C                 | 0xCC
+0x1 << 4                      | 0x10
+0x100 >> 4                    | 0x10
+0x1 shl 8                     | 0x100
+12 & 10                       | 8
+5 xor 3                       | 6
+~0                            | -1
+~0x0F                         | 0xFFFFFFFFFFFFFFF0
+0x7FFFFFFFFFFFFFFF + 0x1      | 0x8000000000000000
+byteswap(0x1234, 16)          | 0x3412
+0xFFFFFFFFFFFFFFFF            | 0xFFFFFFFFFFFFFFFF
+0x1234567890ABCDEF            | 0x1234567890ABCDEF
+0x1234567890ABCDEF + 0x1      | 0x1234567890ABCDF0
+0x1234567890ABCDEF xor 0xFF   | 0x1234567890ABCD10
+2^63 to hex                   | 0x8000000000000000
+2^40 to binary                | 0b10000000000000000000000000000000000000000
+2 + 3 to hex                  | 0x5
+2^10 to sci                   | 1,02e3
+"#.to_string(), ",".to_string(), ".".to_string(), "UTC".to_string());
+}
+
+#[test]
+fn fraction_tests() {
+    execute(r#"
+3/4                           | 3/4
+1 1/2                         | 1 1/2
+6/4                           | 1 1/2
+1 1/2 + 3/4                   | 2 1/4
+2 * 3/4                       | 1 1/2
+1 1/2 * 2                     | 3
+1/3 + 0,5                     | 5/6
+1/2 + %50                     | 3/4
+(1/2) ^ 2                     | 1/4
+10 / 3                        | 3,33
+10 / 3 to fraction            | 3 1/3
+0,75 to fraction              | 3/4
+3/4 to decimal                | 0,75
+8/2 to hex                    | 0x4
+10 usd * 3/4                  | $7,50
+1/2 usd                       | $0,50
+3/4 km                        | 0,75 Kilometer
+1 1/2 hours                   | 1 hour 30 minutes
+3/4 minute                    | 45 seconds
+1 1/2 days                    | 1 day 12 hours
+sqrt(1/4)                     | 1/2
+x = 1/3                       | 1/3
+x * 3                         | 1
+12/02/2022                    | 12 Feb 2022
+"#.to_string(), ",".to_string(), ".".to_string(), "UTC".to_string());
+}
+
+#[test]
+fn scientific_notation_tests() {
+    execute(r#"
+6,022e23                      | 6,02e23
+1,5E-3 to sci                 | 1,5e-3
+2e3 * 2                       | 4.000
+6,022e23 / 1e23               | 6,02
+123456789012345               | 123.456.789.012.345
+1e15                          | 1e15
+0,005                         | 0,01
+2e3 usd                       | $2.000,00
+1,5e-1 usd                    | $0,15
+$2e3                          | $2.000,00
+0,00047 to eng                | 470 µ
+12346 to eng                  | 12,35 k
+0,00047 to sci                | 4,7e-4
+1500 to scientific            | 1,5e3
+2,5 to hex                    | 0x3
+1/3 to sci                    | 3,33e-1
+"#.to_string(), ",".to_string(), ".".to_string(), "UTC".to_string());
+}
+
+#[test]
+fn rounding_tests() {
+    execute(r#"
+round 12,345 eur to 2 digits half even   | 12,34 €
+round 12,355 eur to 2 digits half even   | 12,36 €
+round 12,345 eur to 2 digits             | 12,35 €
+round 12,349 eur to 2 digits truncate    | 12,34 €
+round 12,341 to 2 digits up              | 12,35
+round -12,349 to 1 digit down            | -12,40
+12,32 to nearest 0,05                    | 12,30
+round 12,33 eur to nearest 0,05          | 12,35 €
+round 17 to nearest 5 down               | 15
+10:07 to nearest 5 minutes               | 10:05:00 UTC
+round 10:08 to nearest 15 minutes up     | 10:15:00 UTC
+2 hours 8 minutes to nearest 5 minutes   | 2 hours 10 minutes
+"#.to_string(), ",".to_string(), ".".to_string(), "UTC".to_string());
+}
+
+#[test]
+fn significant_figures_tests() {
+    execute(r#"
+0,000123456 to 3 sig figs                | 0,000123
+123456 to 3 sig figs                     | 123.000
+3,14159 to 4 significant figures         | 3,142
+2 as 3 sig figs                          | 2,00
+9,996 to 3 sig figs                      | 10,0
+0,04567 to 1 sig fig                     | 0,05
+"#.to_string(), ",".to_string(), ".".to_string(), "UTC".to_string());
+}
+
+#[test]
+fn number_words_tests() {
+    execute(r#"
+one hundred and twenty five usd          | $125,00
+twenty-one + three                       | 24
+1250 eur to words                        | one thousand two hundred fifty euros
+12,5 usd to words                        | twelve dollars and fifty cents
+-42 as words                             | minus forty two
+"#.to_string(), ",".to_string(), ".".to_string(), "UTC".to_string());
+}
+
+#[test]
+fn number_notation_tests() {
+    execute(r#"
+2,5 million usd                          | $2.500.000,00
+$1 billion / 4                           | $250.000.000,00
+3 dozen                                  | 36
+2 gross                                  | 288
+5 lakh + 1 crore                         | 10.500.000
+%2 thousand of 1                         | 20
+5 thousand + 250                         | 5.250
+"#.to_string(), ",".to_string(), ".".to_string(), "UTC".to_string());
+}
+
+#[test]
+fn statistics_tests() {
+    execute(r#"
+average of 12, 15, 21 usd                | $16,00
+median 3 5 9 11                          | 7
+sum(1, 2, 3) * 2                         | 12
+count 3 5 9                              | 3
+percentile 75 of 3, 5, 9, 11             | 9,50
+minimum of 3 km, 2500 m                  | 2.500 Meter
+average of 1 hour, 30 minutes            | 45 minutes
+stddev 2 4 4 4 5 5 7 9                   | 2,14
+prices = 12, 15, 20 usd                  | $12,00; $15,00; $20,00
+sum of prices                            | $47,00
+maximum of prices                        | $20,00
+"#.to_string(), ",".to_string(), ".".to_string(), "UTC".to_string());
+}
+
+#[test]
+fn random_tests() {
+    execute(r#"
+random between 5 and 5                   | 5
+random 3 usd to 3 usd                    | $3,00
+1d1 + 2d1                                | 3
+pick 3 of 7, 7, 7                        | 7; 7; 7
+d2 = 5                                   | 5
+d2 * 2                                   | 10
+x = 7                                    | 7
+pick one of x, x                         | 7
+pick one of Alice, Alice                 | Alice
+pick 2 of a, a                           | a; a
+d1 + 1                                   | 2
+"#.to_string(), ",".to_string(), ".".to_string(), "UTC".to_string());
+}
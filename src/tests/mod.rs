@@ -4,6 +4,8 @@
  * Licensed under the GNU General Public License v2.0.
  */
 
+mod config_test;
+mod error_test;
 mod executer_test;
 mod general_test;
 mod variable_test;
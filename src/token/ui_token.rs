@@ -80,13 +80,18 @@ impl UiTokenCollection {
 
     pub fn add_from_regex_match(&mut self, capture: Option<Match<'_>>, token_type: UiTokenType) {
         if let Some(content) = capture {
-            if content.start() < content.end() && self.check_collision(content.start(), content.end()) {
-                self.tokens.push(UiToken {
-                    start: self.get_position(content.start()),
-                    end: self.get_position(content.end()),
-                    ui_type: token_type
-                });
-            }
+            self.add_from_location(content.start(), content.end(), token_type);
+        }
+    }
+
+    /* Start and end are byte positions in the text */
+    pub fn add_from_location(&mut self, start: usize, end: usize, token_type: UiTokenType) {
+        if start < end && self.check_collision(start, end) {
+            self.tokens.push(UiToken {
+                start: self.get_position(start),
+                end: self.get_position(end),
+                ui_type: token_type
+            });
         }
     }
 
@@ -4,6 +4,7 @@
  * Licensed under the GNU General Public License v2.0.
  */
 
+use core::ops::Deref;
 use alloc::vec::Vec;
 use alloc::string::ToString;
 
@@ -14,7 +15,12 @@ use super::{Tokinizer, regex_tokinizer::get_atom};
  
 
 pub fn alias_tokinizer(tokinizer: &mut Tokinizer) {
-    for token in tokinizer.token_infos.iter() {
+    /* Function calls like "sum(1, 2)" are not aliases */
+    let tokens = tokinizer.token_infos.iter()
+        .filter(|token| !matches!(token.token_type.borrow().deref(), Some(TokenType::Function(_))))
+        .collect::<Vec<_>>();
+
+    for token in tokens.iter() {
         for (re, data) in tokinizer.config.alias_regex.iter() {
             if re.is_match(&token.original_text.to_lowercase()) {
                 let new_values = match tokinizer.config.token_parse_regex.get("atom") {
@@ -39,7 +45,7 @@ pub fn alias_tokinizer(tokinizer: &mut Tokinizer) {
         }
     }
 
-    for token in tokinizer.token_infos.iter() {
+    for token in tokens.iter() {
         for (re, data) in tokinizer.config.language_alias_regex.get(&tokinizer.language).unwrap().iter() {
             if re.is_match(&token.original_text.to_lowercase()) {
                 let new_values = match tokinizer.config.token_parse_regex.get("atom") {
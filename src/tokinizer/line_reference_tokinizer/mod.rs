@@ -0,0 +1,117 @@
+/*
+ * smartcalc v1.0.8
+ * Copyright (c) Erhan BARIS (Ruslan Ognyanov Asenov)
+ * Licensed under the GNU General Public License v2.0.
+ */
+
+use core::cell::{Cell, RefCell};
+use core::ops::Deref;
+use alloc::rc::Rc;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::compiler::{DataItem, OperationType};
+use crate::compiler::number::NumberItem;
+use crate::config::SmartCalcConfig;
+use crate::error::SmartCalcWarning;
+use crate::session::Session;
+use crate::token::ui_token::UiTokenType;
+use crate::types::{TokenType, NumberType};
+
+use super::{Tokinizer, TokenInfo, TokenInfoStatus};
+use super::tools::get_word;
+
+fn is_in_group(config: &SmartCalcConfig, language: &str, group: &str, text: &str) -> bool {
+    match config.word_group.get(language).and_then(|groups| groups.get(group)) {
+        Some(items) => items.iter().any(|item| item.to_lowercase() == text),
+        None => false
+    }
+}
+
+/* Unit-less numbers are not added to money or unit totals and other lines are not added to unit-less totals */
+fn is_unitless(item: &dyn DataItem) -> bool {
+    matches!(item.type_name(), "NUMBER" | "FRACTION")
+}
+
+/* First line decides the type of the total, money lines are converted to the currency of the first line. Lines that could not be added are reported */
+fn calculate_total(config: &SmartCalcConfig, session: &Session, items: &[(usize, Rc<dyn DataItem>)]) -> Rc<dyn DataItem> {
+    let mut items = items.iter();
+    let mut total: Rc<dyn DataItem> = match items.next() {
+        Some((_, item)) => item.clone(),
+        None => return Rc::new(NumberItem(0.0, NumberType::Decimal))
+    };
+
+    let mut skipped_lines = Vec::new();
+    for (line, item) in items {
+        match total.calculate(config, true, item.deref(), OperationType::Add) {
+            Some(result) if is_unitless(total.deref()) == is_unitless(item.deref()) => total = result,
+            _ => skipped_lines.push(line + 1)
+        };
+    }
+
+    if !skipped_lines.is_empty() {
+        session.add_warning(SmartCalcWarning::SkippedTotalLines { lines: skipped_lines });
+    }
+
+    total
+}
+
+pub fn line_reference_tokinizer(tokinizer: &mut Tokinizer) {
+    let session = tokinizer.session;
+    let config = tokinizer.config;
+
+    /* Variable name can not have line reference */
+    let start = tokinizer.token_infos.iter()
+        .position(|token| matches!(token.token_type.borrow().deref(), Some(TokenType::Operator('='))))
+        .map_or(0, |index| index + 1);
+    let mut index = start;
+
+    while index < tokinizer.token_infos.len() {
+        let text = get_word(&tokinizer.token_infos[index]).unwrap_or_default();
+
+        /* "sum" is also an alias of "+", it is a total only if there is no value on its left */
+        let is_operator = matches!(tokinizer.token_infos[index].token_type.borrow().deref(), Some(TokenType::Operator(_)));
+        let has_left_value = index > start && !matches!(tokinizer.token_infos[index - 1].token_type.borrow().deref(), Some(TokenType::Operator(operator)) if *operator != ')');
+
+        let mut token_count = 1;
+        let item = if text.is_empty() || (is_operator && has_left_value) {
+            None
+        } else if is_in_group(config, &tokinizer.language, "line_total_group", &text) {
+            Some(calculate_total(config, session, &session.get_block_results()))
+        } else if is_operator {
+            None
+        } else if is_in_group(config, &tokinizer.language, "line_previous_group", &text) {
+            session.get_previous_line_result()
+        } else if is_in_group(config, &tokinizer.language, "line_group", &text) {
+            /* Line number should follow the keyword, line3 or line 3 */
+            let line_number = match tokinizer.token_infos.get(index + 1).map(|token| token.token_type.borrow().clone()) {
+                Some(Some(TokenType::Number(number, _))) if number >= 1.0 && number.fract() == 0.0 => Some(number as usize),
+                _ => None
+            };
+
+            token_count = 2;
+            line_number.and_then(|line_number| session.get_line_result(line_number - 1))
+        } else {
+            None
+        };
+
+        if let Some(item) = item {
+            session.set_line_reference();
+            let start = tokinizer.token_infos[index].start;
+            let end = tokinizer.token_infos[index + token_count - 1].end;
+            let original_text = tokinizer.token_infos[index..index + token_count].iter().map(|token| token.original_text.as_str()).collect::<String>();
+
+            tokinizer.ui_tokens.update_tokens(start, end, UiTokenType::VariableUse);
+            tokinizer.token_infos.drain(index..index + token_count);
+            tokinizer.token_infos.insert(index, Rc::new(TokenInfo {
+                start,
+                end,
+                token_type: RefCell::new(Some(item.as_token_type())),
+                original_text,
+                status: Cell::new(TokenInfoStatus::Active)
+            }));
+        }
+
+        index += 1;
+    }
+}
@@ -8,14 +8,20 @@ mod regex_tokinizer;
 mod alias_tokinizer;
 mod rule_tokinizer;
 mod dynamic_type_tokinizer;
+mod line_reference_tokinizer;
+mod statistics_tokinizer;
 mod tools;
 
 pub use self::rule_tokinizer::rules::date_rules::small_date;
 
 pub use self::regex_tokinizer::regex_tokinizer;
 pub use self::regex_tokinizer::language_tokinizer;
+pub use self::regex_tokinizer::number_word_tokinizer;
+pub use self::regex_tokinizer::number_notation_tokinizer;
 pub use self::alias_tokinizer::alias_tokinizer;
 pub use self::dynamic_type_tokinizer::dynamic_type_tokinizer;
+pub use self::line_reference_tokinizer::line_reference_tokinizer;
+pub use self::statistics_tokinizer::statistics_tokinizer;
 pub use self::tools::*;
 pub use self::rule_tokinizer::{rule_tokinizer, RuleType, RuleItemList, RULE_FUNCTIONS};
 
@@ -27,10 +33,12 @@ use alloc::rc::Rc;
 use alloc::string::String;
 use alloc::vec::Vec;
 use alloc::string::ToString;
+use alloc::format;
 
 use regex::Match;
 
 use crate::UiTokenType;
+use crate::error::SmartCalcError;
 use crate::session::Session;
 use crate::config::SmartCalcConfig;
 use crate::variable::update_token_variables;
@@ -50,6 +58,7 @@ pub struct Tokinizer<'a> {
     pub language: String,
     pub token_infos: Vec<Rc<TokenInfo>>,
     pub tokens: Vec<Rc<TokenType>>,
+    pub token_locations: Vec<(usize, usize)>
 }
 
 #[derive(Debug)]
@@ -88,7 +97,8 @@ impl<'a> Tokinizer<'a> {
             session,
             language: session.get_language(),
             token_infos: Vec::new(),
-            tokens: Vec::new()
+            tokens: Vec::new(),
+            token_locations: Vec::new()
         }
     }
 
@@ -105,7 +115,8 @@ impl<'a> Tokinizer<'a> {
             session,
             language: session.get_language(),
             token_infos: Vec::new(),
-            tokens: Vec::new()
+            tokens: Vec::new(),
+            token_locations: Vec::new()
         };
 
         language_tokinizer(&mut tokinizer);
@@ -117,12 +128,20 @@ impl<'a> Tokinizer<'a> {
     pub fn tokinize(&mut self) -> bool {
         language_tokinizer(self);
         log::debug!(" > language_tokinizer");
+        number_notation_tokinizer(self);
+        log::debug!(" > number_notation_tokinizer");
+        number_word_tokinizer(self);
+        log::debug!(" > number_word_tokinizer");
         regex_tokinizer(self);
         log::debug!(" > regex_tokinizer");
         alias_tokinizer(self);
         log::debug!(" > alias_tokinizer");
         update_token_variables(self);
         log::debug!(" > update_token_variables");
+        statistics_tokinizer(self);
+        log::debug!(" > statistics_tokinizer");
+        line_reference_tokinizer(self);
+        log::debug!(" > line_reference_tokinizer");
         dynamic_type_tokinizer(self);
         log::debug!(" > dynamic_type_tokinizer");
         rule_tokinizer(self);
@@ -133,6 +152,8 @@ impl<'a> Tokinizer<'a> {
         log::debug!(" > token_generator");        
         self.token_cleaner();
         log::debug!(" > token_cleaner");
+        self.list_token_adder();
+        log::debug!(" > list_token_adder");
         self.missing_token_adder();
         log::debug!(" > missing_token_adder");
 
@@ -162,6 +183,10 @@ impl<'a> Tokinizer<'a> {
         self.ui_tokens.add_from_regex_match(capture, token_type)
     }
 
+    pub fn add_uitoken_location(&mut self, start: usize, end: usize, token_type: UiTokenType) {
+        self.ui_tokens.add_from_location(start, end, token_type)
+    }
+
     pub fn add_token_location(&mut self, start: usize, end: usize, token_type: Option<TokenType>, text: String) -> bool {
         for item in self.token_infos.iter() {
             if (item.start <= start && item.end > start) || (item.start < end && item.end >= end) {
@@ -184,13 +209,14 @@ impl<'a> Tokinizer<'a> {
         for token_location in self.token_infos.iter() {
             if token_location.status.get() == TokenInfoStatus::Active {
                 if let Some(token_type) = &token_location.token_type.borrow().deref() {
-                    tokens.push(token_type.clone());
+                    tokens.push((token_type.clone(), token_location.start, token_location.end));
                 }
             }
         }
         
-        for token in tokens {
+        for (token, start, end) in tokens {
             self.tokens.push(Rc::new(token));
+            self.token_locations.push((start, end));
         }
     }
 
@@ -205,14 +231,73 @@ impl<'a> Tokinizer<'a> {
 
         while index < self.tokens.len() {
             match self.tokens[index].deref() {
-                TokenType::Text(_) => {
+                TokenType::Text(text) => {
+                    /* Function name is dropped like other words, so "sqrt 16" would be 16. Word after a value is not a call, "5 min 30 sec" */
+                    let name = text.to_lowercase();
+                    let follows_value = index > 0 && Tokinizer::is_list_value(&self.tokens[index - 1]);
+                    if !follows_value && self.config.functions.contains_key(&name) && self.tokens.get(index + 1).is_some_and(|token| Tokinizer::is_list_value(token)) {
+                        self.session.set_line_error(SmartCalcError::InvalidArgument(format!("{} requires parentheses, {}(...)", name, name)));
+                    }
+
                     self.tokens.remove(index);
+                    self.token_locations.remove(index);
                 },
                 _ => index += 1
             };
         }
     }
 
+    fn is_list_value(token: &TokenType) -> bool {
+        matches!(token, TokenType::Number(_, _) | TokenType::Integer(_, _) | TokenType::Money(_, _) | TokenType::DisplayMoney(_, _, _) | TokenType::Percent(_) | TokenType::Fraction(_, _) | TokenType::Duration(_) |
+            TokenType::DynamicType(_, _) | TokenType::Variable(_) | TokenType::List(_))
+    }
+
+    /* Function without parentheses uses the values after it, "median 3 5 9 11" is median(3, 5, 9, 11).
+       Values written with comma are a list, "12, 15, 20 usd" is list(12, 15, 20 usd) */
+    fn list_token_adder(&mut self) {
+        let mut index = 0;
+        while index < self.tokens.len() {
+            if !matches!(self.tokens[index].deref(), TokenType::Function(_)) || matches!(self.tokens.get(index + 1).map(|token| token.deref()), Some(TokenType::Operator('('))) {
+                index += 1;
+                continue;
+            }
+
+            self.insert_missing_token(index + 1, TokenType::Operator('('));
+            index += 2;
+
+            let mut separator_required = false;
+            while index < self.tokens.len() {
+                match self.tokens[index].deref() {
+                    TokenType::Operator(',') => separator_required = false,
+                    token if Tokinizer::is_list_value(token) => {
+                        if separator_required {
+                            self.insert_missing_token(index, TokenType::Operator(','));
+                            index += 1;
+                        }
+                        separator_required = true;
+                    },
+                    _ => break
+                };
+                index += 1;
+            }
+
+            self.insert_missing_token(index, TokenType::Operator(')'));
+            index += 1;
+        }
+
+        let start = self.tokens.iter().position(|token| matches!(token.deref(), TokenType::Operator('='))).map_or(0, |index| index + 1);
+        let tokens = &self.tokens[start..];
+        let is_list = tokens.iter().any(|token| matches!(token.deref(), TokenType::Operator(','))) &&
+            tokens.iter().all(|token| matches!(token.deref(), TokenType::Operator(',')) || Tokinizer::is_list_value(token)) &&
+            tokens.first().is_some_and(|token| Tokinizer::is_list_value(token));
+
+        if is_list {
+            self.insert_missing_token(start, TokenType::Function("list".to_string()));
+            self.insert_missing_token(start + 1, TokenType::Operator('('));
+            self.insert_missing_token(self.tokens.len(), TokenType::Operator(')'));
+        }
+    }
+
     fn missing_token_adder(&mut self) {
         let mut index = 0;
         
@@ -241,8 +326,11 @@ impl<'a> Tokinizer<'a> {
 
         let mut operator_required = false;
 
-        if let TokenType::Operator(_) = self.tokens[index].deref() {
-            self.tokens.insert(index, Rc::new(TokenType::Number(0.0, NumberType::Decimal)));
+        /* Bitwise not is prefix only, there is no left side for it. Function without argument is closed immediately */
+        if let TokenType::Operator(operator) = self.tokens[index].deref() {
+            if *operator != '~' && *operator != ')' {
+                self.insert_missing_token(index, TokenType::Number(0.0, NumberType::Decimal));
+            }
         }
 
         while index < self.tokens.len() {
@@ -251,7 +339,7 @@ impl<'a> Tokinizer<'a> {
                 _ => {
                     if operator_required {
                         log::debug!("Added missing operator between two token");
-                        self.tokens.insert(index, Rc::new(TokenType::Operator('+')));
+                        self.insert_missing_token(index, TokenType::Operator('+'));
                         index += 1;
                     }
                     operator_required = true;
@@ -262,6 +350,16 @@ impl<'a> Tokinizer<'a> {
         }
     }
     
+    /* Added token does not exist in the text, it is located at the start of the next token or at the end of the line */
+    fn insert_missing_token(&mut self, index: usize, token: TokenType) {
+        let start = match self.token_locations.get(index) {
+            Some((start, _)) => *start,
+            None => self.token_locations.last().map_or(0, |(_, end)| *end)
+        };
+        self.tokens.insert(index, Rc::new(token));
+        self.token_locations.insert(index, (start, start));
+    }
+
     pub fn cleanup_token_infos(&mut self) {
         self.token_infos.retain(|x| (*x).token_type.borrow().deref().is_some());
         self.token_infos.sort_by(|a, b| (*a).start.partial_cmp(&b.start).unwrap());
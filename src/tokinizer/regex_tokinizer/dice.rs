@@ -0,0 +1,83 @@
+/*
+ * smartcalc v1.0.8
+ * Copyright (c) Erhan BARIS (Ruslan Ognyanov Asenov)
+ * Licensed under the GNU General Public License v2.0.
+ */
+
+use alloc::borrow::ToOwned;
+use rand::Rng;
+use crate::config::SmartCalcConfig;
+use crate::types::*;
+use crate::tokinizer::Tokinizer;
+use regex::{Match, Regex};
+use crate::token::ui_token::{UiTokenType};
+
+/* Dice without count can be a variable name, "d2 = 5" defines it and "d2 * 2" uses it */
+fn is_variable_name(tokinizer: &Tokinizer, name: &Match) -> bool {
+    tokinizer.data[name.end()..].contains('=') || tokinizer.session.variables.borrow().contains_key(&name.as_str().to_lowercase())
+}
+
+/* "3d6" is the total of three dices with six sides, "d20" is one dice */
+pub fn dice_regex_parser(_: &SmartCalcConfig, tokinizer: &mut Tokinizer, group_item: &[Regex]) {
+    for re in group_item.iter() {
+        for capture in re.captures_iter(&tokinizer.data.to_owned()) {
+            let count = match capture.name("COUNT") {
+                Some(count) => count.as_str().parse::<u32>().unwrap(),
+                None if is_variable_name(tokinizer, &capture.get(0).unwrap()) => continue,
+                None => 1
+            };
+            let sides = capture.name("SIDES").unwrap().as_str().parse::<u32>().unwrap();
+
+            if count == 0 || sides == 0 {
+                continue;
+            }
+
+            let total: u32 = tokinizer.session.with_random(|random| (0..count).map(|_| random.gen_range(1, sides + 1)).sum());
+            if tokinizer.add_token_from_match(&capture.get(0), Some(TokenType::Number(total as f64, NumberType::Decimal))) {
+                tokinizer.add_uitoken_from_match(capture.get(0), UiTokenType::Number);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn dice_test() {
+    use core::ops::Deref;
+    use alloc::string::ToString;
+    use crate::tokinizer::regex_tokinizer;
+    use crate::tokinizer::test::setup_tokinizer;
+    use crate::config::SmartCalcConfig;
+    use crate::session::Session;
+    let mut session = Session::new();
+    session.set_random_seed(Some(1));
+    let config = SmartCalcConfig::default();
+    let mut tokinizer_mut = setup_tokinizer("3d6 + 1d20 + d20 + 0x3d6".to_string(), &mut session, &config);
+
+    regex_tokinizer(&mut tokinizer_mut);
+    let tokens = &tokinizer_mut.token_infos;
+
+    assert_eq!(tokens[0].start, 0);
+    assert_eq!(tokens[0].end, 3);
+    match tokens[0].token_type.borrow().deref() {
+        Some(TokenType::Number(number, NumberType::Decimal)) => assert!((3.0..=18.0).contains(number)),
+        _ => panic!("3d6 is not a number")
+    };
+
+    assert_eq!(tokens[2].start, 6);
+    assert_eq!(tokens[2].end, 10);
+    match tokens[2].token_type.borrow().deref() {
+        Some(TokenType::Number(number, NumberType::Decimal)) => assert!((1.0..=20.0).contains(number)),
+        _ => panic!("1d20 is not a number")
+    };
+
+    /* Without count it is one dice */
+    assert_eq!(tokens[4].start, 13);
+    assert_eq!(tokens[4].end, 16);
+    match tokens[4].token_type.borrow().deref() {
+        Some(TokenType::Number(number, NumberType::Decimal)) => assert!((1.0..=20.0).contains(number)),
+        _ => panic!("d20 is not a number")
+    };
+
+    assert_eq!(tokens[tokens.len() - 1].token_type.borrow().deref(), &Some(TokenType::Integer(0x3d6, NumberType::Hexadecimal)));
+}
@@ -18,6 +18,7 @@ fn get_field_type<'t>(config: &SmartCalcConfig, type_name: &str, value: &str, la
         "DATE" => Some(FieldType::Date(value.to_string())),
         "TIME" => Some(FieldType::Time(value.to_string())),
         "NUMBER" => Some(FieldType::Number(value.to_string())),
+        "FRACTION" => Some(FieldType::Fraction(value.to_string())),
         "MONEY" => Some(FieldType::Money(value.to_string())),
         "PERCENT" => Some(FieldType::Percent(value.to_string())),
         "MONTH" => Some(FieldType::Month(value.to_string())),
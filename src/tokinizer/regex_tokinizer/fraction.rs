@@ -0,0 +1,135 @@
+/*
+ * smartcalc v1.0.8
+ * Copyright (c) Erhan BARIS (Ruslan Ognyanov Asenov)
+ * Licensed under the GNU General Public License v2.0.
+ */
+
+use alloc::borrow::ToOwned;
+use alloc::string::ToString;
+use chrono::Duration;
+use crate::compiler::fraction::FractionItem;
+use crate::config::SmartCalcConfig;
+use crate::constants::ConstantType;
+use crate::formatter::{MINUTE, HOUR, DAY, WEEK};
+use crate::types::*;
+use crate::tokinizer::{Tokinizer, resolve_currency};
+use regex::Regex;
+use crate::token::ui_token::{UiTokenType};
+
+/* Units, months and years are parsed from numbers, "3/4 km" and "1/2 year" */
+fn is_unit(config: &SmartCalcConfig, language: &str, unit: &str) -> bool {
+    let unit = unit.to_lowercase();
+    let is_duration = match config.word_group.get(language).and_then(|groups| groups.get("duration_group")) {
+        Some(items) => items.iter().any(|item| item.to_lowercase() == unit),
+        None => false
+    };
+
+    is_duration || config.types.values().flat_map(|types| types.values()).any(|dynamic_type| dynamic_type.names.iter().any(|name| name.to_lowercase() == unit))
+}
+
+/* Fixed length durations keep the fract part, "1 1/2 hours" is 1 hour 30 minutes */
+fn fraction_duration(config: &SmartCalcConfig, language: &str, unit: &str, value: f64) -> Option<Duration> {
+    let seconds = match config.constant_pair.get(language)?.get(&unit.to_lowercase())? {
+        ConstantType::Week => WEEK,
+        ConstantType::Day => DAY,
+        ConstantType::Hour => HOUR,
+        ConstantType::Minute => MINUTE,
+        ConstantType::Second => 1,
+        _ => return None
+    };
+
+    Some(Duration::milliseconds((value * (seconds * 1000) as f64).round() as i64))
+}
+
+pub fn fraction_regex_parser(config: &SmartCalcConfig, tokinizer: &mut Tokinizer, group_item: &[Regex]) {
+    let data = tokinizer.data.to_owned();
+
+    for re in group_item.iter() {
+        for capture in re.captures_iter(&data) {
+            let fraction_match = capture.name("FRACTION").unwrap();
+
+            /* Dates (1/2/2022) and parts of other numbers (1,5/2) are not fraction */
+            let previous_char = data[..fraction_match.start()].chars().last();
+            if capture.name("REST").is_some() || matches!(previous_char, Some(previous) if previous.is_alphanumeric() || previous == '.' || previous == ',' || previous == '/') {
+                continue;
+            }
+
+            let whole = match capture.name("WHOLE") {
+                Some(whole) => match whole.as_str().parse::<i128>() {
+                    Ok(whole) => Some(whole),
+                    Err(_) => continue
+                },
+                None => None
+            };
+
+            let (numerator, denominator) = match (capture.name("NUMERATOR").unwrap().as_str().parse::<i128>(), capture.name("DENOMINATOR").unwrap().as_str().parse::<i128>()) {
+                (Ok(numerator), Ok(denominator)) if denominator != 0 => (numerator, denominator),
+                _ => continue
+            };
+
+            let fraction = match whole {
+                /* Mixed number only valid with proper fraction, 2 5/3 is not a mixed number */
+                Some(_) if numerator >= denominator => continue,
+                Some(whole) => FractionItem::new(whole * denominator + numerator, denominator),
+                None => FractionItem::new(numerator, denominator)
+            };
+
+            let fraction = match fraction {
+                Some(fraction) => fraction,
+                None => continue
+            };
+
+            /* Fraction with a currency is money, with a duration it is a duration and with a unit it is a number for the unit rules. Other words are not part of the fraction */
+            let unit = capture.name("UNIT");
+            let value = fraction.get_numerator() as f64 / fraction.get_denominator() as f64;
+
+            let (end, token_type, unit_match) = match unit.map(|unit| (unit, resolve_currency(config, tokinizer.session, unit.as_str()), fraction_duration(config, &tokinizer.language, unit.as_str(), value))) {
+                Some((unit, Some(currency), _)) => (unit.end(), TokenType::Money(value, currency), Some((unit, UiTokenType::Symbol1))),
+                Some((unit, None, Some(duration))) => (unit.end(), TokenType::Duration(duration), Some((unit, UiTokenType::Symbol2))),
+                Some((unit, None, None)) if is_unit(config, &tokinizer.language, unit.as_str()) => (fraction_match.end(), TokenType::Number(value, NumberType::Decimal), None),
+                _ => (fraction_match.end(), TokenType::Fraction(fraction.get_numerator(), fraction.get_denominator()), None)
+            };
+
+            if tokinizer.add_token_location(fraction_match.start(), end, Some(token_type), fraction_match.as_str().to_string()) {
+                tokinizer.add_uitoken_from_match(Some(fraction_match), UiTokenType::Number);
+                if let Some((unit, ui_type)) = unit_match {
+                    tokinizer.add_uitoken_from_match(Some(unit), ui_type);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn fraction_test() {
+    use core::ops::Deref;
+    use alloc::string::ToString;
+    use crate::tokinizer::regex_tokinizer;
+    use crate::tokinizer::test::setup_tokinizer;
+    use crate::config::SmartCalcConfig;
+    use crate::session::Session;
+    let mut session = Session::new();
+    let config = SmartCalcConfig::default();
+    let mut tokinizer_mut = setup_tokinizer("3/4 1 1/2 6/4 12/02/2022 2 5/3".to_string(), &mut session, &config);
+
+    regex_tokinizer(&mut tokinizer_mut);
+    let tokens = &tokinizer_mut.token_infos;
+
+    assert_eq!(tokens.len(), 10);
+    assert_eq!(tokens[0].start, 0);
+    assert_eq!(tokens[0].end, 3);
+    assert_eq!(tokens[0].token_type.borrow().deref(), &Some(TokenType::Fraction(3, 4)));
+
+    assert_eq!(tokens[1].start, 4);
+    assert_eq!(tokens[1].end, 9);
+    assert_eq!(tokens[1].token_type.borrow().deref(), &Some(TokenType::Fraction(3, 2)));
+
+    assert_eq!(tokens[2].start, 10);
+    assert_eq!(tokens[2].end, 13);
+    assert_eq!(tokens[2].token_type.borrow().deref(), &Some(TokenType::Fraction(3, 2)));
+
+    assert_eq!(tokens[3].token_type.borrow().deref(), &Some(TokenType::Number(12.0, NumberType::Decimal)));
+    assert_eq!(tokens[8].token_type.borrow().deref(), &Some(TokenType::Number(2.0, NumberType::Decimal)));
+    assert_eq!(tokens[9].token_type.borrow().deref(), &Some(TokenType::Fraction(5, 3)));
+}
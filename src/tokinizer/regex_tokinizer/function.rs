@@ -0,0 +1,51 @@
+/*
+ * smartcalc v1.0.8
+ * Copyright (c) Erhan BARIS (Ruslan Ognyanov Asenov)
+ * Licensed under the GNU General Public License v2.0.
+ */
+
+use alloc::borrow::ToOwned;
+use regex::Regex;
+use crate::config::SmartCalcConfig;
+use crate::types::TokenType;
+use crate::tokinizer::Tokinizer;
+use crate::token::ui_token::UiTokenType;
+
+pub fn function_regex_parser(config: &SmartCalcConfig, tokinizer: &mut Tokinizer, group_item: &[Regex]) {
+    for re in group_item.iter() {
+        for capture in re.captures_iter(&tokinizer.data.to_owned()) {
+            let name = capture.name("FUNCTION").unwrap().as_str().to_lowercase();
+
+            /* Only registered functions are tokinized, other words are still text */
+            if config.functions.contains_key(&name) && tokinizer.add_token_from_match(&capture.name("FUNCTION"), Some(TokenType::Function(name))) {
+                tokinizer.add_uitoken_from_match(capture.name("FUNCTION"), UiTokenType::Symbol2);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn function_test_1() {
+    use core::ops::Deref;
+    use alloc::string::ToString;
+    use crate::tokinizer::regex_tokinizer;
+    use crate::tokinizer::test::setup_tokinizer;
+    use crate::session::Session;
+    let mut session = Session::new();
+    let config = SmartCalcConfig::default();
+    let mut tokinizer_mut = setup_tokinizer("sqrt(16) + log10 (100) + erhan(1)".to_string(), &mut session, &config);
+
+    regex_tokinizer(&mut tokinizer_mut);
+    let tokens = &tokinizer_mut.token_infos;
+
+    assert_eq!(tokens[0].start, 0);
+    assert_eq!(tokens[0].end, 4);
+    assert_eq!(tokens[0].token_type.borrow().deref(), &Some(TokenType::Function("sqrt".to_string())));
+
+    assert_eq!(tokens[5].start, 11);
+    assert_eq!(tokens[5].end, 16);
+    assert_eq!(tokens[5].token_type.borrow().deref(), &Some(TokenType::Function("log10".to_string())));
+
+    assert_eq!(tokens[10].token_type.borrow().deref(), &Some(TokenType::Text("erhan".to_string())));
+}
@@ -12,6 +12,7 @@ use regex::Regex;
 use lazy_static::*;
 
 mod number;
+mod fraction;
 mod operator;
 mod text;
 mod whitespace;
@@ -22,12 +23,17 @@ mod time;
 mod money;
 mod comment;
 mod month;
+mod number_word;
+mod number_notation;
 mod timezone;
+mod function;
+mod dice;
 
 use crate::SmartCalcConfig;
 
 pub use self::time::time_regex_parser;
 pub use self::number::number_regex_parser;
+pub use self::fraction::fraction_regex_parser;
 pub use self::percent::percent_regex_parser;
 pub use self::money::money_regex_parser;
 pub use self::text::text_regex_parser;
@@ -37,7 +43,11 @@ pub use self::whitespace::whitespace_regex_parser;
 pub use self::comment::comment_regex_parser;
 pub use self::timezone::timezone_regex_parser;
 pub use self::month::month_parser;
+pub use self::number_word::number_word_parser;
+pub use self::number_notation::number_notation_parser;
 pub use self::operator::operator_regex_parser;
+pub use self::function::function_regex_parser;
+pub use self::dice::dice_regex_parser;
 
 use super::Tokinizer;
 
@@ -51,6 +61,9 @@ lazy_static! {
         let m = vec![
         ("comment",    comment_regex_parser    as RegexParser),
         ("field",      field_regex_parser      as RegexParser),
+        ("function",   function_regex_parser   as RegexParser),
+        ("fraction",   fraction_regex_parser   as RegexParser),
+        ("dice",       dice_regex_parser       as RegexParser),
         ("money",      money_regex_parser      as RegexParser),
         ("atom",       atom_regex_parser       as RegexParser),
         ("percent",    percent_regex_parser    as RegexParser),
@@ -90,3 +103,17 @@ pub fn language_tokinizer(tokinizer: &mut Tokinizer) {
 
     tokinizer.cleanup_token_infos();
 }
+
+/* Numbers written with words, added before the regex parsers so the words are not parsed as text */
+pub fn number_word_tokinizer(tokinizer: &mut Tokinizer) {
+    let data = tokinizer.data.to_string();
+    number_word_parser(tokinizer.config, tokinizer, &data);
+    tokinizer.cleanup_token_infos();
+}
+
+/* Magnitude words like "million" and "dozen", parsed before the number words so "5 thousand" stays as one number */
+pub fn number_notation_tokinizer(tokinizer: &mut Tokinizer) {
+    let data = tokinizer.data.to_string();
+    number_notation_parser(tokinizer.config, tokinizer, &data);
+    tokinizer.cleanup_token_infos();
+}
@@ -8,26 +8,27 @@ use alloc::string::ToString;
 use regex::Regex;
 use alloc::borrow::ToOwned;
 use crate::config::SmartCalcConfig;
-use crate::tokinizer::{Tokinizer, read_currency};
+use crate::tokinizer::{Tokinizer, resolve_currency};
 use crate::types::{TokenType};
+use crate::error::SmartCalcError;
 use crate::token::ui_token::{UiTokenType};
 
 pub fn money_regex_parser(config: &SmartCalcConfig, tokinizer: &mut Tokinizer, group_item: &[Regex]) {
     for re in group_item.iter() {
         for capture in re.captures_iter(&tokinizer.data.to_owned()) {
-            /* Check price value */
-            let price = match capture.name("PRICE").unwrap().as_str().replace(&config.thousand_separator[..], "").replace(&config.decimal_seperator[..], ".").parse::<f64>() {
+            /* Check price value, exponent is parsed with the price */
+            let mut price_text = capture.name("PRICE").unwrap().as_str().replace(&config.thousand_separator[..], "").replace(&config.decimal_seperator[..], ".");
+            if let Some(exponent) = capture.name("EXPONENT") {
+                price_text.push_str(exponent.as_str());
+            }
+
+            let price = match price_text.parse::<f64>() {
+                Ok(price) if !price.is_finite() => {
+                    tokinizer.session.set_line_error(SmartCalcError::Overflow);
+                    continue
+                },
                 Ok(price) => match capture.name("NOTATION") {
-                    Some(notation) => price * match notation.as_str() {
-                        "k" | "K" => 1_000.0,
-                        "M" => 1_000_000.0,
-                        "G" => 1_000_000_000.0,
-                        "T" => 1_000_000_000_000.0,
-                        "P" => 1_000_000_000_000_000.0,
-                        "Z" => 1_000_000_000_000_000_000.0,
-                        "Y" => 1_000_000_000_000_000_000_000.0,
-                        _ => 1.0
-                    },
+                    Some(notation) => price * config.get_number_notation(&tokinizer.language, notation.as_str()).unwrap_or(1.0),
                     _ => price
                 },
                 _ => continue
@@ -39,20 +40,21 @@ pub fn money_regex_parser(config: &SmartCalcConfig, tokinizer: &mut Tokinizer, g
                 _ => continue
             };
 
-            let currency = match read_currency(config, currency) {
+            let currency = match resolve_currency(config, tokinizer.session, currency) {
                 Some(real_currency) => real_currency,
                 _ => continue
             };
             
             let end = match capture.name("NOTATION") {
                 Some(notation) => notation.end(),
-                _ => capture.name("CURRENCY").unwrap().end()
+                _ => capture.get(0).unwrap().end()
             };
 
             if tokinizer.add_token_location(capture.get(0).unwrap().start(), end, Some(TokenType::Money(price, currency.clone())), capture.name("PRICE").unwrap().as_str().to_string()) {
                 tokinizer.add_uitoken_from_match(capture.name("PRICE"), UiTokenType::Number);
                 tokinizer.add_uitoken_from_match(capture.name("CURRENCY"), UiTokenType::Symbol1);
                 tokinizer.add_uitoken_from_match(capture.name("NOTATION"), UiTokenType::Symbol2);
+                tokinizer.add_uitoken_from_match(capture.name("EXPONENT"), UiTokenType::Symbol2);
             }
         }
     }
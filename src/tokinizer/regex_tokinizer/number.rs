@@ -6,11 +6,20 @@
 
 use alloc::string::ToString;
 use alloc::borrow::ToOwned;
+use crate::compiler::OperationType;
 use crate::config::SmartCalcConfig;
 use crate::types::*;
 use crate::tokinizer::{Tokinizer};
 use regex::Regex;
 use crate::token::ui_token::{UiTokenType};
+use crate::tools::{do_math, parse_number};
+use crate::error::SmartCalcError;
+
+const INTEGER_GROUPS: [(&str, &str, u32, NumberType); 3] = [
+    ("BINARY", "BINARY_FULL", 2, NumberType::Binary),
+    ("HEX", "HEX_FULL", 16, NumberType::Hexadecimal),
+    ("OCTAL", "OCTAL_FULL", 8, NumberType::Octal)
+];
 
 pub fn number_regex_parser(config: &SmartCalcConfig, tokinizer: &mut Tokinizer, group_item: &[Regex]) {
     for re in group_item.iter() {
@@ -20,54 +29,55 @@ pub fn number_regex_parser(config: &SmartCalcConfig, tokinizer: &mut Tokinizer,
             let mut notation_match = None;
 
             /* Check price value */
-            let mut number = 0.0;
-            let mut number_type = NumberType::Decimal;
-
-            if let Some(binary) = capture.name("BINARY") {
-                parse_end = binary.end();
-                number = i64::from_str_radix(binary.as_str(), 2).unwrap() as f64;
-                number_type = NumberType::Binary;
-                number_match = capture.name("BINARY_FULL");
-            }
-            else if let Some(hex) = capture.name("HEX") { 
-                parse_end = hex.end();
-                number = i64::from_str_radix(hex.as_str(), 16).unwrap() as f64;
-                number_type = NumberType::Hexadecimal;
-                number_match = capture.name("HEX_FULL");
-            }
-            else if let Some(octal) = capture.name("OCTAL") { 
-                parse_end = octal.end();
-                number = i64::from_str_radix(octal.as_str(), 8).unwrap() as f64;
-                number_type = NumberType::Octal;
-                number_match = capture.name("OCTAL_FULL");
+            let mut token_type = None;
+
+            for (group, full_group, radix, number_type) in INTEGER_GROUPS.iter() {
+                if let Some(integer) = capture.name(group) {
+                    parse_end = integer.end();
+                    number_match = capture.name(full_group);
+                    token_type = match i128::from_str_radix(integer.as_str(), *radix) {
+                        Ok(number) => Some(TokenType::Integer(number, *number_type)),
+
+                        /* Too many digits for an integer */
+                        Err(_) => {
+                            tokinizer.session.set_line_error(SmartCalcError::Overflow);
+                            None
+                        }
+                    };
+                    break;
+                }
             }
-            else if let Some(decimal) = capture.name("DECIMAL") {
+
+            if let Some(decimal) = capture.name("DECIMAL") {
                 parse_end = decimal.end();
-                number = match decimal.as_str().replace(&config.thousand_separator[..], "").replace(&config.decimal_seperator[..], ".").parse::<f64>() {
-                    Ok(num) => {
-                        number_match = Some(decimal);
-                        match capture.name("NOTATION") {
-                            Some(notation) => {
-                                notation_match = Some(notation);
-                                num * match notation.as_str() {
-                                    "k" | "K" => 1_000.0,
-                                    "M" => 1_000_000.0,
-                                    "G" => 1_000_000_000.0,
-                                    "T" => 1_000_000_000_000.0,
-                                    "P" => 1_000_000_000_000_000.0,
-                                    "Z" => 1_000_000_000_000_000_000.0,
-                                    "Y" => 1_000_000_000_000_000_000_000.0,
-                                    _ => 1.0
-                                }
-                            },
-                            _ => num
-                        }
-                    },
-                    _ => continue
-                };
+                let mut decimal_text = decimal.as_str().replace(&config.thousand_separator[..], "").replace(&config.decimal_seperator[..], ".");
+
+                /* Exponent is parsed with the number, 1,5e-3 */
+                if let Some(exponent) = capture.name("EXPONENT") {
+                    parse_end = exponent.end();
+                    notation_match = Some(exponent);
+                    decimal_text.push_str(exponent.as_str());
+                }
+
+                /* Too big for f64, 1e400 */
+                if matches!(parse_number(&decimal_text), Some(num) if !num.is_finite()) {
+                    tokinizer.session.set_line_error(SmartCalcError::Overflow);
+                    continue;
+                }
+
+                token_type = parse_number(&decimal_text).map(|num| {
+                    number_match = Some(decimal);
+                    match capture.name("NOTATION") {
+                        Some(notation) => {
+                            notation_match = Some(notation);
+                            TokenType::Number(do_math(num, config.get_number_notation(&tokinizer.language, notation.as_str()).unwrap_or(1.0), OperationType::Mul), NumberType::Decimal)
+                        },
+                        _ => TokenType::Number(num, NumberType::Decimal)
+                    }
+                });
             }
 
-            if tokinizer.add_token_location(capture.get(0).unwrap().start(), parse_end, Some(TokenType::Number(number, number_type)), capture.get(0).unwrap().as_str().to_string()) {
+            if token_type.is_some() && tokinizer.add_token_location(capture.get(0).unwrap().start(), parse_end, token_type, capture.get(0).unwrap().as_str().to_string()) {
                 tokinizer.add_uitoken_from_match(number_match, UiTokenType::Number);
                 tokinizer.add_uitoken_from_match(notation_match, UiTokenType::Symbol2);
             }
@@ -75,7 +85,6 @@ pub fn number_regex_parser(config: &SmartCalcConfig, tokinizer: &mut Tokinizer,
     }
 }
 
-
 #[cfg(test)]
 #[test]
 fn number_test_1() {
@@ -128,27 +137,27 @@ fn number_test_2() {
     assert_eq!(tokens.len(), 6);
     assert_eq!(tokens[0].start, 0);
     assert_eq!(tokens[0].end, 5);
-    assert_eq!(tokens[0].token_type.borrow().deref(), &Some(TokenType::Number(256.0, NumberType::Hexadecimal)));
+    assert_eq!(tokens[0].token_type.borrow().deref(), &Some(TokenType::Integer(256, NumberType::Hexadecimal)));
     
     assert_eq!(tokens[1].start, 6);
     assert_eq!(tokens[1].end, 11);
-    assert_eq!(tokens[1].token_type.borrow().deref(), &Some(TokenType::Number(256.0, NumberType::Hexadecimal)));
+    assert_eq!(tokens[1].token_type.borrow().deref(), &Some(TokenType::Integer(256, NumberType::Hexadecimal)));
     
     assert_eq!(tokens[2].start, 12);
     assert_eq!(tokens[2].end, 15);
-    assert_eq!(tokens[2].token_type.borrow().deref(), &Some(TokenType::Number(1.0, NumberType::Hexadecimal)));
+    assert_eq!(tokens[2].token_type.borrow().deref(), &Some(TokenType::Integer(1, NumberType::Hexadecimal)));
     
     assert_eq!(tokens[3].start, 16);
     assert_eq!(tokens[3].end, 19);
-    assert_eq!(tokens[3].token_type.borrow().deref(), &Some(TokenType::Number(1.0, NumberType::Hexadecimal)));
+    assert_eq!(tokens[3].token_type.borrow().deref(), &Some(TokenType::Integer(1, NumberType::Hexadecimal)));
     
     assert_eq!(tokens[4].start, 20);
     assert_eq!(tokens[4].end, 23);
-    assert_eq!(tokens[4].token_type.borrow().deref(), &Some(TokenType::Number(0.0, NumberType::Hexadecimal)));
+    assert_eq!(tokens[4].token_type.borrow().deref(), &Some(TokenType::Integer(0, NumberType::Hexadecimal)));
     
     assert_eq!(tokens[5].start, 24);
     assert_eq!(tokens[5].end, 27);
-    assert_eq!(tokens[5].token_type.borrow().deref(), &Some(TokenType::Number(0.0, NumberType::Hexadecimal)));
+    assert_eq!(tokens[5].token_type.borrow().deref(), &Some(TokenType::Integer(0, NumberType::Hexadecimal)));
 }
 
 #[cfg(test)]
@@ -169,27 +178,27 @@ fn number_test_3() {
     assert_eq!(tokens.len(), 6);
     assert_eq!(tokens[0].start, 0);
     assert_eq!(tokens[0].end, 5);
-    assert_eq!(tokens[0].token_type.borrow().deref(), &Some(TokenType::Number(4.0, NumberType::Binary)));
+    assert_eq!(tokens[0].token_type.borrow().deref(), &Some(TokenType::Integer(4, NumberType::Binary)));
     
     assert_eq!(tokens[1].start, 6);
     assert_eq!(tokens[1].end, 11);
-    assert_eq!(tokens[1].token_type.borrow().deref(), &Some(TokenType::Number(4.0, NumberType::Binary)));
+    assert_eq!(tokens[1].token_type.borrow().deref(), &Some(TokenType::Integer(4, NumberType::Binary)));
     
     assert_eq!(tokens[2].start, 12);
     assert_eq!(tokens[2].end, 15);
-    assert_eq!(tokens[2].token_type.borrow().deref(), &Some(TokenType::Number(1.0, NumberType::Binary)));
+    assert_eq!(tokens[2].token_type.borrow().deref(), &Some(TokenType::Integer(1, NumberType::Binary)));
     
     assert_eq!(tokens[3].start, 16);
     assert_eq!(tokens[3].end, 19);
-    assert_eq!(tokens[3].token_type.borrow().deref(), &Some(TokenType::Number(1.0, NumberType::Binary)));
+    assert_eq!(tokens[3].token_type.borrow().deref(), &Some(TokenType::Integer(1, NumberType::Binary)));
     
     assert_eq!(tokens[4].start, 20);
     assert_eq!(tokens[4].end, 23);
-    assert_eq!(tokens[4].token_type.borrow().deref(), &Some(TokenType::Number(0.0, NumberType::Binary)));
+    assert_eq!(tokens[4].token_type.borrow().deref(), &Some(TokenType::Integer(0, NumberType::Binary)));
     
     assert_eq!(tokens[5].start, 24);
     assert_eq!(tokens[5].end, 27);
-    assert_eq!(tokens[5].token_type.borrow().deref(), &Some(TokenType::Number(0.0, NumberType::Binary)));
+    assert_eq!(tokens[5].token_type.borrow().deref(), &Some(TokenType::Integer(0, NumberType::Binary)));
 }
 
 
@@ -211,25 +220,25 @@ fn number_test_4() {
     assert_eq!(tokens.len(), 6);
     assert_eq!(tokens[0].start, 0);
     assert_eq!(tokens[0].end, 5);
-    assert_eq!(tokens[0].token_type.borrow().deref(), &Some(TokenType::Number(64.0, NumberType::Octal)));
+    assert_eq!(tokens[0].token_type.borrow().deref(), &Some(TokenType::Integer(64, NumberType::Octal)));
     
     assert_eq!(tokens[1].start, 6);
     assert_eq!(tokens[1].end, 11);
-    assert_eq!(tokens[1].token_type.borrow().deref(), &Some(TokenType::Number(64.0, NumberType::Octal)));
+    assert_eq!(tokens[1].token_type.borrow().deref(), &Some(TokenType::Integer(64, NumberType::Octal)));
     
     assert_eq!(tokens[2].start, 12);
     assert_eq!(tokens[2].end, 15);
-    assert_eq!(tokens[2].token_type.borrow().deref(), &Some(TokenType::Number(1.0, NumberType::Octal)));
+    assert_eq!(tokens[2].token_type.borrow().deref(), &Some(TokenType::Integer(1, NumberType::Octal)));
     
     assert_eq!(tokens[3].start, 16);
     assert_eq!(tokens[3].end, 19);
-    assert_eq!(tokens[3].token_type.borrow().deref(), &Some(TokenType::Number(1.0, NumberType::Octal)));
+    assert_eq!(tokens[3].token_type.borrow().deref(), &Some(TokenType::Integer(1, NumberType::Octal)));
     
     assert_eq!(tokens[4].start, 20);
     assert_eq!(tokens[4].end, 23);
-    assert_eq!(tokens[4].token_type.borrow().deref(), &Some(TokenType::Number(0.0, NumberType::Octal)));
+    assert_eq!(tokens[4].token_type.borrow().deref(), &Some(TokenType::Integer(0, NumberType::Octal)));
     
     assert_eq!(tokens[5].start, 24);
     assert_eq!(tokens[5].end, 27);
-    assert_eq!(tokens[5].token_type.borrow().deref(), &Some(TokenType::Number(0.0, NumberType::Octal)));
+    assert_eq!(tokens[5].token_type.borrow().deref(), &Some(TokenType::Integer(0, NumberType::Octal)));
 }
@@ -0,0 +1,79 @@
+/*
+ * smartcalc v1.0.8
+ * Copyright (c) Erhan BARIS (Ruslan Ognyanov Asenov)
+ * Licensed under the GNU General Public License v2.0.
+ */
+
+use alloc::string::ToString;
+use crate::config::SmartCalcConfig;
+use crate::tokinizer::{Tokinizer, resolve_currency};
+use crate::token::ui_token::UiTokenType;
+use crate::types::{TokenType, NumberType};
+use crate::tools::parse_number;
+
+pub fn number_notation_parser(config: &SmartCalcConfig, tokinizer: &mut Tokinizer, data: &str) {
+    let re = match config.number_notation_regex.get(&tokinizer.language) {
+        Some(re) => re,
+        None => return
+    };
+
+    for capture in re.captures_iter(data) {
+        let number_match = capture.name("NUMBER").unwrap();
+        let notation_match = capture.name("NOTATION").unwrap();
+
+        let number = match parse_number(&number_match.as_str().replace(&config.thousand_separator[..], "").replace(&config.decimal_seperator[..], ".")) {
+            Some(number) => number,
+            None => continue
+        };
+
+        let number = match config.get_number_notation(&tokinizer.language, notation_match.as_str()) {
+            Some(multiplier) => number * multiplier,
+            None => continue
+        };
+
+        /* "$2,5 million", "2,5 million usd", "%5 million" and "5 million%" */
+        let currency_prefix = capture.name("CURRENCY_PREFIX").and_then(|currency| resolve_currency(config, tokinizer.session, currency.as_str()).map(|info| (currency, info)));
+        let currency_suffix = capture.name("CURRENCY").and_then(|currency| resolve_currency(config, tokinizer.session, currency.as_str()).map(|info| (currency, info)));
+        let percent = capture.name("PERCENT_PREFIX").or_else(|| capture.name("PERCENT"));
+        let start = capture.get(0).unwrap().start();
+
+        let (token_type, end, symbol_match) = match (currency_prefix, percent, currency_suffix) {
+            (Some((currency_match, currency)), _, _) => (TokenType::Money(number, currency), notation_match.end(), Some((currency_match, UiTokenType::Symbol1))),
+            (None, Some(percent_match), _) => (TokenType::Percent(number), notation_match.end().max(percent_match.end()), Some((percent_match, UiTokenType::Symbol2))),
+            (None, None, Some((currency_match, currency))) => (TokenType::Money(number, currency), currency_match.end(), Some((currency_match, UiTokenType::Symbol1))),
+            (None, None, None) => (TokenType::Number(number, NumberType::Decimal), notation_match.end(), None)
+        };
+
+        if tokinizer.add_token_location(start, end, Some(token_type), data[start..end].to_string()) {
+            tokinizer.add_uitoken_from_match(Some(number_match), UiTokenType::Number);
+            tokinizer.add_uitoken_from_match(Some(notation_match), UiTokenType::Symbol2);
+            if let Some((symbol_match, ui_token_type)) = symbol_match {
+                tokinizer.add_uitoken_from_match(Some(symbol_match), ui_token_type);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn number_notation_test() {
+    use core::ops::Deref;
+    use crate::tokinizer::number_notation_tokinizer;
+    use crate::tokinizer::test::setup_tokinizer;
+    use crate::session::Session;
+    let mut session = Session::new();
+    let config = SmartCalcConfig::default();
+    let mut tokinizer_mut = setup_tokinizer("2,5 million usd + 3 Dozen + $1 billion + %5 thousand + 2 lakh".to_string(), &mut session, &config);
+
+    number_notation_tokinizer(&mut tokinizer_mut);
+    let tokens = &tokinizer_mut.token_infos;
+
+    assert_eq!(tokens.len(), 5);
+    assert_eq!(tokens[0].start, 0);
+    assert_eq!(tokens[0].end, 15);
+    assert_eq!(tokens[0].token_type.borrow().deref(), &Some(TokenType::Money(2_500_000.0, config.get_currency("usd".to_string()).unwrap())));
+    assert_eq!(tokens[1].token_type.borrow().deref(), &Some(TokenType::Number(36.0, NumberType::Decimal)));
+    assert_eq!(tokens[2].token_type.borrow().deref(), &Some(TokenType::Money(1_000_000_000.0, config.get_currency("usd".to_string()).unwrap())));
+    assert_eq!(tokens[3].token_type.borrow().deref(), &Some(TokenType::Percent(5_000.0)));
+    assert_eq!(tokens[4].token_type.borrow().deref(), &Some(TokenType::Number(200_000.0, NumberType::Decimal)));
+}
@@ -0,0 +1,195 @@
+/*
+ * smartcalc v1.0.8
+ * Copyright (c) Erhan BARIS (Ruslan Ognyanov Asenov)
+ * Licensed under the GNU General Public License v2.0.
+ */
+
+use alloc::string::String;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use crate::config::SmartCalcConfig;
+use crate::constants::JsonNumberWords;
+use crate::tokinizer::{Tokinizer, resolve_currency};
+use crate::token::ui_token::UiTokenType;
+use crate::types::{TokenType, NumberType};
+
+#[derive(PartialEq)]
+enum WordType {
+    Unit,
+    Tens,
+    Hundred,
+    Scale
+}
+
+fn get_word(number_words: &JsonNumberWords, word: &str) -> Option<(WordType, f64)> {
+    if let Some(index) = number_words.units.iter().position(|item| item == word) {
+        return Some((WordType::Unit, index as f64));
+    }
+
+    if let Some(index) = number_words.tens.iter().position(|item| !item.is_empty() && item == word) {
+        return Some((WordType::Tens, index as f64 * 10.0));
+    }
+
+    if number_words.hundred == word {
+        return Some((WordType::Hundred, 100.0));
+    }
+
+    number_words.scales.iter().position(|item| item == word).map(|index| (WordType::Scale, 1000_f64.powi(index as i32 + 1)))
+}
+
+/* "one hundred and twenty five" is 125, words that can not follow each other like "five six" are not a number */
+pub fn parse_number_words(number_words: &JsonNumberWords, text: &str) -> Option<f64> {
+    let mut total = 0.0;
+    let mut current = 0.0;
+    let mut previous: Option<WordType> = None;
+    let mut last_scale = f64::MAX;
+
+    for word in text.split([' ', '-']).filter(|word| !word.is_empty()) {
+        if number_words.connectors.iter().any(|connector| connector == word) {
+            continue;
+        }
+
+        let (word_type, value) = get_word(number_words, word)?;
+        match word_type {
+            WordType::Unit | WordType::Tens => {
+                /* Only "twenty" "five" like pairs can follow each other */
+                let is_valid = match previous {
+                    Some(WordType::Tens) => word_type == WordType::Unit && value < 10.0,
+                    Some(WordType::Unit) => false,
+                    _ => true
+                };
+
+                if !is_valid || (current % 100.0) + value >= 100.0 {
+                    return None;
+                }
+
+                current += value;
+            },
+            WordType::Hundred => {
+                if current >= 100.0 {
+                    return None;
+                }
+
+                current = if current == 0.0 { 1.0 } else { current } * value;
+            },
+            WordType::Scale => {
+                if value >= last_scale {
+                    return None;
+                }
+
+                total += if current == 0.0 { 1.0 } else { current } * value;
+                current = 0.0;
+                last_scale = value;
+            }
+        };
+
+        previous = Some(word_type);
+    }
+
+    previous.map(|_| total + current)
+}
+
+/* Lowercase text and the position in the original text of every lowercase byte. Lowercase of a letter can have
+   a different length, Turkish "İ" is "i" and "I" is "ı" */
+fn lowercase_with_positions(language: &str, data: &str) -> (String, Vec<usize>) {
+    let mut lowercase = String::with_capacity(data.len());
+    let mut positions = Vec::with_capacity(data.len() + 1);
+
+    for (index, ch) in data.char_indices() {
+        let start = lowercase.len();
+        match (language, ch) {
+            ("tr", 'İ') => lowercase.push('i'),
+            ("tr", 'I') => lowercase.push('ı'),
+            _ => lowercase.extend(ch.to_lowercase())
+        };
+        positions.resize(positions.len() + lowercase.len() - start, index);
+    }
+
+    positions.push(data.len());
+    (lowercase, positions)
+}
+
+pub fn number_word_parser(config: &SmartCalcConfig, tokinizer: &mut Tokinizer, data: &str) {
+    let (re, number_words) = match (config.number_word_regex.get(&tokinizer.language), config.json_data.languages.get(&tokinizer.language)) {
+        (Some(re), Some(language)) => (re, &language.number_words),
+        _ => return
+    };
+
+    let original = data;
+    let (lowercase_data, positions) = lowercase_with_positions(&tokinizer.language, original);
+    let data = lowercase_data.as_str();
+
+    /* Words in the comments are not numbers */
+    let data = match data.find('#') {
+        Some(position) => &data[..position],
+        None => data
+    };
+
+    for capture in re.captures_iter(data) {
+        let number = match capture.name("WORDS").and_then(|words| parse_number_words(number_words, words.as_str())) {
+            Some(number) => number,
+            None => continue
+        };
+
+        let words = capture.name("WORDS").unwrap();
+        let (start, end) = (positions[words.start()], positions[words.end()]);
+        let currency = capture.name("CURRENCY").and_then(|currency| resolve_currency(config, tokinizer.session, currency.as_str()).map(|info| (currency, info)));
+
+        match currency {
+            Some((currency_match, currency)) => {
+                let (currency_start, currency_end) = (positions[currency_match.start()], positions[currency_match.end()]);
+                if tokinizer.add_token_location(start, currency_end, Some(TokenType::Money(number, currency)), original[start..end].to_string()) {
+                    tokinizer.add_uitoken_location(start, end, UiTokenType::Number);
+                    tokinizer.add_uitoken_location(currency_start, currency_end, UiTokenType::Symbol1);
+                }
+            },
+            None => {
+                if tokinizer.add_token_location(start, end, Some(TokenType::Number(number, NumberType::Decimal)), original[start..end].to_string()) {
+                    tokinizer.add_uitoken_location(start, end, UiTokenType::Number);
+                }
+            }
+        };
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn parse_number_words_test() {
+    let config = SmartCalcConfig::default();
+    let english = &config.json_data.languages.get("en").unwrap().number_words;
+    assert_eq!(parse_number_words(english, "one hundred and twenty five"), Some(125.0));
+    assert_eq!(parse_number_words(english, "twenty-one"), Some(21.0));
+    assert_eq!(parse_number_words(english, "one thousand two hundred fifty"), Some(1250.0));
+    assert_eq!(parse_number_words(english, "two million three hundred thousand"), Some(2_300_000.0));
+    assert_eq!(parse_number_words(english, "hundred"), Some(100.0));
+    assert_eq!(parse_number_words(english, "zero"), Some(0.0));
+    assert_eq!(parse_number_words(english, "five six"), None);
+    assert_eq!(parse_number_words(english, "thousand million"), None);
+
+    let turkish = &config.json_data.languages.get("tr").unwrap().number_words;
+    assert_eq!(parse_number_words(turkish, "yüz yirmi beş"), Some(125.0));
+    assert_eq!(parse_number_words(turkish, "bin iki yüz elli"), Some(1250.0));
+    assert_eq!(parse_number_words(turkish, "on dokuz"), Some(19.0));
+    assert_eq!(parse_number_words(turkish, "iki milyon"), Some(2_000_000.0));
+}
+
+#[cfg(test)]
+#[test]
+fn number_word_test() {
+    use core::ops::Deref;
+    use crate::tokinizer::number_word_tokinizer;
+    use crate::tokinizer::test::setup_tokinizer;
+    use crate::session::Session;
+    let mut session = Session::new();
+    let config = SmartCalcConfig::default();
+    let mut tokinizer_mut = setup_tokinizer("one hundred and twenty five usd + three # four".to_string(), &mut session, &config);
+
+    number_word_tokinizer(&mut tokinizer_mut);
+    let tokens = &tokinizer_mut.token_infos;
+
+    assert_eq!(tokens.len(), 2);
+    assert_eq!(tokens[0].start, 0);
+    assert_eq!(tokens[0].end, 31);
+    assert_eq!(tokens[0].token_type.borrow().deref(), &Some(TokenType::Money(125.0, config.get_currency("usd".to_string()).unwrap())));
+    assert_eq!(tokens[1].token_type.borrow().deref(), &Some(TokenType::Number(3.0, NumberType::Decimal)));
+}
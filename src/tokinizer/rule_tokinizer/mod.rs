@@ -30,6 +30,7 @@ use self::rules::cleanup_rules::*;
 use self::rules::date_rules::*;
 use self::rules::duration_rules::*;
 use self::rules::dynamic_type_rules::*;
+use self::rules::rounding_rules::*;
 
 use super::TokenInfoStatus;
 use super::Tokinizer;
@@ -58,6 +59,8 @@ lazy_static! {
         m.insert("from_unixtime".to_string(),      from_unixtime as ExpressionFunc);
         
         m.insert("convert_money".to_string(),      convert_money as ExpressionFunc);
+        m.insert("convert_money_on".to_string(),   convert_money_on as ExpressionFunc);
+        m.insert("currency_display".to_string(),   currency_display as ExpressionFunc);
 
         m.insert("number_on".to_string(),          number_on as ExpressionFunc);
         m.insert("number_of".to_string(),          number_of as ExpressionFunc);
@@ -68,6 +71,7 @@ lazy_static! {
         m.insert("as_duration".to_string(),        as_duration as ExpressionFunc);
         m.insert("to_duration".to_string(),        to_duration as ExpressionFunc);
         m.insert("at_date".to_string(),            at_date as ExpressionFunc);
+        m.insert("small_date".to_string(),         small_date as ExpressionFunc);
         
         m.insert("combine_durations".to_string(),  combine_durations as ExpressionFunc);
 
@@ -75,6 +79,10 @@ lazy_static! {
         m.insert("find_total_from_percent".to_string(), find_total_from_percent as ExpressionFunc);
 
         m.insert("number_type_convert".to_string(),     number_type_convert as ExpressionFunc);
+        m.insert("fraction_convert".to_string(),        fraction_convert as ExpressionFunc);
+        m.insert("significant_figures_convert".to_string(), significant_figures_convert as ExpressionFunc);
+        m.insert("number_to_words".to_string(),         number_to_words as ExpressionFunc);
+        m.insert("round_value".to_string(),             round_value as ExpressionFunc);
         
         m.insert("dynamic_type_convert".to_string(),     dynamic_type_convert as ExpressionFunc);
 
@@ -216,7 +224,10 @@ pub fn rule_tokinizer(tokinizer: &mut Tokinizer) {
                                     for (_, token) in fields.iter() {
                                         let ui_token = match token.token_type.borrow().as_ref() {
                                             Some(TokenType::Number(_, _)) => UiTokenType::Number,
+                                            Some(TokenType::Integer(_, _)) => UiTokenType::Number,
+                                            Some(TokenType::Fraction(_, _)) => UiTokenType::Number,
                                             Some(TokenType::Money(_, _)) => UiTokenType::Number,
+                                            Some(TokenType::DisplayMoney(_, _, _)) => UiTokenType::Number,
                                             Some(TokenType::Date(_, _)) => UiTokenType::DateTime,
                                             Some(TokenType::Time(_, _)) => UiTokenType::DateTime,
                                             Some(TokenType::DateTime(_, _)) => UiTokenType::DateTime,
@@ -20,8 +20,10 @@ pub fn division_cleanup(_: &SmartCalcConfig, _: &Tokinizer, fields: &BTreeMap<St
         return match &fields.get(&"data".to_string()).unwrap().token_type.borrow().deref()  {
             Some(token) => match &token {
                 TokenType::Number(number, number_type) => Ok(TokenType::Number(*number, *number_type)),
+                TokenType::Integer(number, number_type) => Ok(TokenType::Integer(*number, *number_type)),
                 TokenType::Percent(percent) => Ok(TokenType::Percent(*percent)),
                 TokenType::Money(price, currency) => Ok(TokenType::Money(*price, currency.clone())),
+                TokenType::DisplayMoney(price, currency, display) => Ok(TokenType::DisplayMoney(*price, currency.clone(), *display)),
                 TokenType::Variable(variable) => {
                     match variable.data.borrow().deref().deref() {
                         SmartCalcAstType::Item(item) => Ok(item.as_token_type()),
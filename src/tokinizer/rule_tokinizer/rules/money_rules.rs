@@ -9,21 +9,50 @@ use alloc::string::String;
 use alloc::string::ToString;
 use alloc::collections::btree_map::BTreeMap;
 
-use crate::config::SmartCalcConfig;
+use alloc::vec::Vec;
+use core::ops::Deref;
+
+use crate::config::{SmartCalcConfig, CurrencyDisplay};
+use crate::error::SmartCalcWarning;
 use crate::tokinizer::get_currency;
 use crate::tokinizer::get_money;
+use crate::tokinizer::get_date;
+use crate::tokinizer::get_text;
 use crate::{tokinizer::Tokinizer, types::{TokenType}};
-use crate::tokinizer::TokenInfo;
-use crate::tools::do_divition;
+use crate::tokinizer::{TokenInfo, TokenInfoStatus};
+use crate::tools::{do_divition, round_converted_price};
+
+/* "100 usd to eur on 1 Mar 2024" is converted after the date is parsed, the word after the currency
+   is checked so the conversion is not made with the current rate */
+fn has_history_word(config: &SmartCalcConfig, tokinizer: &Tokinizer, fields: &BTreeMap<String, Rc<TokenInfo>>) -> bool {
+    let words = match config.word_group.get(&tokinizer.language).and_then(|groups| groups.get("history_group")) {
+        Some(words) => words,
+        None => return false
+    };
+
+    let tokens = tokinizer.token_infos.iter().filter(|token| token.status.get() == TokenInfoStatus::Active).collect::<Vec<_>>();
+    let next_token = fields.get("currency")
+        .and_then(|currency| tokens.iter().position(|token| Rc::ptr_eq(token, currency)))
+        .and_then(|index| tokens.get(index + 1));
+
+    match next_token.map(|token| token.token_type.borrow()) {
+        Some(token_type) => matches!(token_type.deref(), Some(TokenType::Text(text)) if words.contains(&text.to_lowercase())),
+        None => false
+    }
+}
 
-pub fn convert_money(config: &SmartCalcConfig, _: &Tokinizer, fields: &BTreeMap<String, Rc<TokenInfo>>) -> core::result::Result<TokenType, String> {
+pub fn convert_money(config: &SmartCalcConfig, tokinizer: &Tokinizer, fields: &BTreeMap<String, Rc<TokenInfo>>) -> core::result::Result<TokenType, String> {
     if fields.contains_key("money") && fields.contains_key("currency") {
+        if has_history_word(config, tokinizer, fields) {
+            return Err("Money is converted with the rate of the date".to_string());
+        }
+
         let money = match get_money(config, "money", fields) {
             Some(money) => money,
             _ => return Err("Money information not valid".to_string())
         };
 
-        let to_currency = match get_currency(config, "currency", fields) {
+        let to_currency = match get_currency(config, tokinizer.session, "currency", fields) {
             Some(to_currency) => to_currency,
             _ => return Err("Currency information not valid".to_string())
         };
@@ -38,12 +67,72 @@ pub fn convert_money(config: &SmartCalcConfig, _: &Tokinizer, fields: &BTreeMap<
             _ => return Err("Currency information not valid".to_string())
         };
 
-        return Ok(TokenType::Money(calculated_price, to_currency));
+        if let Some(conversion) = config.get_currency_conversion(&money.get_currency(), &to_currency).filter(|conversion| conversion.from != conversion.to) {
+            tokinizer.session.add_currency_conversion(conversion);
+        }
+
+        return Ok(TokenType::Money(round_converted_price(config, calculated_price, &to_currency), to_currency));
     }
 
     Err("Money type not valid".to_string())
 }
 
+/* "10 usd as code", only the display of the value is changed */
+pub fn currency_display(config: &SmartCalcConfig, tokinizer: &Tokinizer, fields: &BTreeMap<String, Rc<TokenInfo>>) -> core::result::Result<TokenType, String> {
+    if fields.contains_key("money") && fields.contains_key("display") {
+        let money = match get_money(config, "money", fields) {
+            Some(money) => money,
+            _ => return Err("Money information not valid".to_string())
+        };
+
+        let display = match (fields.contains_key("narrow"), get_text("display", fields).unwrap_or_default().to_lowercase().as_str()) {
+            (true, "symbol") => CurrencyDisplay::NarrowSymbol,
+            (false, "symbol") => CurrencyDisplay::Symbol,
+            (false, "code") => CurrencyDisplay::Code,
+            (false, "name") => CurrencyDisplay::Name,
+            _ => return Err("Currency display not valid".to_string())
+        };
+
+        let has_name = config.json_data.languages.get(&tokinizer.language)
+            .is_some_and(|language| language.number_words.currencies.contains_key(&money.get_currency().code));
+
+        if display == CurrencyDisplay::Name && !has_name {
+            tokinizer.session.add_warning(SmartCalcWarning::MissingCurrencyName {
+                currency: money.get_currency().code.to_string(),
+                language: tokinizer.language.to_string()
+            });
+        }
+
+        return Ok(TokenType::DisplayMoney(money.get_price(), money.get_currency(), display));
+    }
+
+    Err("Money type not valid".to_string())
+}
+
+/* "100 usd to eur on 1 Mar 2024", rate of the nearest earlier date in the history is used */
+pub fn convert_money_on(config: &SmartCalcConfig, tokinizer: &Tokinizer, fields: &BTreeMap<String, Rc<TokenInfo>>) -> core::result::Result<TokenType, String> {
+    let money = get_money(config, "money", fields).ok_or_else(|| "Money information not valid".to_string())?;
+    let to_currency = get_currency(config, tokinizer.session, "currency", fields).ok_or_else(|| "Currency information not valid".to_string())?;
+    let (date, _) = get_date("date", fields).ok_or_else(|| "Date information not valid".to_string())?;
+
+    if money.get_currency() == to_currency {
+        return Ok(TokenType::Money(money.get_price(), to_currency));
+    }
+
+    match config.get_historical_conversion(&money.get_currency(), &to_currency, date) {
+        Ok(conversion) => {
+            let price = round_converted_price(config, money.get_price() * conversion.rate, &to_currency);
+            tokinizer.session.add_currency_conversion(conversion);
+            Ok(TokenType::Money(price, to_currency))
+        },
+        Err(error) => {
+            let message = error.to_string();
+            tokinizer.session.set_line_error(error);
+            Err(message)
+        }
+    }
+}
+
 #[cfg(test)]
 #[test]
 fn convert_money_1() {
@@ -55,7 +144,8 @@ fn convert_money_1() {
     let tokens = execute("10 usd as try".to_string());
 
     assert_eq!(tokens.len(), 4);
-    assert_eq!(tokens[0].token_type.borrow().deref(), &Some(TokenType::Money(70.727697572, conf.get_currency("try".to_string()).unwrap())));
+    let currency = conf.get_currency("try".to_string()).unwrap();
+    assert_eq!(tokens[0].token_type.borrow().deref(), &Some(TokenType::Money(round_converted_price(&conf, 70.727697572, &currency), currency)));
 
 }
 
@@ -71,7 +161,8 @@ fn convert_money_2() {
     let tokens = execute("10 usd try".to_string());
 
     assert_eq!(tokens.len(), 3);
-    assert_eq!(tokens[0].token_type.borrow().deref(), &Some(TokenType::Money(70.727697572, conf.get_currency("try".to_string()).unwrap())));
+    let currency = conf.get_currency("try".to_string()).unwrap();
+    assert_eq!(tokens[0].token_type.borrow().deref(), &Some(TokenType::Money(round_converted_price(&conf, 70.727697572, &currency), currency)));
 
 }
 
@@ -86,7 +177,8 @@ fn convert_money_3() {
     let tokens = execute("10 usd into try".to_string());
 
     assert_eq!(tokens.len(), 4);
-    assert_eq!(tokens[0].token_type.borrow().deref(), &Some(TokenType::Money(70.727697572, conf.get_currency("try".to_string()).unwrap())));
+    let currency = conf.get_currency("try".to_string()).unwrap();
+    assert_eq!(tokens[0].token_type.borrow().deref(), &Some(TokenType::Money(round_converted_price(&conf, 70.727697572, &currency), currency)));
 
 }
 
@@ -103,7 +195,8 @@ fn convert_money_4() {
 
     assert_eq!(tokens.len(), 5);
     assert_eq!(tokens[0].token_type.borrow().deref(), &Some(TokenType::Text("salary".to_string())));
-    assert_eq!(tokens[2].token_type.borrow().deref(), &Some(TokenType::Money(134.4772867837901, conf.get_currency("eur".to_string()).unwrap())));
+    let currency = conf.get_currency("eur".to_string()).unwrap();
+    assert_eq!(tokens[2].token_type.borrow().deref(), &Some(TokenType::Money(round_converted_price(&conf, 134.4772867837901, &currency), currency)));
 
 }
 
@@ -119,7 +212,8 @@ fn convert_money_5() {
     let conf = SmartCalcConfig::default();
     let tokens = execute("$9 in Euro".to_string());
 
-    assert_eq!(tokens[0].token_type.borrow().deref(), &Some(TokenType::Money(7.5106400733, conf.get_currency("eur".to_string()).unwrap())));
+    let currency = conf.get_currency("eur".to_string()).unwrap();
+    assert_eq!(tokens[0].token_type.borrow().deref(), &Some(TokenType::Money(round_converted_price(&conf, 7.5106400733, &currency), currency)));
 
 }
 
@@ -182,3 +276,34 @@ fn money_off_1() {
 
     assert_eq!(tokens[0].token_type.borrow().deref(), &Some(TokenType::Money(37.6, conf.get_currency("eur".to_string()).unwrap())));
 }
+
+#[cfg(test)]
+#[test]
+fn convert_money_on_1() {
+    use chrono::NaiveDate;
+    let mut config = SmartCalcConfig::default();
+    assert_eq!(config.load_currency_rate_history_csv("date,base,currency,rate\n2024-02-29,EUR,USD,1.08\n2024-03-04,EUR,USD,1.09\n2024-03-04,EUR,TRY,34.5"), Ok(3));
+
+    let usd = config.get_currency("usd".to_string()).unwrap();
+    let eur = config.get_currency("eur".to_string()).unwrap();
+    let try_currency = config.get_currency("try".to_string()).unwrap();
+    let date = |day: u32| NaiveDate::from_ymd_opt(2024, 3, day).unwrap();
+
+    /* Nearest earlier date that has both currencies */
+    assert!((config.get_historical_conversion(&eur, &usd, date(3)).unwrap().rate - 1.08).abs() < 0.000001);
+    assert!((config.get_historical_conversion(&usd, &try_currency, date(4)).unwrap().rate - 34.5 / 1.09).abs() < 0.000001);
+    assert!(config.get_historical_conversion(&usd, &try_currency, date(3)).is_err());
+    assert!((config.get_historical_conversion(&eur, &usd, date(5)).unwrap().rate - 1.09).abs() < 0.000001);
+    assert!(config.get_historical_conversion(&eur, &usd, NaiveDate::from_ymd_opt(2024, 2, 28).unwrap()).is_err());
+
+    /* New base is added with the rate of a known currency */
+    assert_eq!(config.load_currency_rate_history_json(r#"{"base": "USD", "rates": {"2024-03-04": {"GBP": 0.79}}}"#), Ok(1));
+    let gbp = config.get_currency("gbp".to_string()).unwrap();
+    assert!((config.get_historical_conversion(&eur, &gbp, date(4)).unwrap().rate - 1.09 * 0.79).abs() < 0.000001);
+
+    /* Nothing is loaded if one of the rates is not valid */
+    assert!(config.load_currency_rate_history_csv("2024-03-05,EUR,USD,1.1\n2024-03-05,EUR,XYZ,1.1").is_err());
+    assert!(config.load_currency_rate_history_csv("2024-03-05,EUR,USD,-1").is_err());
+    assert!(config.load_currency_rate_history_json(r#"{"base": "EUR", "rates": {"5 March": {"USD": 1.1}}}"#).is_err());
+    assert!((config.get_historical_conversion(&eur, &usd, date(5)).unwrap().rate - 1.09).abs() < 0.000001);
+}
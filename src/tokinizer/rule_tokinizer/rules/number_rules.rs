@@ -10,7 +10,13 @@ use alloc::string::ToString;
 use alloc::collections::btree_map::BTreeMap;
 
 use crate::config::SmartCalcConfig;
+use crate::compiler::integer::{wrap_integer, to_integer};
+use crate::compiler::fraction::FractionItem;
 use crate::tokinizer::get_currency;
+use crate::tokinizer::get_dynamic_type;
+use crate::tokinizer::get_fraction;
+use crate::tokinizer::get_integer;
+use crate::tokinizer::get_money;
 use crate::tokinizer::get_number;
 use crate::tokinizer::get_number_or_price;
 use crate::tokinizer::get_percent;
@@ -19,8 +25,10 @@ use crate::types::NumberType;
 use crate::{tokinizer::Tokinizer, types::{TokenType}};
 use crate::tokinizer::{TokenInfo};
 use crate::tools::do_divition;
+use crate::constants::MAX_SIGNIFICANT_FIGURES;
+use crate::error::SmartCalcError;
 
-pub fn number_on(config: &SmartCalcConfig, _: &Tokinizer, fields: &BTreeMap<String, Rc<TokenInfo>>) -> core::result::Result<TokenType, String> {
+pub fn number_on(config: &SmartCalcConfig, tokinizer: &Tokinizer, fields: &BTreeMap<String, Rc<TokenInfo>>) -> core::result::Result<TokenType, String> {
     if fields.contains_key("number") && fields.contains_key("p") {
         let number = match get_number_or_price(config, "number", fields) {
             Some(number) => number,
@@ -33,7 +41,7 @@ pub fn number_on(config: &SmartCalcConfig, _: &Tokinizer, fields: &BTreeMap<Stri
         };
 
         let calculated_number = number + do_divition(number * percent, 100.0);
-        return Ok(match get_currency(config, "number", fields) {
+        return Ok(match get_currency(config, tokinizer.session, "number", fields) {
             Some(currency) => TokenType::Money(calculated_number, currency),
             None => TokenType::Number(calculated_number, NumberType::Decimal)
         });
@@ -43,7 +51,7 @@ pub fn number_on(config: &SmartCalcConfig, _: &Tokinizer, fields: &BTreeMap<Stri
 }
 
 
-pub fn number_of(config: &SmartCalcConfig, _: &Tokinizer, fields: &BTreeMap<String, Rc<TokenInfo>>) -> core::result::Result<TokenType, String> {
+pub fn number_of(config: &SmartCalcConfig, tokinizer: &Tokinizer, fields: &BTreeMap<String, Rc<TokenInfo>>) -> core::result::Result<TokenType, String> {
     if fields.contains_key("number") && fields.contains_key("p") {
         let number = match get_number_or_price(config, "number", fields) {
             Some(number) => number,
@@ -56,7 +64,7 @@ pub fn number_of(config: &SmartCalcConfig, _: &Tokinizer, fields: &BTreeMap<Stri
         };
 
         let calculated_number = do_divition(number * percent, 100.0);
-        return Ok(match get_currency(config, "number", fields) {
+        return Ok(match get_currency(config, tokinizer.session, "number", fields) {
             Some(currency) => TokenType::Money(calculated_number, currency),
             None => TokenType::Number(calculated_number, NumberType::Decimal)
         });
@@ -66,7 +74,7 @@ pub fn number_of(config: &SmartCalcConfig, _: &Tokinizer, fields: &BTreeMap<Stri
 }
 
 
-pub fn number_off(config: &SmartCalcConfig, _: &Tokinizer, fields: &BTreeMap<String, Rc<TokenInfo>>) -> core::result::Result<TokenType, String> {
+pub fn number_off(config: &SmartCalcConfig, tokinizer: &Tokinizer, fields: &BTreeMap<String, Rc<TokenInfo>>) -> core::result::Result<TokenType, String> {
     if fields.contains_key("number") && fields.contains_key("p") {
         let number = match get_number_or_price(config, "number", fields) {
             Some(number) => number,
@@ -79,7 +87,7 @@ pub fn number_off(config: &SmartCalcConfig, _: &Tokinizer, fields: &BTreeMap<Str
         };
 
         let calculated_number = number - do_divition(number * percent, 100.0);
-        return Ok(match get_currency(config, "number", fields) {
+        return Ok(match get_currency(config, tokinizer.session, "number", fields) {
             Some(currency) => TokenType::Money(calculated_number, currency),
             None => TokenType::Number(calculated_number, NumberType::Decimal)
         });
@@ -88,10 +96,22 @@ pub fn number_off(config: &SmartCalcConfig, _: &Tokinizer, fields: &BTreeMap<Str
     Err("Number type not valid".to_string())
 }
 
+fn get_number_type(name: &str) -> Option<NumberType> {
+    match name {
+        "hex" | "hexadecimal" => Some(NumberType::Hexadecimal),
+        "octal"               => Some(NumberType::Octal),
+        "binary"              => Some(NumberType::Binary),
+        "decimal"             => Some(NumberType::Decimal),
+        "sci" | "scientific"  => Some(NumberType::Scientific),
+        "eng" | "engineering" => Some(NumberType::Engineering),
+        _ => None
+    }
+}
+
 pub fn number_type_convert(_: &SmartCalcConfig, _: &Tokinizer, fields: &BTreeMap<String, Rc<TokenInfo>>) -> core::result::Result<TokenType, String> {
     if fields.contains_key("number") && fields.contains_key("type") {
         let number = match get_number("number", fields) {
-            Some(number) => number.round(),
+            Some(number) => number,
             None => return Err("Number type not valid".to_string())
         };
         let number_type = match get_text("type", fields) {
@@ -99,20 +119,110 @@ pub fn number_type_convert(_: &SmartCalcConfig, _: &Tokinizer, fields: &BTreeMap
             None => return Err("Number type not valid".to_string())
         };
         
-        let number_type = match &number_type[..] {
-            "hex" | "hexadecimal" => NumberType::Hexadecimal,
-            "octal"               => NumberType::Octal,
-            "binary"              => NumberType::Binary,
-            "decimal"             => NumberType::Decimal,
-            _ => return Err("Target number type not valid".to_string())
+        let number_type = match get_number_type(&number_type) {
+            Some(number_type) => number_type,
+            None => return Err("Target number type not valid".to_string())
         };
 
-        return Ok(TokenType::Number(number, number_type));
+        /* Only integer types lose the fract part, exact value is used if the number is already an integer */
+        let integer = match number_type {
+            NumberType::Hexadecimal | NumberType::Octal | NumberType::Binary => match get_integer("number", fields) {
+                Some(integer) => Some(integer),
+                None => Some(to_integer(number.round()).ok_or_else(|| "Number is too big for an integer".to_string())?)
+            },
+            _ => None
+        };
+
+        /* Number is reinterpreted with the given width, 8 bit signed. Non decimal numbers always show the bit pattern */
+        if let Some((bits, dynamic_type)) = get_dynamic_type("bits", fields) {
+            if !dynamic_type.names.iter().any(|name| name == "bit") {
+                return Err("Integer width not valid".to_string());
+            }
+
+            let bits = match bits as u8 {
+                8 | 16 | 32 | 64 if bits.fract() == 0.0 => bits as u8,
+                _ => return Err("Integer width not valid".to_string())
+            };
+
+            let signed = number_type == NumberType::Decimal && matches!(get_text("sign", fields).as_deref(), Some("signed"));
+            return match integer {
+                Some(integer) => Ok(TokenType::Integer(wrap_integer(integer, bits, signed), number_type)),
+                None => Ok(TokenType::Number(wrap_integer(number as i128, bits, signed) as f64, number_type))
+            };
+        }
+
+        return match integer {
+            Some(integer) => Ok(TokenType::Integer(integer, number_type)),
+            None => Ok(TokenType::Number(number, number_type))
+        };
     }
 
     Err("Number type not valid".to_string())
 }
 
+pub fn fraction_convert(_: &SmartCalcConfig, tokinizer: &Tokinizer, fields: &BTreeMap<String, Rc<TokenInfo>>) -> core::result::Result<TokenType, String> {
+    if fields.contains_key("fraction") && fields.contains_key("type") {
+        let number = match get_fraction("fraction", fields) {
+            Some((numerator, denominator)) => numerator as f64 / denominator as f64,
+            None => return Err("Fraction not valid".to_string())
+        };
+
+        /* Only decimal keeps the fract part, other number types are integer and "7/2 to hex" is not rounded */
+        return match get_text("type", fields).as_deref().and_then(get_number_type) {
+            Some(number_type @ NumberType::Decimal) | Some(number_type @ NumberType::Scientific) | Some(number_type @ NumberType::Engineering) => Ok(TokenType::Number(number, number_type)),
+            Some(_) if number.fract() != 0.0 => {
+                tokinizer.session.set_line_error(SmartCalcError::InvalidArgument("Fraction is not an integer".to_string()));
+                Err("Fraction is not an integer".to_string())
+            },
+            Some(number_type) => match to_integer(number) {
+                Some(integer) => Ok(TokenType::Integer(integer, number_type)),
+                None => Err("Fraction is too big for an integer".to_string())
+            },
+            None => Err("Target number type not valid".to_string())
+        };
+    }
+
+    if fields.contains_key("number") && fields.contains_key("type") {
+        return match get_number("number", fields).and_then(FractionItem::from_number) {
+            Some(fraction) => Ok(TokenType::Fraction(fraction.get_numerator(), fraction.get_denominator())),
+            None => Err("Number could not be converted to fraction".to_string())
+        };
+    }
+
+    Err("Fraction not valid".to_string())
+}
+
+pub fn significant_figures_convert(_: &SmartCalcConfig, _: &Tokinizer, fields: &BTreeMap<String, Rc<TokenInfo>>) -> core::result::Result<TokenType, String> {
+    if fields.contains_key("number") && fields.contains_key("figures") {
+        let number = match get_number("number", fields) {
+            Some(number) => number,
+            None => return Err("Number information not valid".to_string())
+        };
+
+        return match get_number("figures", fields) {
+            Some(figures) if figures.fract() == 0.0 && figures >= 1.0 && figures <= MAX_SIGNIFICANT_FIGURES as f64 => Ok(TokenType::Number(number, NumberType::SignificantFigures(figures as u8))),
+            _ => Err("Significant figures not valid".to_string())
+        };
+    }
+
+    Err("Significant figures not valid".to_string())
+}
+
+pub fn number_to_words(config: &SmartCalcConfig, _: &Tokinizer, fields: &BTreeMap<String, Rc<TokenInfo>>) -> core::result::Result<TokenType, String> {
+    if fields.contains_key("number") {
+        if let Some(money) = get_money(config, "number", fields) {
+            return Ok(TokenType::Words(money.get_price(), Some(money.get_currency())));
+        }
+
+        return match get_number("number", fields) {
+            Some(number) => Ok(TokenType::Words(number, None)),
+            None => Err("Number information not valid".to_string())
+        };
+    }
+
+    Err("Number information not valid".to_string())
+}
+
 #[cfg(test)]
 #[test]
 fn number_on_1() {
@@ -155,7 +265,7 @@ fn number_type_convert_1() {
     use crate::tokinizer::test::execute;
 
     let tokens = execute("100 to hex".to_string());
-    assert_eq!(tokens[0].token_type.borrow().deref(), &Some(TokenType::Number(100.0, NumberType::Hexadecimal)));
+    assert_eq!(tokens[0].token_type.borrow().deref(), &Some(TokenType::Integer(100, NumberType::Hexadecimal)));
 }
 
 #[cfg(test)]
@@ -165,7 +275,7 @@ fn number_type_convert_2() {
     use crate::tokinizer::test::execute;
     
     let tokens = execute("100,0 to hex".to_string());
-    assert_eq!(tokens[0].token_type.borrow().deref(), &Some(TokenType::Number(100.0, NumberType::Hexadecimal)));
+    assert_eq!(tokens[0].token_type.borrow().deref(), &Some(TokenType::Integer(100, NumberType::Hexadecimal)));
 }
 
 #[cfg(test)]
@@ -175,7 +285,7 @@ fn number_type_convert_3() {
     use crate::tokinizer::test::execute;
     
     let tokens = execute("100,0 to hexadecimal".to_string());
-    assert_eq!(tokens[0].token_type.borrow().deref(), &Some(TokenType::Number(100.0, NumberType::Hexadecimal)));
+    assert_eq!(tokens[0].token_type.borrow().deref(), &Some(TokenType::Integer(100, NumberType::Hexadecimal)));
 }
 
 #[cfg(test)]
@@ -185,7 +295,7 @@ fn number_type_convert_4() {
     use crate::tokinizer::test::execute;
     
     let tokens = execute("100,0 to octal".to_string());
-    assert_eq!(tokens[0].token_type.borrow().deref(), &Some(TokenType::Number(100.0, NumberType::Octal)));
+    assert_eq!(tokens[0].token_type.borrow().deref(), &Some(TokenType::Integer(100, NumberType::Octal)));
 }
 
 #[cfg(test)]
@@ -194,8 +304,8 @@ fn number_type_convert_5() {
     use core::ops::Deref;
     use crate::tokinizer::test::execute;
     
-    let tokens = execute("100,0 to oct".to_string());
-    assert_eq!(tokens[0].token_type.borrow().deref(), &Some(TokenType::Number(100.0, NumberType::Octal)));
+    let tokens = execute("0x64 to octal".to_string());
+    assert_eq!(tokens[0].token_type.borrow().deref(), &Some(TokenType::Integer(100, NumberType::Octal)));
 }
 
 #[cfg(test)]
@@ -204,8 +314,8 @@ fn number_type_convert_6() {
     use core::ops::Deref;
     use crate::tokinizer::test::execute;
     
-    let tokens = execute("100,0 to bin".to_string());
-    assert_eq!(tokens[0].token_type.borrow().deref(), &Some(TokenType::Number(100.0, NumberType::Binary)));
+    let tokens = execute("0o144 to binary".to_string());
+    assert_eq!(tokens[0].token_type.borrow().deref(), &Some(TokenType::Integer(100, NumberType::Binary)));
 }
 
 #[cfg(test)]
@@ -215,5 +325,33 @@ fn number_type_convert_7() {
     use crate::tokinizer::test::execute;
     
     let tokens = execute("100,0 to binary".to_string());
-    assert_eq!(tokens[0].token_type.borrow().deref(), &Some(TokenType::Number(100.0, NumberType::Binary)));
+    assert_eq!(tokens[0].token_type.borrow().deref(), &Some(TokenType::Integer(100, NumberType::Binary)));
+}
+
+#[cfg(test)]
+#[test]
+fn significant_figures_convert_1() {
+    use core::ops::Deref;
+    use crate::tokinizer::test::execute;
+
+    let tokens = execute("0,000123456 to 3 sig figs".to_string());
+    assert_eq!(tokens[0].token_type.borrow().deref(), &Some(TokenType::Number(0.000123456, NumberType::SignificantFigures(3))));
+
+    let tokens = execute("1234 as 2 significant digits".to_string());
+    assert_eq!(tokens[0].token_type.borrow().deref(), &Some(TokenType::Number(1234.0, NumberType::SignificantFigures(2))));
+}
+
+#[cfg(test)]
+#[test]
+fn number_to_words_1() {
+    use core::ops::Deref;
+    use crate::tokinizer::test::execute;
+    use crate::config::SmartCalcConfig;
+    let config = SmartCalcConfig::default();
+
+    let tokens = execute("1250 eur to words".to_string());
+    assert_eq!(tokens[0].token_type.borrow().deref(), &Some(TokenType::Words(1250.0, config.get_currency("eur".to_string()))));
+
+    let tokens = execute("125 as words".to_string());
+    assert_eq!(tokens[0].token_type.borrow().deref(), &Some(TokenType::Words(125.0, None)));
 }
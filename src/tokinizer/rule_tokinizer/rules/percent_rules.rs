@@ -53,7 +53,7 @@ pub fn find_numbers_percent(config: &SmartCalcConfig, _: &Tokinizer, fields: &BT
     Err("Find percent not valid".to_string())
 }
 
-pub fn find_total_from_percent(config: &SmartCalcConfig, _: &Tokinizer, fields: &BTreeMap<String, Rc<TokenInfo>>) -> core::result::Result<TokenType, String> {
+pub fn find_total_from_percent(config: &SmartCalcConfig, tokinizer: &Tokinizer, fields: &BTreeMap<String, Rc<TokenInfo>>) -> core::result::Result<TokenType, String> {
     if fields.contains_key("number_part") && fields.contains_key("percent_part") {
         let number_part = match get_number_or_price(config, "number_part", fields) {
             Some(number) => number,
@@ -65,7 +65,7 @@ pub fn find_total_from_percent(config: &SmartCalcConfig, _: &Tokinizer, fields:
             _ => return Err("Percent part information not valid".to_string())
         };
 
-        return Ok(match get_currency(config, "number_part", fields) {
+        return Ok(match get_currency(config, tokinizer.session, "number_part", fields) {
             Some(currency) => TokenType::Money(do_divition(number_part * 100.0, percent_part), currency),
             None => TokenType::Number(do_divition(number_part * 100.0, percent_part), NumberType::Decimal)
         });
@@ -0,0 +1,135 @@
+/*
+ * smartcalc v1.0.8
+ * Copyright (c) Erhan BARIS (Ruslan Ognyanov Asenov)
+ * Licensed under the GNU General Public License v2.0.
+ */
+
+use alloc::rc::Rc;
+use alloc::string::String;
+use alloc::string::ToString;
+use alloc::collections::btree_map::BTreeMap;
+
+use chrono::{Duration, Timelike};
+
+use crate::config::{SmartCalcConfig, RoundingMode};
+use crate::error::SmartCalcError;
+use crate::tokinizer::get_duration;
+use crate::tokinizer::get_money;
+use crate::tokinizer::get_number;
+use crate::tokinizer::get_number_or_price;
+use crate::tokinizer::get_text;
+use crate::tokinizer::get_time;
+use crate::types::NumberType;
+use crate::{tokinizer::Tokinizer, types::{TokenType}};
+use crate::tokinizer::{TokenInfo};
+use crate::tools::{round_number, round_to_step};
+
+/* Half up, half down and half even only change the ties. Up and down without half always move the value */
+fn get_rounding_mode(fields: &BTreeMap<String, Rc<TokenInfo>>) -> core::result::Result<Option<RoundingMode>, String> {
+    let mode = match get_text("mode", fields) {
+        Some(mode) => mode,
+        None => return Ok(None)
+    };
+
+    let half = fields.contains_key("half");
+    match (half, mode.as_str()) {
+        (true, "up")                      => Ok(Some(RoundingMode::HalfUp)),
+        (true, "down")                    => Ok(Some(RoundingMode::HalfDown)),
+        (_, "even")                       => Ok(Some(RoundingMode::HalfEven)),
+        (false, "up") | (false, "ceiling") => Ok(Some(RoundingMode::Ceiling)),
+        (false, "down") | (false, "floor") => Ok(Some(RoundingMode::Floor)),
+        (false, "truncate")               => Ok(Some(RoundingMode::Truncate)),
+        _ => Err("Rounding mode not valid".to_string())
+    }
+}
+
+/* Wrong digits or step is an error of the line, the tokens are not calculated as "12,345 + 20" */
+fn invalid_argument(tokinizer: &Tokinizer, message: &str) -> String {
+    tokinizer.session.set_line_error(SmartCalcError::InvalidArgument(message.to_string()));
+    message.to_string()
+}
+
+fn round_with_fields(number: f64, rounding_mode: RoundingMode, fields: &BTreeMap<String, Rc<TokenInfo>>, config: &SmartCalcConfig) -> core::result::Result<f64, String> {
+    if fields.contains_key("digits") {
+        return match get_number("digits", fields) {
+            Some(digits) if digits.fract() == 0.0 && (0.0..=15.0).contains(&digits) => Ok(round_number(number, digits as u8, rounding_mode)),
+            _ => Err("Decimal digits not valid".to_string())
+        };
+    }
+
+    match get_number_or_price(config, "step", fields) {
+        Some(step) if step != 0.0 => Ok(round_to_step(number, step, rounding_mode)),
+        _ => Err("Rounding step not valid".to_string())
+    }
+}
+
+pub fn round_value(config: &SmartCalcConfig, tokinizer: &Tokinizer, fields: &BTreeMap<String, Rc<TokenInfo>>) -> core::result::Result<TokenType, String> {
+    if fields.contains_key("number") && (fields.contains_key("digits") || fields.contains_key("step")) {
+        let rounding_mode = get_rounding_mode(fields)?;
+
+        if let Some(money) = get_money(config, "number", fields) {
+            let rounded = round_with_fields(money.get_price(), rounding_mode.unwrap_or(config.money_config.rounding_mode), fields, config).map_err(|error| invalid_argument(tokinizer, &error))?;
+            return Ok(TokenType::Money(rounded, money.get_currency()));
+        }
+
+        if let Some(number) = get_number("number", fields) {
+            let rounded = round_with_fields(number, rounding_mode.unwrap_or(config.number_config.rounding_mode), fields, config).map_err(|error| invalid_argument(tokinizer, &error))?;
+            return Ok(TokenType::Number(rounded, NumberType::Decimal));
+        }
+
+        /* Durations and times are rounded with seconds, 10:07 to nearest 5 minutes is 10:05 */
+        let rounding_mode = rounding_mode.unwrap_or_default();
+        let step = match get_duration("step", fields) {
+            Some(step) if step.num_seconds() != 0 => step.num_seconds() as f64,
+            _ => return Err(invalid_argument(tokinizer, "Rounding step not valid"))
+        };
+
+        if let Some(duration) = get_duration("number", fields) {
+            let rounded = round_to_step(duration.num_seconds() as f64, step, rounding_mode);
+            return Ok(TokenType::Duration(Duration::seconds(rounded as i64)));
+        }
+
+        if let Some((time, tz)) = get_time("number", fields) {
+            let seconds = time.num_seconds_from_midnight() as f64;
+            let rounded = round_to_step(seconds, step, rounding_mode);
+            let midnight = time - Duration::seconds(seconds as i64) - Duration::nanoseconds(time.nanosecond() as i64);
+            return Ok(TokenType::Time(midnight + Duration::seconds(rounded as i64), tz));
+        }
+
+        return Err("Rounding value not valid".to_string());
+    }
+
+    Err("Rounding information not valid".to_string())
+}
+
+#[cfg(test)]
+#[test]
+fn round_value_1() {
+    use core::ops::Deref;
+    use crate::tokinizer::test::execute;
+
+    let tokens = execute("round 12,345 to 2 digits half even".to_string());
+    assert_eq!(tokens[0].token_type.borrow().deref(), &Some(TokenType::Number(12.34, NumberType::Decimal)));
+
+    let tokens = execute("round 12,345 to 2 digits".to_string());
+    assert_eq!(tokens[0].token_type.borrow().deref(), &Some(TokenType::Number(12.35, NumberType::Decimal)));
+
+    let tokens = execute("round 12,341 to 2 digits up".to_string());
+    assert_eq!(tokens[0].token_type.borrow().deref(), &Some(TokenType::Number(12.35, NumberType::Decimal)));
+
+    let tokens = execute("12,32 to nearest 0,05".to_string());
+    assert_eq!(tokens[0].token_type.borrow().deref(), &Some(TokenType::Number(12.3, NumberType::Decimal)));
+}
+
+#[cfg(test)]
+#[test]
+fn round_value_2() {
+    use core::ops::Deref;
+    use crate::tokinizer::test::execute;
+
+    let tokens = execute("round 2 hours 7 minutes to nearest 5 minutes".to_string());
+    assert_eq!(tokens[0].token_type.borrow().deref(), &Some(TokenType::Duration(Duration::minutes(125))));
+
+    let tokens = execute("round 2 hours 7 minutes to nearest 5 minutes up".to_string());
+    assert_eq!(tokens[0].token_type.borrow().deref(), &Some(TokenType::Duration(Duration::minutes(130))));
+}
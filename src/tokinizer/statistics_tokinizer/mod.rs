@@ -0,0 +1,124 @@
+/*
+ * smartcalc v1.0.8
+ * Copyright (c) Erhan BARIS (Ruslan Ognyanov Asenov)
+ * Licensed under the GNU General Public License v2.0.
+ */
+
+use core::ops::Deref;
+use alloc::rc::Rc;
+use alloc::vec;
+use alloc::vec::Vec;
+use alloc::string::ToString;
+
+use crate::compiler::text::TextItem;
+use crate::config::SmartCalcConfig;
+use crate::token::ui_token::UiTokenType;
+use crate::types::TokenType;
+
+use super::Tokinizer;
+use super::tools::get_word;
+
+/* Word group and the function that is called with the values after the word */
+const FUNCTION_GROUPS: [(&str, &str); 10] = [
+    ("sum_group",        "sum"),
+    ("average_group",    "average"),
+    ("median_group",     "median"),
+    ("minimum_group",    "min"),
+    ("maximum_group",    "max"),
+    ("count_group",      "count"),
+    ("deviation_group",  "stddev"),
+    ("percentile_group", "percentile"),
+    ("random_group",     "random"),
+    ("pick_group",       "pick")
+];
+
+/* Functions that can be called without values */
+const NO_ARGUMENT_FUNCTIONS: [&str; 1] = ["random"];
+
+fn is_value(token_type: &Option<TokenType>) -> bool {
+    matches!(token_type, Some(TokenType::Number(_, _)) | Some(TokenType::Integer(_, _)) | Some(TokenType::Money(_, _)) | Some(TokenType::DisplayMoney(_, _, _)) | Some(TokenType::Percent(_)) | Some(TokenType::Fraction(_, _)) |
+        Some(TokenType::Duration(_)) | Some(TokenType::DynamicType(_, _)) | Some(TokenType::Variable(_)) | Some(TokenType::List(_)))
+}
+
+fn get_function(config: &SmartCalcConfig, language: &str, text: &str) -> Option<&'static str> {
+    let groups = config.word_group.get(language)?;
+    FUNCTION_GROUPS.iter()
+        .find(|(group, _)| groups.get(*group).is_some_and(|items| items.iter().any(|item| item.to_lowercase() == text)))
+        .map(|(_, function)| *function)
+}
+
+/* Words in a list are picked as they are written, "pick one of a, b, c". Each word is a list with one text and pick opens the lists */
+fn text_items_to_values(tokinizer: &mut Tokinizer, index: usize) {
+    let tokens = &tokinizer.token_infos[index + 1..];
+    let is_separator = |position: usize| tokens.get(position).is_some_and(|token| matches!(token.token_type.borrow().deref(), Some(TokenType::Operator(','))));
+    let text_items = tokens.iter().enumerate()
+        .filter(|(position, token)| matches!(token.token_type.borrow().deref(), Some(TokenType::Text(_))) && ((*position > 0 && is_separator(position - 1)) || is_separator(position + 1)))
+        .map(|(_, token)| token.clone())
+        .collect::<Vec<_>>();
+
+    for token in text_items.iter() {
+        let text = token.token_type.borrow().as_ref().map(|token_type| token_type.to_string()).unwrap_or_default();
+        *token.token_type.borrow_mut() = Some(TokenType::List(vec![Rc::new(TextItem(text))]));
+        tokinizer.ui_tokens.update_tokens(token.start, token.end, UiTokenType::Text);
+    }
+}
+
+/* "average of 12, 15, 20 usd", "median 3 5 9 11" and "random between 1 and 100". The word should be followed
+   by values and should not follow a value, so "5 min 30 sec" is still a duration */
+pub fn statistics_tokinizer(tokinizer: &mut Tokinizer) {
+    let config = tokinizer.config;
+
+    /* Variable name can not be a function */
+    let start = tokinizer.token_infos.iter()
+        .position(|token| matches!(token.token_type.borrow().deref(), Some(TokenType::Operator('='))))
+        .map_or(0, |index| index + 1);
+
+    for index in start..tokinizer.token_infos.len() {
+        let text = match get_word(&tokinizer.token_infos[index]) {
+            Some(text) => text,
+            None => continue
+        };
+
+        if index > start && is_value(tokinizer.token_infos[index - 1].token_type.borrow().deref()) {
+            continue;
+        }
+
+        let function = get_function(config, &tokinizer.language, &text);
+        if function == Some("pick") {
+            text_items_to_values(tokinizer, index);
+        }
+
+        let has_values = tokinizer.token_infos.iter()
+            .skip(index + 1)
+            .find(|token| !matches!(token.token_type.borrow().deref(), Some(TokenType::Text(_))))
+            .is_some_and(|token| is_value(token.token_type.borrow().deref()));
+
+        let function = match function {
+            Some(function) if (has_values || NO_ARGUMENT_FUNCTIONS.contains(&function)) && config.functions.contains_key(function) => function,
+            _ => continue
+        };
+
+        let token = tokinizer.token_infos[index].clone();
+        *token.token_type.borrow_mut() = Some(TokenType::Function(function.to_string()));
+        tokinizer.ui_tokens.update_tokens(token.start, token.end, UiTokenType::Symbol2);
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn statistics_test() {
+    use alloc::string::ToString;
+    use crate::tokinizer::test::execute;
+
+    let tokens = execute("average of 12, 15 + 3 min 2".to_string());
+    assert_eq!(tokens[0].token_type.borrow().deref(), &Some(TokenType::Function("average".to_string())));
+    assert_eq!(tokens[1].token_type.borrow().deref(), &Some(TokenType::Text("of".to_string())));
+    assert!(!tokens.iter().any(|token| token.token_type.borrow().deref() == &Some(TokenType::Function("min".to_string()))));
+
+    let tokens = execute("random * 10".to_string());
+    assert_eq!(tokens[0].token_type.borrow().deref(), &Some(TokenType::Function("random".to_string())));
+
+    /* Without values it is still the total of the lines */
+    let tokens = execute("sum".to_string());
+    assert_eq!(tokens[0].token_type.borrow().deref(), &Some(TokenType::Number(0.0, crate::types::NumberType::Decimal)));
+}
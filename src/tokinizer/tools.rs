@@ -13,22 +13,25 @@ use chrono::Utc;
 use crate::compiler::date::DateItem;
 use crate::compiler::date_time::DateTimeItem;
 use crate::compiler::duration::DurationItem;
-use crate::compiler::number::NumberItem;
+use crate::compiler::fraction::FractionItem;
 use crate::compiler::percent::PercentItem;
 use crate::compiler::dynamic_type::DynamicTypeItem;
 use crate::compiler::DataItem;
+use crate::compiler::integer::{get_integer as get_item_integer, to_integer};
 use crate::compiler::time::TimeItem;
 use crate::types::TimeOffset;
 use core::ops::Deref;
 use chrono::{Duration, NaiveDate};
 
 use crate::config::SmartCalcConfig;
+use crate::error::SmartCalcWarning;
+use crate::session::Session;
 use crate::config::DynamicType;
 use crate::types::CurrencyInfo;
 use crate::types::Money;
 use crate::types::{TokenType, SmartCalcAstType};
 use crate::tokinizer::TokenInfo;
-use crate::compiler::money::MoneyItem;
+use crate::compiler::money::get_money_item;
 
 pub fn read_currency(config: &SmartCalcConfig, currency: &'_ str) -> Option<Rc<CurrencyInfo>> {
     match config.currency_alias.get(&currency.to_lowercase()) {
@@ -37,14 +40,88 @@ pub fn read_currency(config: &SmartCalcConfig, currency: &'_ str) -> Option<Rc<C
     }
 }
 
+/* Shared symbols are read as the first preferred currency of the session or the configuration, otherwise the alias is used with a warning */
+pub fn resolve_currency(config: &SmartCalcConfig, session: &Session, currency: &'_ str) -> Option<Rc<CurrencyInfo>> {
+    let default_currency = read_currency(config, currency)?;
+    let candidates = match config.ambiguous_currency.get(&currency.to_lowercase()) {
+        Some(candidates) => candidates,
+        None => return Some(default_currency)
+    };
+
+    let preferred = session.get_preferred_currencies().iter()
+        .chain(config.money_config.preferred_currencies.iter())
+        .find_map(|code| candidates.iter().find(|candidate| &candidate.code == code));
+
+    if let Some(currency) = preferred {
+        return Some(currency.clone());
+    }
+
+    session.add_warning(SmartCalcWarning::AmbiguousCurrency {
+        symbol: currency.to_string(),
+        currency: default_currency.code.to_string(),
+        alternatives: candidates.iter().filter(|candidate| **candidate != default_currency).map(|candidate| candidate.code.to_string()).collect()
+    });
+    Some(default_currency)
+}
+
+/* Text of a word token, words that are aliases of an operator like "sum" are also returned */
+pub fn get_word(token: &TokenInfo) -> Option<String> {
+    match token.token_type.borrow().deref() {
+        Some(TokenType::Text(text)) => Some(text.to_lowercase()),
+        Some(TokenType::Operator(_)) if token.original_text.chars().all(char::is_alphabetic) => Some(token.original_text.to_lowercase()),
+        _ => None
+    }
+}
+
 pub fn get_number(field_name: &str, fields: &BTreeMap<String, Rc<TokenInfo>>) -> Option<f64> {
     return match fields.get(field_name) {
         Some(data) => match data.token_type.borrow().deref() {
             Some(token) => match &token {
                 TokenType::Number(number, _) => Some(*number),
+                TokenType::Integer(number, _) => Some(*number as f64),
+                TokenType::Variable(variable) => {
+                    match variable.data.borrow().deref().deref() {
+                        SmartCalcAstType::Item(item) if item.type_name() == "NUMBER" => Some(item.get_underlying_number()),
+                        _ => None
+                    }
+                },
+                _ => None
+            },
+            _ => None
+        },
+        _ => None
+    }
+}
+
+/* Exact value of an integer number, big binary, octal and hexadecimal numbers do not fit into f64 */
+pub fn get_integer(field_name: &str, fields: &BTreeMap<String, Rc<TokenInfo>>) -> Option<i128> {
+    match fields.get(field_name) {
+        Some(data) => match data.token_type.borrow().deref() {
+            Some(token) => match &token {
+                TokenType::Integer(number, _) => Some(*number),
+                TokenType::Number(number, _) => to_integer(*number),
+                TokenType::Variable(variable) => {
+                    match variable.data.borrow().deref().deref() {
+                        SmartCalcAstType::Item(item) => get_item_integer(item.deref()),
+                        _ => None
+                    }
+                },
+                _ => None
+            },
+            _ => None
+        },
+        _ => None
+    }
+}
+
+pub fn get_fraction(field_name: &str, fields: &BTreeMap<String, Rc<TokenInfo>>) -> Option<(i64, i64)> {
+    match fields.get(field_name) {
+        Some(data) => match data.token_type.borrow().deref() {
+            Some(token) => match &token {
+                TokenType::Fraction(numerator, denominator) => Some((*numerator, *denominator)),
                 TokenType::Variable(variable) => {
                     match variable.data.borrow().deref().deref() {
-                        SmartCalcAstType::Item(item) => item.as_any().downcast_ref::<NumberItem>().map(|number| number.get_underlying_number()),
+                        SmartCalcAstType::Item(item) => item.as_any().downcast_ref::<FractionItem>().map(|fraction| (fraction.get_numerator(), fraction.get_denominator())),
                         _ => None
                     }
                 },
@@ -221,10 +298,10 @@ pub fn get_money(_: &SmartCalcConfig, field_name: &str, fields: &BTreeMap<String
     return match &fields.get(field_name) {
         Some(data) => match &data.token_type.borrow().deref() {
             Some(token) => match &token {
-                TokenType::Money(price, currency) => Some(Money(*price, currency.clone())),
+                TokenType::Money(price, currency) | TokenType::DisplayMoney(price, currency, _) => Some(Money(*price, currency.clone())),
                 TokenType::Variable(variable) => {
                     match variable.data.borrow().deref().deref() {
-                        SmartCalcAstType::Item(item) => item.as_any().downcast_ref::<MoneyItem>().map(|money_item| Money(money_item.get_price(), money_item.get_currency())),
+                        SmartCalcAstType::Item(item) => get_money_item(item.deref()).map(|money_item| Money(money_item.get_price(), money_item.get_currency())),
                         _ => None
                     }
                 },
@@ -236,15 +313,15 @@ pub fn get_money(_: &SmartCalcConfig, field_name: &str, fields: &BTreeMap<String
     }
 }
 
-pub fn get_currency(config: &SmartCalcConfig, field_name: &str, fields: &BTreeMap<String, Rc<TokenInfo>>) -> Option<Rc<CurrencyInfo>> {
+pub fn get_currency(config: &SmartCalcConfig, session: &Session, field_name: &str, fields: &BTreeMap<String, Rc<TokenInfo>>) -> Option<Rc<CurrencyInfo>> {
     match &fields.get(field_name) {
         Some(data) => match &data.token_type.borrow().deref() {
             Some(token) => match &token {
-                TokenType::Text(currency) => read_currency(config, currency),
-                TokenType::Money(_, currency) => Some(currency.clone()),
+                TokenType::Text(currency) => resolve_currency(config, session, currency),
+                TokenType::Money(_, currency) | TokenType::DisplayMoney(_, currency, _) => Some(currency.clone()),
                 TokenType::Variable(variable) => {
                     match variable.data.borrow().deref().deref() {
-                        SmartCalcAstType::Item(item) => item.as_any().downcast_ref::<MoneyItem>().map(|money_item| money_item.get_currency()),
+                        SmartCalcAstType::Item(item) => get_money_item(item.deref()).map(|money_item| money_item.get_currency()),
                         _ => None
                     }
                 },
@@ -5,14 +5,173 @@
  */
 
 use alloc::string::{ToString, String};
-use crate::config::SmartCalcConfig;
+#[cfg(feature = "decimal")]
+use core::str::FromStr;
+#[cfg(feature = "decimal")]
+use rust_decimal::Decimal;
+#[cfg(feature = "decimal")]
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive, MathematicalOps};
+#[cfg(feature = "decimal")]
+use rust_decimal::RoundingStrategy;
+use crate::compiler::OperationType;
+use crate::config::{SmartCalcConfig, RoundingMode};
+use crate::types::CurrencyInfo;
  
+#[cfg(feature = "decimal")]
+fn decimal_math(left: f64, right: f64, operation_type: OperationType) -> Option<f64> {
+    let left = Decimal::from_f64(left)?;
+    let right = Decimal::from_f64(right)?;
+
+    let result = match operation_type {
+        OperationType::Add => left.checked_add(right),
+        OperationType::Sub => left.checked_sub(right),
+        OperationType::Mul => left.checked_mul(right),
+        OperationType::Div => left.checked_div(right),
+        OperationType::Mod => left.checked_rem(right),
+        OperationType::Pow => match right.fract().is_zero() {
+            true => left.checked_powi(right.to_i64()?),
+            false => None
+        }
+    };
+
+    result?.to_f64()
+}
+
+fn float_math(left: f64, right: f64, operation_type: OperationType) -> f64 {
+    let calculation = match operation_type {
+        OperationType::Add => left + right,
+        OperationType::Sub => left - right,
+        OperationType::Mul => left * right,
+        OperationType::Div => left / right,
+        OperationType::Mod => left % right,
+        OperationType::Pow => left.powf(right)
+    };
+
+    match operation_type {
+        OperationType::Div | OperationType::Mod if calculation.is_infinite() || calculation.is_nan() => 0.0,
+        _ => calculation
+    }
+}
+
+/* With the decimal feature calculation made with decimal numbers, f64 is used if the result does not fit into decimal */
+pub fn do_math(left: f64, right: f64, operation_type: OperationType) -> f64 {
+    #[cfg(feature = "decimal")]
+    {
+        if let OperationType::Div | OperationType::Mod = operation_type {
+            if right == 0.0 {
+                return 0.0;
+            }
+        }
+
+        if let Some(calculation) = decimal_math(left, right, operation_type) {
+            return calculation;
+        }
+    }
+
+    float_math(left, right, operation_type)
+}
+
 pub fn do_divition(left: f64, right: f64) -> f64 {
-    let mut calculation = left / right;
-    if calculation.is_infinite() || calculation.is_nan() {
-        calculation = 0.0;
+    do_math(left, right, OperationType::Div)
+}
+
+pub fn do_modulo(left: f64, right: f64) -> f64 {
+    do_math(left, right, OperationType::Mod)
+}
+
+pub fn convert_amount(amount: f64, source_rate: f64, target_rate: f64) -> f64 {
+    /* Multiplication made first to keep the precision */
+    match source_rate == 0.0 {
+        true => 0.0,
+        false => do_divition(do_math(amount, target_rate, OperationType::Mul), source_rate)
     }
-    calculation
+}
+
+/* With the decimal feature converted money is rounded to the digits of the target currency, so conversions are exact to the cent */
+pub fn round_converted_price(config: &SmartCalcConfig, price: f64, currency: &CurrencyInfo) -> f64 {
+    #[cfg(feature = "decimal")]
+    let price = round_number(price, currency.decimal_digits, config.money_config.rounding_mode);
+    #[cfg(not(feature = "decimal"))]
+    let _ = (config, currency);
+
+    price
+}
+
+#[cfg(feature = "decimal")]
+fn decimal_round(number: f64, decimal_digits: u8, rounding_mode: RoundingMode) -> Option<f64> {
+    let strategy = match rounding_mode {
+        RoundingMode::HalfUp => RoundingStrategy::MidpointAwayFromZero,
+        RoundingMode::HalfEven => RoundingStrategy::MidpointNearestEven,
+        RoundingMode::HalfDown => RoundingStrategy::MidpointTowardZero,
+        RoundingMode::Truncate => RoundingStrategy::ToZero,
+        RoundingMode::Ceiling => RoundingStrategy::ToPositiveInfinity,
+        RoundingMode::Floor => RoundingStrategy::ToNegativeInfinity
+    };
+
+    Decimal::from_f64(number)?.round_dp_with_strategy(decimal_digits.into(), strategy).to_f64()
+}
+
+/* Floating numbers are not exact, 1.005 is stored as 1.00499.. so values that close to the written digits are accepted as exact */
+fn float_round(number: f64, decimal_digits: u8, rounding_mode: RoundingMode) -> f64 {
+    let multiplier = 10_f64.powi(decimal_digits.into());
+    let scaled = number * multiplier;
+    if !scaled.is_finite() {
+        return number;
+    }
+
+    let tolerance = f64::EPSILON * scaled.abs().max(1.0) * 8.0;
+    let floor = scaled.floor();
+    let fract = scaled - floor;
+    let is_whole = fract <= tolerance || 1.0 - fract <= tolerance;
+    let is_half = (fract - 0.5).abs() <= tolerance;
+
+    let rounded = match rounding_mode {
+        _ if is_whole => scaled.round(),
+        RoundingMode::Truncate => scaled.trunc(),
+        RoundingMode::Ceiling => scaled.ceil(),
+        RoundingMode::Floor => floor,
+        _ if !is_half => scaled.round(),
+        RoundingMode::HalfUp => if scaled < 0.0 { floor } else { floor + 1.0 },
+        RoundingMode::HalfDown => if scaled < 0.0 { floor + 1.0 } else { floor },
+        RoundingMode::HalfEven => if floor % 2.0 == 0.0 { floor } else { floor + 1.0 }
+    };
+
+    rounded / multiplier
+}
+
+pub fn round_number(number: f64, decimal_digits: u8, rounding_mode: RoundingMode) -> f64 {
+    #[cfg(feature = "decimal")]
+    {
+        if let Some(rounded) = decimal_round(number, decimal_digits, rounding_mode) {
+            return rounded;
+        }
+    }
+
+    float_round(number, decimal_digits, rounding_mode)
+}
+
+/* Rounds to the closest multiple of the step, 12.32 to nearest 0.05 is 12.3 */
+pub fn round_to_step(number: f64, step: f64, rounding_mode: RoundingMode) -> f64 {
+    let step = step.abs();
+    if step == 0.0 || !step.is_finite() {
+        return number;
+    }
+
+    /* Multiplication could add floating noise, result can not have more digits than the step */
+    let step_digits = (0..15_u8).find(|digits| float_round(step, *digits, RoundingMode::HalfUp) == step).unwrap_or(15);
+    let steps = round_number(do_divition(number, step), 0, rounding_mode);
+    round_number(do_math(steps, step, OperationType::Mul), step_digits, RoundingMode::HalfUp)
+}
+
+pub fn parse_number(number: &str) -> Option<f64> {
+    #[cfg(feature = "decimal")]
+    {
+        if let Ok(number) = Decimal::from_str(number) {
+            return number.to_f64();
+        }
+    }
+
+    number.parse::<f64>().ok()
 }
 
 pub fn parse_timezone<'t>(config: &SmartCalcConfig, capture: &regex::Captures<'t>) -> Option<(String, i32)> {
@@ -50,4 +209,39 @@ pub fn parse_timezone<'t>(config: &SmartCalcConfig, capture: &regex::Captures<'t
             None => None
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(all(test, feature = "decimal"))]
+#[test]
+fn decimal_math_test() {
+    assert_eq!(do_math(0.1, 0.2, OperationType::Add), 0.3);
+    assert_eq!(do_math(1.1, 1000.0, OperationType::Mul), 1100.0);
+    assert_eq!(do_math(0.3, 0.1, OperationType::Div), 3.0);
+    assert_eq!(do_math(10.0, 0.0, OperationType::Div), 0.0);
+    assert_eq!(do_math(1.1, 2.0, OperationType::Pow), 1.21);
+    assert!(do_math(10.0, 400.0, OperationType::Pow).is_infinite());
+    assert_eq!(convert_amount(100.0, 3.0, 3.0), 100.0);
+
+    let config = SmartCalcConfig::default();
+    let currency = config.get_currency("usd".to_string()).unwrap();
+    assert_eq!(round_converted_price(&config, convert_amount(10.0, 3.0, 1.0), &currency), 3.33);
+}
+
+#[cfg(test)]
+#[test]
+fn round_number_test() {
+    assert_eq!(round_number(2.5, 0, RoundingMode::HalfUp), 3.0);
+    assert_eq!(round_number(-2.5, 0, RoundingMode::HalfUp), -3.0);
+    assert_eq!(round_number(2.5, 0, RoundingMode::HalfEven), 2.0);
+    assert_eq!(round_number(3.5, 0, RoundingMode::HalfEven), 4.0);
+    assert_eq!(round_number(2.5, 0, RoundingMode::HalfDown), 2.0);
+    assert_eq!(round_number(2.6, 0, RoundingMode::HalfDown), 3.0);
+    assert_eq!(round_number(1.005, 2, RoundingMode::HalfUp), 1.01);
+    assert_eq!(round_number(12.345, 2, RoundingMode::HalfEven), 12.34);
+    assert_eq!(round_number(1.15, 2, RoundingMode::Truncate), 1.15);
+    assert_eq!(round_number(-1.159, 2, RoundingMode::Truncate), -1.15);
+    assert_eq!(round_number(1.151, 2, RoundingMode::Ceiling), 1.16);
+    assert_eq!(round_number(-1.159, 2, RoundingMode::Floor), -1.16);
+    assert_eq!(round_to_step(12.32, 0.05, RoundingMode::HalfUp), 12.3);
+    assert_eq!(round_to_step(12.33, 0.05, RoundingMode::HalfUp), 12.35);
+    assert_eq!(round_to_step(7.0, 5.0, RoundingMode::Ceiling), 10.0);
+}
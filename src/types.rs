@@ -14,19 +14,26 @@ use core::ops::Deref;
 use chrono::{NaiveDateTime, TimeZone};
 
 use serde_derive::{Deserialize, Serialize};
+use lazy_static::*;
+use regex::Regex;
 use alloc::collections::btree_map::BTreeMap;
 use chrono::{Duration, NaiveDate};
 use crate::compiler::DataItem;
 use crate::compiler::dynamic_type::DynamicTypeItem;
-use crate::config::DynamicType;
+use crate::compiler::list::ListItem;
+use crate::config::{DynamicType, CurrencyDisplay};
 use crate::config::SmartCalcConfig;
+use crate::error::SmartCalcError;
+use crate::session::Session;
 
 use crate::tokinizer::TokenInfoStatus;
 use crate::tokinizer::{TokenInfo, Tokinizer};
 use crate::variable::VariableInfo;
 
 pub type ExpressionFunc     = fn(config: &SmartCalcConfig, tokinizer: &Tokinizer, fields: &BTreeMap<String, Rc<TokenInfo>>) -> core::result::Result<TokenType, String>;
-pub type AstResult          = Result<SmartCalcAstType, (&'static str, u16, u16)>;
+pub type FunctionFunc       = fn(config: &SmartCalcConfig, arguments: &[Rc<dyn DataItem>]) -> core::result::Result<Rc<dyn DataItem>, SmartCalcError>;
+pub type SessionFunctionFunc = fn(config: &SmartCalcConfig, session: &Session, arguments: &[Rc<dyn DataItem>]) -> core::result::Result<Rc<dyn DataItem>, SmartCalcError>;
+pub type AstResult          = Result<SmartCalcAstType, SmartCalcError>;
 
 pub struct Money(pub f64, pub Rc<CurrencyInfo>);
 impl Money {
@@ -50,6 +57,7 @@ pub enum FieldType {
     Money(String),
     Percent(String),
     Number(String),
+    Fraction(String),
     Group(String, Vec<String>),
     TypeGroup(Vec<String>, String),
     Month(String),
@@ -71,6 +79,7 @@ impl FieldType {
             FieldType::Money(_) => "MONEY".to_string(),
             FieldType::Percent(_) => "PERCENT".to_string(),
             FieldType::Number(_) => "NUMBER".to_string(),
+            FieldType::Fraction(_) => "FRACTION".to_string(),
             FieldType::Group(_, _) => "GROUP".to_string(),
             FieldType::TypeGroup(_, _) => "TYPE_GROUP".to_string(),
             FieldType::Month(_) => "MONTH".to_string(),
@@ -88,6 +97,7 @@ impl PartialEq for FieldType {
             (FieldType::Timezone(l), FieldType::Timezone(r)) => r == l,
             (FieldType::Percent(l), FieldType::Percent(r)) => r == l,
             (FieldType::Number(l),  FieldType::Number(r)) => r == l,
+            (FieldType::Fraction(l),  FieldType::Fraction(r)) => r == l,
             (FieldType::Text(l, _),    FieldType::Text(r, _)) => r.to_lowercase() == l.to_lowercase(),
             (FieldType::Date(l),    FieldType::Date(r)) => r == l,
             (FieldType::DateTime(l),    FieldType::DateTime(r)) => r == l,
@@ -126,6 +136,48 @@ pub struct CurrencyInfo {
     pub decimal_digits: u8
 }
 
+lazy_static! {
+    static ref COUNTRY_PREFIXED_SYMBOL: Regex = Regex::new(r"^[A-Z]+(\p{Currency_Symbol}+)$").unwrap();
+}
+
+impl CurrencyInfo {
+    /* Country prefix is removed only from currency signs, "CA$" is "$" but "CHF", "Kč" and "S/." stay the same */
+    pub fn narrow_symbol(&self) -> &str {
+        match COUNTRY_PREFIXED_SYMBOL.captures(&self.symbol).and_then(|capture| capture.get(1)) {
+            Some(symbol) => symbol.as_str(),
+            None => &self.symbol
+        }
+    }
+}
+
+/// Currency rate given by a rate provider. Rate is the amount of the currency for one base currency
+/// and timestamp is the unix time of the rate.
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Serialize, Deserialize)]
+pub struct CurrencyRate {
+    pub currency: String,
+    pub base: String,
+    pub rate: f64,
+    pub timestamp: i64
+}
+
+/// Currency conversion made while calculating a line. Rate is the amount of `to` currency for one
+/// `from` currency, timestamp is the oldest rate used and it is not known for the built-in rates.
+/// Historical conversions use the rate of the requested date.
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Serialize, Deserialize)]
+pub struct CurrencyConversion {
+    pub from: String,
+    pub to: String,
+    pub rate: f64,
+    pub timestamp: Option<i64>,
+    pub historical: bool
+}
+
 
 use core::cmp::{
     PartialEq,
@@ -163,12 +215,17 @@ pub enum NumberType {
     Octal,
     Hexadecimal,
     Binary,
+    Scientific,
+    Engineering,
+    SignificantFigures(u8),
     Raw
 }
 
 #[derive(Debug, Clone)]
 pub enum TokenType {
     Number(f64, NumberType),
+    Integer(i128, NumberType),
+    Fraction(i64, i64),
     Text(String),
     Time(NaiveDateTime, TimeOffset),
     Date(NaiveDate, TimeOffset),
@@ -178,10 +235,14 @@ pub enum TokenType {
     Percent(f64),
     DynamicType(f64, Rc<DynamicType>),
     Money(f64, Rc<CurrencyInfo>),
+    DisplayMoney(f64, Rc<CurrencyInfo>, CurrencyDisplay),
     Variable(Rc<VariableInfo>),
     Month(u32),
     Duration(Duration),
-    Timezone(String, i32)
+    Timezone(String, i32),
+    Function(String),
+    Words(f64, Option<Rc<CurrencyInfo>>),
+    List(Vec<Rc<dyn DataItem>>)
 }
 
 
@@ -191,15 +252,21 @@ impl PartialEq for TokenType {
             (TokenType::Timezone(l_value, l_type),     TokenType::Timezone(r_value, r_type)) => *l_value == *r_value && *l_type == *r_type,
             (TokenType::Text(l_value),     TokenType::Text(r_value)) => l_value.to_lowercase() == r_value.to_lowercase(),
             (TokenType::Number(l_value, _),   TokenType::Number(r_value, _)) => l_value == r_value,
+            (TokenType::Integer(l_value, _),  TokenType::Integer(r_value, _)) => l_value == r_value,
+            (TokenType::Fraction(l_numerator, l_denominator), TokenType::Fraction(r_numerator, r_denominator)) => l_numerator == r_numerator && l_denominator == r_denominator,
             (TokenType::Percent(l_value),  TokenType::Percent(r_value)) => l_value == r_value,
             (TokenType::Operator(l_value), TokenType::Operator(r_value)) => l_value == r_value,
             (TokenType::Variable(l_value), TokenType::Variable(r_value)) => l_value == r_value,
             (TokenType::Money(l_value, l_symbol), TokenType::Money(r_value, r_symbol)) => l_value == r_value && l_symbol == r_symbol,
+            (TokenType::DisplayMoney(l_value, l_symbol, l_display), TokenType::DisplayMoney(r_value, r_symbol, r_display)) => l_value == r_value && l_symbol == r_symbol && l_display == r_display,
+            (TokenType::Words(l_value, l_symbol), TokenType::Words(r_value, r_symbol)) => l_value == r_value && l_symbol == r_symbol,
+            (TokenType::List(l_items), TokenType::List(r_items)) => ListItem(l_items.clone()).is_same(r_items),
             (TokenType::Time(l_value, l_tz),     TokenType::Time(r_value, r_tz)) => l_value == r_value && l_tz == r_tz,
             (TokenType::Month(l_value),     TokenType::Month(r_value)) => l_value == r_value,
             (TokenType::Duration(l_value),     TokenType::Duration(r_value)) => l_value == r_value,
             (TokenType::Date(l_value, l_tz),     TokenType::Date(r_value, r_tz)) => l_value == r_value && l_tz == r_tz,
             (TokenType::Field(l_value),    TokenType::Field(r_value)) => l_value.deref() == r_value.deref(),
+            (TokenType::Function(l_value), TokenType::Function(r_value)) => l_value == r_value,
             (_, _)  => false
         }
     }
@@ -210,6 +277,8 @@ impl ToString for TokenType {
         match &self {
             TokenType::DynamicType(number, dynamic_type) => dynamic_type.format.replace("{value}", &number.to_string()),
             TokenType::Number(number, _) => number.to_string(),
+            TokenType::Integer(number, _) => number.to_string(),
+            TokenType::Fraction(numerator, denominator) => format!("{}/{}", numerator, denominator),
             TokenType::Text(text) => text.to_string(),
             TokenType::Time(time, tz) => {
                 let tz_offset = chrono::FixedOffset::east(tz.offset * 60);
@@ -229,11 +298,17 @@ impl ToString for TokenType {
             TokenType::Operator(ch) => ch.to_string(),
             TokenType::Field(_) => "field".to_string(),
             TokenType::Percent(number) => format!("%{}", number),
-            TokenType::Money(price, currency) => format!("{} {}", price, currency.code.to_string()),
+            TokenType::Money(price, currency) | TokenType::DisplayMoney(price, currency, _) => format!("{} {}", price, currency.code.to_string()),
             TokenType::Variable(var) => var.to_string(),
             TokenType::Month(month) => month.to_string(),
             TokenType::Duration(duration) => duration.to_string(),
-            TokenType::Timezone(timezone, offset) => format!("{} {:?}", timezone, offset)
+            TokenType::Timezone(timezone, offset) => format!("{} {:?}", timezone, offset),
+            TokenType::Function(name) => name.to_string(),
+            TokenType::Words(number, currency) => match currency {
+                Some(currency) => format!("{} {}", number, currency.code),
+                None => number.to_string()
+            },
+            TokenType::List(items) => items.iter().map(|item| item.as_token_type().to_string()).collect::<Vec<_>>().join(", ")
         }
     }
 }
@@ -242,7 +317,8 @@ impl ToString for TokenType {
 impl TokenType {
     pub fn type_name(&self) -> String {
         match self {
-            TokenType::Number(_, _) => "NUMBER".to_string(),
+            TokenType::Number(_, _) | TokenType::Integer(_, _) => "NUMBER".to_string(),
+            TokenType::Fraction(_, _) => "FRACTION".to_string(),
             TokenType::Text(_) => "TEXT".to_string(),
             TokenType::Time(_, _) => "TIME".to_string(),
             TokenType::Date(_, _) => "DATE".to_string(),
@@ -250,12 +326,15 @@ impl TokenType {
             TokenType::Operator(_) => "OPERATOR".to_string(),
             TokenType::Field(_) => "FIELD".to_string(),
             TokenType::Percent(_) => "PERCENT".to_string(),
-            TokenType::Money(_, _) => "MONEY".to_string(),
+            TokenType::Money(_, _) | TokenType::DisplayMoney(_, _, _) => "MONEY".to_string(),
             TokenType::Variable(_) => "VARIABLE".to_string(),
             TokenType::Month(_) => "MONTH".to_string(),
             TokenType::Duration(_) => "DURATION".to_string(),
             TokenType::Timezone(_, _) => "TIMEZONE".to_string(),
-            TokenType::DynamicType(_, _) => "DYNAMIC_TYPE".to_string()
+            TokenType::DynamicType(_, _) => "DYNAMIC_TYPE".to_string(),
+            TokenType::Function(_) => "FUNCTION".to_string(),
+            TokenType::Words(_, _) => "WORDS".to_string(),
+            TokenType::List(_) => "LIST".to_string()
         }
     }
 
@@ -265,11 +344,14 @@ impl TokenType {
             (FieldType::Percent(_), TokenType::Percent(_)) => true,
             (FieldType::Timezone(_),  TokenType::Timezone(_, _)) => true,
             (FieldType::Number(_),  TokenType::Number(_, _)) => true,
+            (FieldType::Number(_),  TokenType::Integer(_, _)) => true,
+            (FieldType::Fraction(_),  TokenType::Fraction(_, _)) => true,
             (FieldType::Text(_, expected),    TokenType::Text(text) ) => expected.as_ref().map_or(true, |v| v.to_lowercase() == text.to_lowercase()),
             (FieldType::Time(_),    TokenType::Time(_, _)) => true,
             (FieldType::DateTime(_),    TokenType::DateTime(_, _)) => true,
             (FieldType::Date(_),    TokenType::Date(_, _)) => true,
             (FieldType::Money(_),   TokenType::Money(_, _)) => true,
+            (FieldType::Money(_),   TokenType::DisplayMoney(_, _, _)) => true,
             (FieldType::Month(_),   TokenType::Month(_)) => true,
             (FieldType::Duration(_),   TokenType::Duration(_)) => true,
             (FieldType::Group(_, items),   TokenType::Text(text)) => items.iter().any(|item| item.to_lowercase() == text.to_lowercase()),
@@ -284,10 +366,13 @@ impl TokenType {
                 (TokenType::Text(l_value), SmartCalcAstType::Symbol(r_value)) => l_value.deref().to_lowercase() == r_value.to_lowercase(),
                 (TokenType::Timezone(l_value, l_type), SmartCalcAstType::Item(r_value)) => r_value.is_same(&(l_value.clone(), *l_type)),
                 (TokenType::Number(l_value, _), SmartCalcAstType::Item(r_value)) => r_value.is_same(l_value),
+                (TokenType::Integer(l_value, _), SmartCalcAstType::Item(r_value)) => r_value.is_same(l_value),
+                (TokenType::Fraction(l_numerator, l_denominator), SmartCalcAstType::Item(r_value)) => r_value.is_same(&(*l_numerator, *l_denominator)),
                 (TokenType::Percent(l_value), SmartCalcAstType::Item(r_value)) => r_value.is_same(l_value),
                 (TokenType::Duration(l_value), SmartCalcAstType::Item(r_value)) => r_value.is_same(l_value),
                 (TokenType::Time(l_value, l_tz), SmartCalcAstType::Item(r_value)) => r_value.is_same(&(*l_value, l_tz.clone())),
                 (TokenType::Money(l_value, l_symbol), SmartCalcAstType::Item(r_value)) => r_value.is_same(&(*l_value, l_symbol.clone())),
+                (TokenType::DisplayMoney(l_value, l_symbol, _), SmartCalcAstType::Item(r_value)) => r_value.is_same(&(*l_value, l_symbol.clone())),
                 (TokenType::Date(l_value, l_tz), SmartCalcAstType::Item(r_value)) => r_value.is_same(&(*l_value, l_tz.clone())),
                 (TokenType::Field(l_value), _) => right.field_compare(l_value.deref()),
                 (_, _) => false
@@ -306,6 +391,7 @@ impl TokenType {
                 FieldType::Money(field_name)   => Some(field_name.to_string()),
                 FieldType::Percent(field_name) => Some(field_name.to_string()),
                 FieldType::Number(field_name)  => Some(field_name.to_string()),
+                FieldType::Fraction(field_name)  => Some(field_name.to_string()),
                 FieldType::Month(field_name)  => Some(field_name.to_string()),
                 FieldType::Duration(field_name)  => Some(field_name.to_string()),
                 FieldType::Group(field_name, _)  => Some(field_name.to_string()),
@@ -354,12 +440,17 @@ impl core::cmp::PartialEq<TokenType> for TokenInfo {
             Some(l_token) => match (&l_token, &other) {
                 (TokenType::Text(l_value), TokenType::Text(r_value)) => l_value.to_lowercase() == r_value.to_lowercase(),
                 (TokenType::Number(l_value, _),   TokenType::Number(r_value, _)) => l_value == r_value,
+                (TokenType::Integer(l_value, _),  TokenType::Integer(r_value, _)) => l_value == r_value,
+                (TokenType::Fraction(l_numerator, l_denominator), TokenType::Fraction(r_numerator, r_denominator)) => l_numerator == r_numerator && l_denominator == r_denominator,
                 (TokenType::Percent(l_value),  TokenType::Percent(r_value)) => l_value == r_value,
                 (TokenType::Operator(l_value), TokenType::Operator(r_value)) => l_value == r_value,
                 (TokenType::Date(l_value, l_tz), TokenType::Date(r_value, r_tz)) => l_value == r_value && l_tz == r_tz,
                 (TokenType::Duration(l_value), TokenType::Duration(r_value)) => l_value == r_value,
                 (TokenType::Month(l_value), TokenType::Month(r_value)) => l_value == r_value,
                 (TokenType::Money(l_value, l_symbol), TokenType::Money(r_value, r_symbol)) => l_value == r_value && l_symbol == r_symbol,
+                (TokenType::DisplayMoney(l_value, l_symbol, l_display), TokenType::DisplayMoney(r_value, r_symbol, r_display)) => l_value == r_value && l_symbol == r_symbol && l_display == r_display,
+                (TokenType::Words(l_value, l_symbol), TokenType::Words(r_value, r_symbol)) => l_value == r_value && l_symbol == r_symbol,
+                (TokenType::List(l_items), TokenType::List(r_items)) => ListItem(l_items.clone()).is_same(r_items),
                 (TokenType::Timezone(l_value, l_symbol), TokenType::Timezone(r_value, r_symbol)) => l_value == r_value && l_symbol == r_symbol,
                 (TokenType::Variable(l_value), TokenType::Variable(r_value)) => l_value == r_value,
                 (TokenType::Field(l_value), _) => other.field_compare(l_value.deref()),
@@ -385,11 +476,16 @@ impl PartialEq for TokenInfo {
             (Some(l_token), Some(r_token)) => match (&l_token, &r_token) {
                 (TokenType::Text(l_value), TokenType::Text(r_value)) => l_value.to_lowercase() == r_value.to_lowercase(),
                 (TokenType::Number(l_value, _),   TokenType::Number(r_value, _)) => l_value == r_value,
+                (TokenType::Integer(l_value, _),  TokenType::Integer(r_value, _)) => l_value == r_value,
+                (TokenType::Fraction(l_numerator, l_denominator), TokenType::Fraction(r_numerator, r_denominator)) => l_numerator == r_numerator && l_denominator == r_denominator,
                 (TokenType::Percent(l_value),  TokenType::Percent(r_value)) => l_value == r_value,
                 (TokenType::Operator(l_value), TokenType::Operator(r_value)) => l_value == r_value,
                 (TokenType::Date(l_value, l_tz), TokenType::Date(r_value, r_tz)) => l_value == r_value && l_tz == r_tz,
                 (TokenType::Duration(l_value), TokenType::Duration(r_value)) => l_value == r_value,
                 (TokenType::Money(l_value, l_symbol), TokenType::Money(r_value, r_symbol)) => l_value == r_value && l_symbol == r_symbol,
+                (TokenType::DisplayMoney(l_value, l_symbol, l_display), TokenType::DisplayMoney(r_value, r_symbol, r_display)) => l_value == r_value && l_symbol == r_symbol && l_display == r_display,
+                (TokenType::Words(l_value, l_symbol), TokenType::Words(r_value, r_symbol)) => l_value == r_value && l_symbol == r_symbol,
+                (TokenType::List(l_items), TokenType::List(r_items)) => ListItem(l_items.clone()).is_same(r_items),
                 (TokenType::Timezone(l_value, l_symbol), TokenType::Timezone(r_value, r_symbol)) => l_value == r_value && l_symbol == r_symbol,
                 (TokenType::Variable(l_value), TokenType::Variable(r_value)) => l_value == r_value,
                 (TokenType::Field(l_value), _) => r_token.field_compare(l_value.deref()),
@@ -435,7 +531,11 @@ pub enum SmartCalcAstType {
         expression: Rc<SmartCalcAstType>
     },
     Symbol(String),
-    Variable(Rc<VariableInfo>)
+    Variable(Rc<VariableInfo>),
+    Call {
+        name: String,
+        args: Vec<Rc<SmartCalcAstType>>
+    }
 }
 
 impl SmartCalcAstType {
@@ -456,7 +556,11 @@ impl SmartCalcAstType {
                 expression: _
             } => "ASSIGNMENT".to_string(),
             SmartCalcAstType::Symbol(_) => "SYMBOL".to_string(),
-            SmartCalcAstType::Variable(variable) => variable.data.borrow().type_name()
+            SmartCalcAstType::Variable(variable) => variable.data.borrow().type_name(),
+            SmartCalcAstType::Call {
+                name: _,
+                args: _
+            } => "CALL".to_string()
         }
     }
 
@@ -468,6 +572,7 @@ impl SmartCalcAstType {
             }),
             (FieldType::Percent(_), SmartCalcAstType::Item(item)) => item.type_name() == "PERCENT",
             (FieldType::Number(_), SmartCalcAstType::Item(item)) => item.type_name() == "NUMBER",
+            (FieldType::Fraction(_), SmartCalcAstType::Item(item)) => item.type_name() == "FRACTION",
             (FieldType::Text(_, expected), SmartCalcAstType::Symbol(symbol)) => expected.as_ref().map_or(true, |v| v.to_lowercase() == symbol.to_lowercase()),
             (FieldType::Time(_), SmartCalcAstType::Item(item)) => item.type_name() == "TIME",
             (FieldType::Money(_),   SmartCalcAstType::Item(item)) => item.type_name() == "MONEY",